@@ -1,25 +1,22 @@
-use std::fs;
+use aoc_common::parse::{Lines, ParseError};
 
-fn main() {
-    let data = fs::read_to_string("input.txt")
-        .expect("Could not read input.txt");
-    let data = data.trim();
-
-    let mut highest_total = 0;
-    let mut current_total = 0;
-    for line in data.split("\n") {
-        if line == "" {
+/// The calories of each item each elf is carrying.
+pub fn parse(data: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+    let mut elves = Vec::new();
+    let mut food = Vec::new();
+    for mut line in Lines::new(data) {
+        if line.is_empty() {
             // we've reached the end of an elf's food
-            if current_total > highest_total {
-                highest_total = current_total;
-            }
-            current_total = 0;
+            elves.push(std::mem::take(&mut food));
         } else {
-            current_total += line.parse::<u32>().unwrap();
+            food.push(line.number()?);
+            line.end()?;
         }
     }
-    if current_total > highest_total {
-        highest_total = current_total;
-    }
-    println!("{}", highest_total);
+    elves.push(food);
+    Ok(elves)
+}
+
+pub fn process(elves: &[Vec<u32>]) -> u32 {
+    elves.iter().map(|food| food.iter().sum()).max().unwrap_or(0)
 }
@@ -1,25 +1,42 @@
 use std::collections::HashMap;
-use std::fs;
 
-fn process(data: &str) -> i32 {
+use aoc_common::parse::{lines, ParseError};
+
+#[derive(Clone, Copy, Debug)]
+pub enum Instruction {
+    Noop,
+    Addx(i32),
+}
+
+pub fn parse(data: &str) -> Result<Vec<Instruction>, ParseError> {
+    lines(data, |line| {
+        if line.eat("noop") {
+            Ok(Instruction::Noop)
+        } else if line.eat("addx ") {
+            Ok(Instruction::Addx(line.number()?))
+        } else {
+            Err(line.error("`noop` or `addx`"))
+        }
+    })
+}
+
+pub fn process(program: &[Instruction]) -> i32 {
     let mut current_cycle = 1;
     let mut accumulator = 1;
     let mut value_at_cycle = HashMap::new();
-    for line in data.split("\n") {
-        let instruction: Vec<&str> = line.split(" ").collect();
-        match instruction[0] {
-            "noop" => {
+    for &instruction in program {
+        match instruction {
+            Instruction::Noop => {
                 value_at_cycle.insert(current_cycle, accumulator);
                 current_cycle += 1;
             }
-            "addx" => {
+            Instruction::Addx(value) => {
                 value_at_cycle.insert(current_cycle, accumulator);
                 current_cycle += 1;
                 value_at_cycle.insert(current_cycle, accumulator);
                 current_cycle += 1;
-                accumulator += instruction[1].parse::<i32>().unwrap();
+                accumulator += value;
             }
-            _ => panic!("Unknown instruction {}", instruction[0]),
         }
     }
     let mut total_signal_strength = 0;
@@ -29,12 +46,6 @@ fn process(data: &str) -> i32 {
     total_signal_strength
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-    println!("{}", process(data));
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -188,6 +199,14 @@ noop";
 
     #[test]
     fn test() {
-        assert!(process(DATA) == 13140);
+        assert!(process(&parse(DATA).unwrap()) == 13140);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("noop\nsubx 3").unwrap_err(),
+            ParseError::new(2, 1, "`noop` or `addx`", "`subx`")
+        );
     }
 }
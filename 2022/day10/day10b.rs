@@ -1,13 +1,12 @@
-use std::fs;
+use crate::day10::Instruction;
 
-fn process(data: &str) -> String {
+pub fn process(program: &[Instruction]) -> String {
     let mut current_cycle = 0; // This is a bit different from the docs, but zero-indexing here makes things easier later
     let mut sprite_position: i32 = 1;
     let mut output = String::new();
-    for line in data.split("\n") {
-        let instruction: Vec<&str> = line.split(" ").collect();
-        match instruction[0] {
-            "noop" => {
+    for &instruction in program {
+        match instruction {
+            Instruction::Noop => {
                 output.push(if (sprite_position - (current_cycle % 40)).abs() <= 1 {
                     '#'
                 } else {
@@ -18,7 +17,7 @@ fn process(data: &str) -> String {
                     output.push('\n');
                 }
             }
-            "addx" => {
+            Instruction::Addx(value) => {
                 output.push(if (sprite_position - (current_cycle % 40)).abs() <= 1 {
                     '#'
                 } else {
@@ -37,23 +36,17 @@ fn process(data: &str) -> String {
                 if current_cycle % 40 == 0 {
                     output.push('\n');
                 }
-                sprite_position += instruction[1].parse::<i32>().unwrap();
+                sprite_position += value;
             }
-            _ => panic!("Unknown instruction {}", instruction[0]),
         }
     }
     output.trim().to_string()
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-    println!("{}", process(data));
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::day10::parse;
 
     static DATA: &str = "addx 15
 addx -11
@@ -204,16 +197,14 @@ noop";
 
     #[test]
     fn test() {
-        println!("{}", process(DATA));
-        assert!(
-            process(DATA)
-                == "##..##..##..##..##..##..##..##..##..##..
+        assert_eq!(
+            process(&parse(DATA).unwrap()),
+            "##..##..##..##..##..##..##..##..##..##..
 ###...###...###...###...###...###...###.
 ####....####....####....####....####....
 #####.....#####.....#####.....#####.....
 ######......######......######......####
 #######.......#######.......#######....."
-                    .to_string()
         );
     }
 }
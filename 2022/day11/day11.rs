@@ -1,58 +1,107 @@
 use std::collections::VecDeque;
-use std::fs;
 
-#[derive(Eq, PartialEq, Debug)]
-struct Monkey {
+use aoc_common::parse::{Lines, ParseError};
+use aoc_common::Part;
+
+/// How a monkey changes the worry level, as in "new = old * 3".
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+enum Operation {
+    Add(usize),
+    Multiply(usize),
+    Square,
+}
+
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub struct Monkey {
     starting_items: VecDeque<usize>,
-    operation: Vec<String>,
+    operation: Operation,
     test_divisible_by_cond: usize,
     target_if_test: usize,
     target_if_not_test: usize,
     inspected_item_count: usize,
 }
 
-fn update_worry_level(operation: &Vec<String>, level: usize) -> usize {
-    if operation[4] == "old" {
-        // new = old * old
-        level * level
-    } else {
-        match operation[3].as_str() {
-            "*" => level * operation[4].parse::<usize>().unwrap(),
-            "+" => level + operation[4].parse::<usize>().unwrap(),
-            _ => panic!("Unknown operator {}", operation[3]),
-        }
+fn update_worry_level(operation: Operation, level: usize) -> usize {
+    match operation {
+        Operation::Add(operand) => level + operand,
+        Operation::Multiply(operand) => level * operand,
+        Operation::Square => level * level,
     }
 }
 
-fn parse(data: &str) -> Vec<Monkey> {
+// The number at the end of a line like "  Test: divisible by 23".
+fn parse_field(lines: &mut Lines, prefix: &str) -> Result<usize, ParseError> {
+    let mut line = lines.next_line()?;
+    line.expect(prefix)?;
+    let value = line.number()?;
+    line.end()?;
+    Ok(value)
+}
+
+pub fn parse(data: &str) -> Result<Vec<Monkey>, ParseError> {
     let mut monkeys = Vec::new();
-    for monkey_data in data.split("\n\n") {
-        let lines: Vec<&str> = monkey_data.split("\n").collect();
+    let mut lines = Lines::new(data);
+    while let Some(mut line) = lines.next() {
+        if line.is_empty() {
+            continue;
+        }
+        line.expect("Monkey ")?;
+        line.number::<usize>()?;
+        line.expect(":")?;
+        line.end()?;
+
+        let mut line = lines.next_line()?;
+        line.expect("  Starting items: ")?;
+        let starting_items = line.list(", ", |item| item.number())?.into();
+        line.end()?;
+
+        let mut line = lines.next_line()?;
+        line.expect("  Operation: new = old ")?;
+        let operator = if line.eat("*") {
+            '*'
+        } else if line.eat("+") {
+            '+'
+        } else {
+            return Err(line.error("`*` or `+`"));
+        };
+        line.expect(" ")?;
+        let start = line.clone();
+        let operation = match (operator, line.eat("old")) {
+            ('*', true) => Operation::Square,
+            ('*', false) => Operation::Multiply(line.number()?),
+            (_, false) => Operation::Add(line.number()?),
+            // "old + old" would need its own operation, and no monkey does it
+            (_, true) => return Err(start.error("a number")),
+        };
+        line.end()?;
+
         monkeys.push(Monkey {
-            starting_items: lines[1][18..]
-                .split(", ")
-                .map(|i| i.parse().unwrap())
-                .collect(),
-            operation: lines[2][13..].split(" ").map(|s| s.to_string()).collect(),
-            test_divisible_by_cond: lines[3][21..].parse().unwrap(),
-            target_if_test: lines[4][29..].parse().unwrap(),
-            target_if_not_test: lines[5][30..].parse().unwrap(),
+            starting_items,
+            operation,
+            test_divisible_by_cond: parse_field(&mut lines, "  Test: divisible by ")?,
+            target_if_test: parse_field(&mut lines, "    If true: throw to monkey ")?,
+            target_if_not_test: parse_field(&mut lines, "    If false: throw to monkey ")?,
             inspected_item_count: 0,
         });
     }
-    monkeys
+    Ok(monkeys)
 }
 
-fn process(monkeys: &mut Vec<Monkey>, rounds: usize) -> usize {
+pub fn process(monkeys: &mut Vec<Monkey>, rounds: usize, part: Part) -> usize {
     let test_product: usize = monkeys.iter().map(|m| m.test_divisible_by_cond).product();
     for _round in 0..rounds {
         for i in 0..monkeys.len() {
             while monkeys[i].starting_items.len() > 0 {
                 monkeys[i].inspected_item_count += 1;
                 let mut item = monkeys[i].starting_items.pop_front().unwrap();
-                item = update_worry_level(&monkeys[i].operation, item);
-                // item /= 3; // part 1 only
-                item %= test_product; // part 2 only
+                item = update_worry_level(monkeys[i].operation, item);
+                match part {
+                    // Relief that the item wasn't damaged
+                    Part::One => item /= 3,
+                    // Without relief the levels grow without bound, but all
+                    // the tests still agree modulo their product
+                    Part::Two => item %= test_product,
+                }
                 let target_monkey = if item % monkeys[i].test_divisible_by_cond == 0 {
                     monkeys[i].target_if_test
                 } else {
@@ -67,13 +116,6 @@ fn process(monkeys: &mut Vec<Monkey>, rounds: usize) -> usize {
     access_counts[0] * access_counts[1]
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-    let mut monkeys = parse(data);
-    println!("{}", process(&mut monkeys, 10000)); // 20 for part 1
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -114,14 +156,11 @@ Monkey 3:
   Test: divisible by 11
     If true: throw to monkey 7
     If false: throw to monkey 2";
-        let monkeys = parse(data);
+        let monkeys = parse(data).unwrap();
         println!("{:?}", monkeys);
         let goal_monkeys = vec![Monkey {
             starting_items: VecDeque::from([75, 63]),
-            operation: vec!["new", "=", "old", "*", "3"]
-                .iter()
-                .map(|s| s.to_string())
-                .collect(),
+            operation: Operation::Multiply(3),
             test_divisible_by_cond: 11,
             target_if_test: 7,
             target_if_not_test: 2,
@@ -131,7 +170,39 @@ Monkey 3:
         assert!(goal_monkeys == monkeys);
     }
     #[test]
-    fn test() {
+    fn test_parse_errors() {
+        let data = "Monkey 0:\n  Starting items: 75, x3";
+        assert_eq!(
+            parse(data),
+            Err(ParseError::new(2, 23, "a number", "`x3`"))
+        );
+        let data = "Monkey 0:\n  Starting items: 75\n  Operation: new = old / 3";
+        assert_eq!(
+            parse(data),
+            Err(ParseError::new(3, 24, "`*` or `+`", "`/`"))
+        );
+        let data = "Monkey 0:\n  Starting items: 75\n  Operation: new = old + old";
+        assert_eq!(
+            parse(data),
+            Err(ParseError::new(3, 26, "a number", "`old`"))
+        );
+        let data = "Monkey 0:\n  Starting items: 75\n  Operation: new = old * 3";
+        assert_eq!(
+            parse(data),
+            Err(ParseError::new(4, 1, "another line", "end of input"))
+        );
+    }
+
+    #[test]
+    fn test_part1() {
+        let mut monkeys = parse(DATA).unwrap();
+        assert_eq!(process(&mut monkeys, 20, Part::One), 10605);
+        let inspected: Vec<usize> = monkeys.iter().map(|m| m.inspected_item_count).collect();
+        assert_eq!(inspected, vec![101, 95, 7, 105]);
+    }
+
+    #[test]
+    fn test_part2() {
         let desired_results = vec![
             (1, 4 * 6),
             (20, 99 * 103),
@@ -147,8 +218,8 @@ Monkey 3:
             (10000, 52166 * 52013),
         ];
         for (rounds, desired_result) in desired_results {
-            let mut monkeys = parse(DATA);
-            let result = process(&mut monkeys, rounds);
+            let mut monkeys = parse(DATA).unwrap();
+            let result = process(&mut monkeys, rounds, Part::Two);
             println!("{} == {}", result, desired_result);
             assert!(result == desired_result);
         }
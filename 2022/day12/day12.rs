@@ -1,80 +1,42 @@
-use std::collections::VecDeque;
-use std::fs;
+use aoc_common::grid::{Grid, Point};
+use aoc_common::parse::ParseError;
+use aoc_common::pathfinding;
 
-fn parse(data: &str) -> (Vec<Vec<i8>>, (usize, usize), (usize, usize)) {
+/// Elevations, then the start and end positions.
+pub type Heightmap = (Grid<i8>, Point, Point);
+
+pub fn parse(data: &str) -> Result<Heightmap, ParseError> {
     let mut start = None;
     let mut end = None;
-    let mut elevations = Vec::new();
-    for (i, line) in data.split("\n").enumerate() {
-        let mut row = Vec::new();
-        for (j, ch) in line.chars().enumerate() {
-            if ch == 'S' {
-                start = Some((i, j));
-                row.push(1);
-            } else if ch == 'E' {
-                end = Some((i, j));
-                row.push(26);
-            } else {
-                row.push(ch as i8 - 'a' as i8);
-            }
-        }
-        elevations.push(row);
-    }
-    (elevations, start.unwrap(), end.unwrap())
-}
-
-fn process(data: &str) -> u32 {
-    let (elevations, start, end) = parse(data);
-    let map_height = elevations.len();
-    let map_width = elevations[0].len();
-    let mut costs = vec![vec![u32::MAX; map_width]; map_height];
-    costs[start.0][start.1] = 0;
-
-    let mut boundaries = VecDeque::new();
-    boundaries.push_back(start);
-
-    while !boundaries.is_empty() {
-        let (x, y) = boundaries.pop_front().unwrap();
-        let boundary_cost = costs[x][y];
-
-        if x > 0 && elevations[x - 1][y] - elevations[x][y] >= 1 {
-            let above_boundary_cost = &mut costs[x - 1][y];
-            if *above_boundary_cost > boundary_cost + 1 {
-                *above_boundary_cost = boundary_cost + 1;
-                boundaries.push_back((x - 1, y));
-            }
-        }
-        if x < map_height - 1 && elevations[x + 1][y] - elevations[x][y] <= 1 {
-            let below_boundary_cost = &mut costs[x + 1][y];
-            if *below_boundary_cost > boundary_cost + 1 {
-                *below_boundary_cost = boundary_cost + 1;
-                boundaries.push_back((x + 1, y));
-            }
-        }
-        if y > 0 && elevations[x][y - 1] - elevations[x][y] <= 1 {
-            let left_boundary_cost = &mut costs[x][y - 1];
-            if *left_boundary_cost > boundary_cost + 1 {
-                *left_boundary_cost = boundary_cost + 1;
-                boundaries.push_back((x, y - 1));
-            }
-        }
-        if y < map_width - 1 && elevations[x][y + 1] - elevations[x][y] <= 1 {
-            let right_boundary_cost = &mut costs[x][y + 1];
-            if *right_boundary_cost > boundary_cost + 1 {
-                *right_boundary_cost = boundary_cost + 1;
-                boundaries.push_back((x, y + 1));
-            }
+    let elevations = Grid::parse(data, "an elevation from `a` to `z`", |point, ch| {
+        if ch == 'S' {
+            start = Some(point);
+            Some(1)
+        } else if ch == 'E' {
+            end = Some(point);
+            Some(26)
+        } else if ch.is_ascii_lowercase() {
+            Some(ch as i8 - 'a' as i8)
+        } else {
+            None
         }
-    }
-
-    costs[end.0][end.1]
+    })?;
+    let missing = |what| ParseError::new(data.split("\n").count() + 1, 1, what, "end of input");
+    let start = start.ok_or_else(|| missing("a start `S`"))?;
+    let end = end.ok_or_else(|| missing("an end `E`"))?;
+    Ok((elevations, start, end))
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-
-    println!("{}", process(data));
+pub fn process((elevations, start, end): &Heightmap) -> u32 {
+    // Each step can climb at most one higher
+    let climbable = |from: Point| {
+        let from_elevation = elevations[from];
+        elevations
+            .neighbors4(from)
+            .filter(move |&to| elevations[to] - from_elevation <= 1)
+    };
+    let path = pathfinding::bfs(*start, |&point| climbable(point), |point| point == end);
+    path.map_or(u32::MAX, |path| path.cost as u32)
 }
 
 #[cfg(test)]
@@ -88,6 +50,6 @@ abcryxxl
 accszExk
 acctuvwj
 abdefghi";
-        assert_eq!(process(data), 31);
+        assert_eq!(process(&parse(data).unwrap()), 31);
     }
 }
@@ -1,93 +1,29 @@
-use std::collections::VecDeque;
-use std::fs;
-
-fn parse(data: &str) -> (Vec<Vec<i8>>, (usize, usize), (usize, usize)) {
-    let mut start = None;
-    let mut end = None;
-    let mut elevations = Vec::new();
-    for (i, line) in data.split("\n").enumerate() {
-        let mut row = Vec::new();
-        for (j, ch) in line.chars().enumerate() {
-            if ch == 'S' {
-                start = Some((i, j));
-                row.push(1);
-            } else if ch == 'E' {
-                end = Some((i, j));
-                row.push(26);
-            } else {
-                row.push(ch as i8 - 'a' as i8);
-            }
-        }
-        elevations.push(row);
-    }
-    (elevations, start.unwrap(), end.unwrap())
-}
-
-fn process(data: &str) -> u32 {
-    let (elevations, _, end) = parse(data);
-    let map_height = elevations.len();
-    let map_width = elevations[0].len();
-    let mut costs = vec![vec![u32::MAX; map_width]; map_height];
-    costs[end.0][end.1] = 0;
-
-    let mut boundaries = VecDeque::new();
-    boundaries.push_back(end);
-
-    while !boundaries.is_empty() {
-        let (x, y) = boundaries.pop_front().unwrap();
-        let boundary_cost = costs[x][y];
-
-        if x > 0 && elevations[x][y] - elevations[x - 1][y] <= 1 {
-            let above_boundary_cost = &mut costs[x - 1][y];
-            if *above_boundary_cost > boundary_cost + 1 {
-                *above_boundary_cost = boundary_cost + 1;
-                boundaries.push_back((x - 1, y));
-            }
-        }
-        if x < map_height - 1 && elevations[x][y] - elevations[x + 1][y] <= 1 {
-            let below_boundary_cost = &mut costs[x + 1][y];
-            if *below_boundary_cost > boundary_cost + 1 {
-                *below_boundary_cost = boundary_cost + 1;
-                boundaries.push_back((x + 1, y));
-            }
-        }
-        if y > 0 && elevations[x][y] - elevations[x][y - 1] <= 1 {
-            let left_boundary_cost = &mut costs[x][y - 1];
-            if *left_boundary_cost > boundary_cost + 1 {
-                *left_boundary_cost = boundary_cost + 1;
-                boundaries.push_back((x, y - 1));
-            }
-        }
-        if y < map_width - 1 && elevations[x][y] - elevations[x][y + 1] <= 1 {
-            let right_boundary_cost = &mut costs[x][y + 1];
-            if *right_boundary_cost > boundary_cost + 1 {
-                *right_boundary_cost = boundary_cost + 1;
-                boundaries.push_back((x, y + 1));
-            }
-        }
-    }
-
-    let mut cheapest_coord_cost = u32::MAX;
-    for (i, row) in elevations.iter().enumerate() {
-        for (j, elevation) in row.iter().enumerate() {
-            if *elevation == 0 && costs[i][j] < cheapest_coord_cost {
-                cheapest_coord_cost = costs[i][j];
-            }
-        }
-    }
-    cheapest_coord_cost
-}
-
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-
-    println!("{}", process(data));
+use aoc_common::grid::Point;
+use aoc_common::pathfinding;
+
+use crate::day12::Heightmap;
+
+// Searches backwards from the end for the nearest lowest point, so steps go
+// down at most one at a time rather than up
+pub fn process((elevations, _, end): &Heightmap) -> u32 {
+    let descendable = |from: Point| {
+        let from_elevation = elevations[from];
+        elevations
+            .neighbors4(from)
+            .filter(move |&to| from_elevation - elevations[to] <= 1)
+    };
+    let path = pathfinding::bfs(
+        *end,
+        |&point| descendable(point),
+        |&point| elevations[point] == 0,
+    );
+    path.map_or(u32::MAX, |path| path.cost as u32)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::day12::parse;
 
     #[test]
     fn test_small_input() {
@@ -96,6 +32,6 @@ abcryxxl
 accszExk
 acctuvwj
 abdefghi";
-        assert_eq!(process(data), 29);
+        assert_eq!(process(&parse(data).unwrap()), 29);
     }
 }
@@ -1,8 +1,9 @@
 use std::cmp::{min, Ordering};
-use std::fs;
+
+use aoc_common::parse::{Lines, ParseError, Scanner};
 
 #[derive(Debug, PartialEq, Eq, Clone)]
-enum List {
+pub enum List {
     Integer(u8),
     List(Vec<List>),
 }
@@ -44,54 +45,49 @@ impl PartialOrd for List {
     }
 }
 
-fn parse(data: &str) -> List {
-    let mut data = &data[1..data.len() - 1]; // Strip square brackets
+fn parse_list(line: &mut Scanner) -> Result<List, ParseError> {
+    line.expect("[")?;
     let mut packet = Vec::new();
-    while data.len() > 0 {
-        match data.chars().next().unwrap() {
-            '[' => {
-                // next element is a list
-                let mut list_str = String::new();
-                let mut depth = 0;
-                loop {
-                    list_str.push(data.chars().next().unwrap());
-                    match data.chars().next().unwrap() {
-                        '[' => depth += 1,
-                        ']' => depth -= 1,
-                        _ => (),
-                    };
-                    data = &data[1..];
-                    if depth == 0 {
-                        break;
-                    }
-                }
-                packet.push(parse(&list_str));
+    if !line.eat("]") {
+        loop {
+            if line.remaining().starts_with('[') {
+                packet.push(parse_list(line)?);
+            } else {
+                packet.push(List::Integer(line.number()?));
             }
-            ',' => data = &data[1..],
-            _ => {
-                // next element is an integer
-                let mut num = String::new();
-                while data.len() > 0 {
-                    match data.chars().next().unwrap() {
-                        ',' => break,
-                        c => num.push(c), // TODO
-                    };
-                    data = &data[1..];
-                }
-                packet.push(List::Integer(num.parse().unwrap()));
+            if line.eat("]") {
+                break;
             }
+            if !line.eat(",") {
+                return Err(line.error("`,` or `]`"));
+            }
+        }
+    }
+    Ok(List::List(packet))
+}
+
+fn parse_packet(mut line: Scanner) -> Result<List, ParseError> {
+    let packet = parse_list(&mut line)?;
+    line.end()?;
+    Ok(packet)
+}
+
+pub fn parse(data: &str) -> Result<Vec<(List, List)>, ParseError> {
+    let mut pairs = Vec::new();
+    let mut lines = Lines::new(data);
+    while let Some(line) = lines.next() {
+        if line.is_empty() {
+            continue;
         }
+        pairs.push((parse_packet(line)?, parse_packet(lines.next_line()?)?));
     }
-    List::List(packet)
+    Ok(pairs)
 }
 
-fn process_part1(data: &str) -> usize {
+pub fn process_part1(pairs: &[(List, List)]) -> usize {
     let mut sum = 0;
-    for (index, packet_pair) in data.split("\n\n").enumerate() {
-        let packets: Vec<&str> = packet_pair.split("\n").collect();
-        let zeroth = parse(packets[0]);
-        let first = parse(packets[1]);
-        match zeroth.cmp(&first) {
+    for (index, (zeroth, first)) in pairs.iter().enumerate() {
+        match zeroth.cmp(first) {
             Ordering::Less => sum += index + 1,
             Ordering::Greater => (),
             Ordering::Equal => panic!("{:?} and {:?} are equal", zeroth, first),
@@ -100,14 +96,13 @@ fn process_part1(data: &str) -> usize {
     sum
 }
 
-fn process_part2(data: &str) -> usize {
-    let mut packets: Vec<List> = data
-        .split("\n")
-        .filter(|p| p != &"")
-        .map(|p| parse(p))
+pub fn process_part2(pairs: &[(List, List)]) -> usize {
+    let mut packets: Vec<List> = pairs
+        .iter()
+        .flat_map(|(zeroth, first)| [zeroth.clone(), first.clone()])
         .collect();
-    let zeroth_divider = parse("[[2]]");
-    let first_divider = parse("[[6]]");
+    let zeroth_divider = List::List(vec![List::List(vec![List::Integer(2)])]);
+    let first_divider = List::List(vec![List::List(vec![List::Integer(6)])]);
     packets.push(zeroth_divider.clone());
     packets.push(first_divider.clone());
     packets.sort();
@@ -120,13 +115,6 @@ fn process_part2(data: &str) -> usize {
     product
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-    println!("{}", process_part1(data));
-    println!("{}", process_part2(data));
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -158,16 +146,37 @@ mod test {
     #[test]
     fn test_flat() {
         let data = "[1,1,3,1,1]\n[1,1,5,1,1]";
-        assert_eq!(process_part1(data), 1);
+        assert_eq!(process_part1(&parse(data).unwrap()), 1);
+    }
+
+    #[test]
+    fn test_parse() {
+        let pairs = parse("[[],10]\n[3]").unwrap();
+        let empty = List::List(vec![]);
+        assert_eq!(
+            pairs,
+            vec![(
+                List::List(vec![empty, List::Integer(10)]),
+                List::List(vec![List::Integer(3)])
+            )]
+        );
+        assert_eq!(
+            parse("[1,2]\n[3;4]"),
+            Err(ParseError::new(2, 3, "`,` or `]`", "`;4]`"))
+        );
+        assert_eq!(
+            parse("[1,2]\n[3]]"),
+            Err(ParseError::new(2, 4, "end of line", "`]`"))
+        );
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(process_part1(DATA), 13);
+        assert_eq!(process_part1(&parse(DATA).unwrap()), 13);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(process_part2(DATA), 140);
+        assert_eq!(process_part2(&parse(DATA).unwrap()), 140);
     }
 }
@@ -1,25 +1,36 @@
 use std::cmp::{max, min};
-use std::fs;
 
-fn format_map(map: &Vec<Vec<char>>, offset: usize) -> String {
+use aoc_common::grid::Grid;
+use aoc_common::parse::{lines, ParseError};
+
+#[cfg(test)]
+fn format_map(map: &Grid<char>, offset: usize) -> String {
     let mut ret = String::new();
-    for row in map {
+    for row in map.rows() {
         ret.push_str(&row.iter().skip(offset).collect::<String>());
         ret.push('\n');
     }
     ret
 }
 
-fn parse_map(data: &str) -> Vec<Vec<char>> {
+/// Each line's rock path, as a list of points.
+pub fn parse_paths(data: &str) -> Result<Vec<Vec<(usize, usize)>>, ParseError> {
+    lines(data, |line| {
+        line.list(" -> ", |point| {
+            let x = point.number()?;
+            point.expect(",")?;
+            Ok((x, point.number()?))
+        })
+    })
+}
+
+pub fn build_map(paths: &[Vec<(usize, usize)>]) -> Grid<char> {
     // find max dimensions
     let mut min_x = usize::MAX;
     let mut max_x = 0;
     let mut max_y = 0;
-    for line in data.split("\n") {
-        for point in line.split(" -> ") {
-            let mut coord_pair = point.split(",");
-            let x = coord_pair.next().unwrap().parse().unwrap();
-            let y = coord_pair.next().unwrap().parse().unwrap();
+    for points in paths {
+        for &(x, y) in points {
             if x < min_x {
                 min_x = x;
             }
@@ -32,16 +43,9 @@ fn parse_map(data: &str) -> Vec<Vec<char>> {
         }
     }
 
-    let mut map = vec![vec!['.'; max_x + 1]; max_y + 1];
+    let mut map = Grid::new(max_x + 1, max_y + 1, '.');
 
-    for line in data.split("\n") {
-        let points: Vec<(usize, usize)> = line
-            .split(" -> ")
-            .map(|c| {
-                let mut coords = c.split(",").map(|p| p.parse().unwrap());
-                (coords.next().unwrap(), coords.next().unwrap())
-            })
-            .collect();
+    for points in paths {
         for i in 0..points.len() - 1 {
             let first = points[i];
             let second = points[i + 1];
@@ -51,7 +55,7 @@ fn parse_map(data: &str) -> Vec<Vec<char>> {
                 let start = min(first.0, second.0);
                 let finish = max(first.0, second.0);
                 for j in start..=finish {
-                    map[first.1][j] = '#';
+                    map[(first.1, j)] = '#';
                 }
             } else {
                 // horizontal wall
@@ -59,7 +63,7 @@ fn parse_map(data: &str) -> Vec<Vec<char>> {
                 let start = min(first.1, second.1);
                 let finish = max(first.1, second.1);
                 for j in start..=finish {
-                    map[j][first.0] = '#';
+                    map[(j, first.0)] = '#';
                 }
             }
         }
@@ -69,24 +73,22 @@ fn parse_map(data: &str) -> Vec<Vec<char>> {
 }
 
 // returns the active sand (if it hasn't fallen off the map)
-fn tick(map: &mut Vec<Vec<char>>, active_sand: &(usize, usize)) -> Option<(usize, usize)> {
-    if active_sand.1 == map.len() - 1 {
+fn tick(map: &mut Grid<char>, active_sand: &(usize, usize)) -> Option<(usize, usize)> {
+    if active_sand.1 == map.height() - 1 {
         return None; // We fell off the bottom of the map!
     }
     for x_offset in vec![0, -1, 1] {
         // first try down, then down-left, then down-right
-        if map[active_sand.1 + 1][active_sand.0.checked_add_signed(x_offset).unwrap()] == '.' {
-            return Some((
-                active_sand.0.checked_add_signed(x_offset).unwrap(),
-                active_sand.1 + 1,
-            ));
+        let x = active_sand.0.checked_add_signed(x_offset).unwrap();
+        if map[(active_sand.1 + 1, x)] == '.' {
+            return Some((x, active_sand.1 + 1));
         }
     }
     // If we got here, we can't move, so we stay put:
     Some(*active_sand)
 }
 
-fn process(map: &mut Vec<Vec<char>>, sand_source: (usize, usize)) -> usize {
+pub fn process(map: &mut Grid<char>, sand_source: (usize, usize)) -> usize {
     let mut sand_count = 0;
     let mut active_sand = sand_source;
     loop {
@@ -94,7 +96,7 @@ fn process(map: &mut Vec<Vec<char>>, sand_source: (usize, usize)) -> usize {
             Some(new_active_sand) => {
                 if new_active_sand == active_sand {
                     // This sand has come to rest; let's add more sand
-                    map[active_sand.1][active_sand.0] = 'o';
+                    map[(active_sand.1, active_sand.0)] = 'o';
                     active_sand = sand_source;
                     sand_count += 1;
                 } else {
@@ -107,12 +109,6 @@ fn process(map: &mut Vec<Vec<char>>, sand_source: (usize, usize)) -> usize {
     sand_count
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-    println!("{}", process(&mut parse_map(data), (500, 0)));
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -132,13 +128,25 @@ mod test {
 ........#.
 ........#.
 #########.";
-        let mut map = parse_map(DATA);
-        map[0][500] = '+';
+        let mut map = build_map(&parse_paths(DATA).unwrap());
+        map[(0, 500)] = '+';
         assert_eq!(format_map(&map, 494).trim(), goal);
     }
 
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_paths("498,4 -> 498,6\n503,4 -> 502"),
+            Err(ParseError::new(2, 13, "`,`", "end of line"))
+        );
+        assert_eq!(
+            parse_paths("498,4 => 498,6"),
+            Err(ParseError::new(1, 6, "end of line", "whitespace"))
+        );
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(process(&mut parse_map(DATA), (500, 0)), 24);
+        assert_eq!(process(&mut build_map(&parse_paths(DATA).unwrap()), (500, 0)), 24);
     }
 }
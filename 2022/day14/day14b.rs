@@ -1,15 +1,13 @@
 use std::cmp::{max, min};
-use std::fs;
 
-fn parse_map(data: &str) -> Vec<Vec<char>> {
+use aoc_common::grid::Grid;
+
+pub fn build_map(paths: &[Vec<(usize, usize)>]) -> Grid<char> {
     // find max dimensions
     let mut max_x = 0;
     let mut max_y = 0;
-    for line in data.split("\n") {
-        for point in line.split(" -> ") {
-            let mut coord_pair = point.split(",");
-            let x = coord_pair.next().unwrap().parse().unwrap();
-            let y = coord_pair.next().unwrap().parse().unwrap();
+    for points in paths {
+        for &(x, y) in points {
             if x > max_x {
                 max_x = x;
             }
@@ -19,17 +17,12 @@ fn parse_map(data: &str) -> Vec<Vec<char>> {
         }
     }
 
-    let mut map = vec![vec!['.'; max_x + max_y + 1]; max_y + 2];
-    map.push(vec!['#'; max_x + max_y + 1]); // Add the infinite floor
+    let mut map = Grid::new(max_x + max_y + 1, max_y + 3, '.');
+    for x in 0..map.width() {
+        map[(max_y + 2, x)] = '#'; // Add the infinite floor
+    }
 
-    for line in data.split("\n") {
-        let points: Vec<(usize, usize)> = line
-            .split(" -> ")
-            .map(|c| {
-                let mut coords = c.split(",").map(|p| p.parse().unwrap());
-                (coords.next().unwrap(), coords.next().unwrap())
-            })
-            .collect();
+    for points in paths {
         for i in 0..points.len() - 1 {
             let first = points[i];
             let second = points[i + 1];
@@ -39,7 +32,7 @@ fn parse_map(data: &str) -> Vec<Vec<char>> {
                 let start = min(first.0, second.0);
                 let finish = max(first.0, second.0);
                 for j in start..=finish {
-                    map[first.1][j] = '#';
+                    map[(first.1, j)] = '#';
                 }
             } else {
                 // horizontal wall
@@ -47,7 +40,7 @@ fn parse_map(data: &str) -> Vec<Vec<char>> {
                 let start = min(first.1, second.1);
                 let finish = max(first.1, second.1);
                 for j in start..=finish {
-                    map[j][first.0] = '#';
+                    map[(j, first.0)] = '#';
                 }
             }
         }
@@ -57,24 +50,22 @@ fn parse_map(data: &str) -> Vec<Vec<char>> {
 }
 
 // returns the active sand (if it hasn't fallen off the map)
-fn tick(map: &mut Vec<Vec<char>>, active_sand: &(usize, usize)) -> Option<(usize, usize)> {
-    if active_sand.1 == map.len() - 1 {
+fn tick(map: &mut Grid<char>, active_sand: &(usize, usize)) -> Option<(usize, usize)> {
+    if active_sand.1 == map.height() - 1 {
         return None; // We fell off the bottom of the map!
     }
     for x_offset in vec![0, -1, 1] {
         // first try down, then down-left, then down-right
-        if map[active_sand.1 + 1][active_sand.0.checked_add_signed(x_offset).unwrap()] == '.' {
-            return Some((
-                active_sand.0.checked_add_signed(x_offset).unwrap(),
-                active_sand.1 + 1,
-            ));
+        let x = active_sand.0.checked_add_signed(x_offset).unwrap();
+        if map[(active_sand.1 + 1, x)] == '.' {
+            return Some((x, active_sand.1 + 1));
         }
     }
     // If we got here, we can't move, so we stay put:
     Some(*active_sand)
 }
 
-fn process(map: &mut Vec<Vec<char>>, sand_source: (usize, usize)) -> usize {
+pub fn process(map: &mut Grid<char>, sand_source: (usize, usize)) -> usize {
     let mut sand_count = 0;
     let mut active_sand = sand_source;
     loop {
@@ -82,10 +73,10 @@ fn process(map: &mut Vec<Vec<char>>, sand_source: (usize, usize)) -> usize {
             Some(new_active_sand) => {
                 if new_active_sand == active_sand {
                     // This sand has come to rest; let's add more sand
-                    if map[active_sand.1][active_sand.0] != '.' {
+                    if map[(active_sand.1, active_sand.0)] != '.' {
                         break; // We're full
                     }
-                    map[active_sand.1][active_sand.0] = 'o';
+                    map[(active_sand.1, active_sand.0)] = 'o';
                     active_sand = sand_source;
                     sand_count += 1;
                 } else {
@@ -98,21 +89,16 @@ fn process(map: &mut Vec<Vec<char>>, sand_source: (usize, usize)) -> usize {
     sand_count
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-    println!("{}", process(&mut parse_map(data), (500, 0)));
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::day14::parse_paths;
 
     const DATA: &str = "498,4 -> 498,6 -> 496,6
 503,4 -> 502,4 -> 502,9 -> 494,9";
 
     #[test]
     fn test_part2() {
-        assert_eq!(process(&mut parse_map(DATA), (500, 0)), 93);
+        assert_eq!(process(&mut build_map(&parse_paths(DATA).unwrap()), (500, 0)), 93);
     }
 }
@@ -1,41 +1,45 @@
-use std::collections::HashSet;
-use std::fs;
+use aoc_common::interval::IntervalSet;
+use aoc_common::parse::{lines, ParseError};
 
-fn process(data: &str, target_row: i32) -> usize {
-    let mut locations_without_beacon = HashSet::new();
-    let mut beacons_on_target_row = HashSet::new();
+/// Each sensor's position, and the position of the beacon closest to it.
+pub type Reading = ((i32, i32), (i32, i32));
 
-    for sensor in data.split("\n") {
-        let (sensor_x, rest) = &sensor[12..].split_once(',').unwrap();
-        let sensor_x: i32 = sensor_x.parse().unwrap();
-        let (sensor_y, rest) = &rest[3..].split_once(':').unwrap();
-        let sensor_y: i32 = sensor_y.parse().unwrap();
-        let (beacon_x, rest) = &rest[24..].split_once(',').unwrap();
-        let beacon_x: i32 = beacon_x.parse().unwrap();
-        let beacon_y: i32 = rest[3..].parse().unwrap();
-        if beacon_y == target_row {
-            beacons_on_target_row.insert(beacon_x);
-        }
-
-        let manhattan_distance: i32 = (beacon_x - sensor_x).abs() + (beacon_y - sensor_y).abs();
-
-        let remaining_width = manhattan_distance - (sensor_y - target_row).abs();
-        for c in (sensor_x - remaining_width)..=(sensor_x + remaining_width) {
-            locations_without_beacon.insert(c);
-        }
-    }
+pub fn parse(data: &str) -> Result<Vec<Reading>, ParseError> {
+    lines(data, |line| {
+        line.expect("Sensor at x=")?;
+        let sensor_x = line.number()?;
+        line.expect(", y=")?;
+        let sensor_y = line.number()?;
+        line.expect(": closest beacon is at x=")?;
+        let beacon_x = line.number()?;
+        line.expect(", y=")?;
+        let beacon_y = line.number()?;
+        Ok(((sensor_x, sensor_y), (beacon_x, beacon_y)))
+    })
+}
 
-    for beacon in beacons_on_target_row {
-        locations_without_beacon.remove(&beacon);
+/// The columns of `row` that are close enough to some sensor for a beacon
+/// there to have been found instead.
+pub fn row_coverage(readings: &[Reading], row: i32) -> IntervalSet {
+    let mut covered = IntervalSet::new();
+    for &((sensor_x, sensor_y), (beacon_x, beacon_y)) in readings {
+        let manhattan_distance = (beacon_x - sensor_x).abs() + (beacon_y - sensor_y).abs();
+        // Negative when the row is out of the sensor's reach, which makes
+        // an empty range
+        let remaining_width = (manhattan_distance - (sensor_y - row).abs()) as i64;
+        covered.insert(sensor_x as i64 - remaining_width..=sensor_x as i64 + remaining_width);
     }
-
-    locations_without_beacon.len()
+    covered
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-    println!("{}", process(data, 2_000_000));
+pub fn process(readings: &[Reading], target_row: i32) -> u64 {
+    let mut locations_without_beacon = row_coverage(readings, target_row);
+    for &(_, (beacon_x, beacon_y)) in readings {
+        if beacon_y == target_row {
+            locations_without_beacon.remove(beacon_x as i64..=beacon_x as i64);
+        }
+    }
+    locations_without_beacon.total_len()
 }
 
 #[cfg(test)]
@@ -57,8 +61,33 @@ Sensor at x=16, y=7: closest beacon is at x=15, y=3
 Sensor at x=14, y=3: closest beacon is at x=15, y=3
 Sensor at x=20, y=1: closest beacon is at x=15, y=3";
 
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("Sensor at x=2, y=18: closest beacon is at x=-2, y=15\nSensor at x=9 y=16"),
+            Err(ParseError::new(2, 14, "`, y=`", "` y=1`"))
+        );
+        assert_eq!(
+            parse("Sensor at x=2, y=18: beacon at x=-2, y=15"),
+            Err(ParseError::new(
+                1,
+                20,
+                "`: closest beacon is at x=`",
+                "`: beacon at x=-2, y=15`"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_row_coverage() {
+        let readings = parse(DATA).unwrap();
+        let covered: Vec<_> = row_coverage(&readings, 11).ranges().collect();
+        // The distress beacon's at 14,11
+        assert_eq!(covered, vec![-3..=13, 15..=25]);
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(process(DATA, 10), 26);
+        assert_eq!(process(&parse(DATA).unwrap(), 10), 26);
     }
 }
@@ -1,78 +1,130 @@
-// rustup run nightly rustc -O day15b.rs
-#![feature(hash_drain_filter)]
+use aoc_common::cancel::{Cancel, Search};
+use aoc_common::checkpoint::Checkpoint;
+use aoc_common::debug;
+use aoc_common::log::Progress;
 
-use std::collections::HashSet;
-use std::fs;
+use crate::day15::{row_coverage, Reading};
 
-fn region_in_range(point: (i32, i32), manhattan_distance: i32, region: (i32, i32)) -> bool {
-    let top_left_covered =
-        (region.0 - point.0).abs() + (region.1 - point.1).abs() <= manhattan_distance;
-    let top_right_covered =
-        (region.0 + 1000 - point.0).abs() + (region.1 - point.1).abs() <= manhattan_distance;
-    let bottom_left_covered =
-        (region.0 - point.0).abs() + (region.1 + 1000 - point.1).abs() <= manhattan_distance;
-    let bottom_right_covered =
-        (region.0 + 1000 - point.0).abs() + (region.1 + 1000 - point.1).abs() <= manhattan_distance;
-    return top_left_covered && top_right_covered && bottom_left_covered && bottom_right_covered;
+// The distress beacon is somewhere in a square this big
+const SEARCH_SIZE: i32 = 4_000_000;
+
+pub fn process(readings: &[Reading]) -> u64 {
+    process_until(readings, &Cancel::never())
+        .finished()
+        .unwrap()
 }
 
-fn process(data: &str) -> u64 {
-    println!("Populating initial hash set...");
-    let mut regions_without_beacon = HashSet::new();
-    for x in 0..4000 {
-        for y in 0..4000 {
-            regions_without_beacon.insert((x * 1000, y * 1000));
-        }
+/// Like `process`, but stops once `cancel` says to. The beacon is the answer,
+/// so there's nothing to show for a search that's stopped early.
+pub fn process_until(readings: &[Reading], cancel: &Cancel) -> Search<u64> {
+    let mut checkpoint = Checkpoint::open("2022-day15b", &fingerprint(readings, SEARCH_SIZE));
+    search(readings, SEARCH_SIZE, cancel, &mut checkpoint)
+}
+
+// The search area and the readings, for telling whether a checkpoint came
+// from this input
+fn fingerprint(readings: &[Reading], size: i32) -> String {
+    let mut fingerprint = size.to_string();
+    for ((sensor_x, sensor_y), (beacon_x, beacon_y)) in readings {
+        fingerprint.push_str(&format!(
+            "\n{},{} {},{}",
+            sensor_x, sensor_y, beacon_x, beacon_y
+        ));
     }
-    println!("hash set population complete!");
+    fingerprint
+}
 
-    let mut sensors = Vec::new();
+// Each row's coverage is merged into runs of columns, and the one column that
+// no sensor covers is the gap between them. A checkpoint is the row to carry
+// on from.
+fn search(
+    readings: &[Reading],
+    size: i32,
+    cancel: &Cancel,
+    checkpoint: &mut Checkpoint,
+) -> Search<u64> {
+    let start = checkpoint
+        .load()
+        .and_then(|saved| saved.parse().ok())
+        .unwrap_or(0);
+    let mut progress = Progress::new("scanning rows", size as u64 + 1);
+    for row in start..=size {
+        let cancelled = cancel.is_cancelled();
+        if cancelled || checkpoint.due() {
+            checkpoint.save(&row.to_string());
+        }
+        if cancelled {
+            return Search::Cancelled(None);
+        }
+        progress.inc();
+        if let Some(gap) = row_coverage(readings, row)
+            .gaps(0..=size as i64)
+            .ranges()
+            .next()
+        {
+            let x = *gap.start();
+            debug!("found beacon at {},{}", x, row);
+            checkpoint.clear();
+            return Search::Finished(x as u64 * 4_000_000 + row as u64);
+        }
+    }
+    panic!("every position within the search area is covered");
+}
 
-    for sensor in data.split("\n") {
-        let (sensor_x, rest) = &sensor[12..].split_once(',').unwrap();
-        let sensor_x: i32 = sensor_x.parse().unwrap();
-        let (sensor_y, rest) = &rest[3..].split_once(':').unwrap();
-        let sensor_y: i32 = sensor_y.parse().unwrap();
-        let (beacon_x, rest) = &rest[24..].split_once(',').unwrap();
-        let beacon_x: i32 = beacon_x.parse().unwrap();
-        let beacon_y: i32 = rest[3..].parse().unwrap();
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::day15::parse;
 
-        let manhattan_distance: i32 = (beacon_x - sensor_x).abs() + (beacon_y - sensor_y).abs();
+    const DATA: &str = "Sensor at x=2, y=18: closest beacon is at x=-2, y=15
+Sensor at x=9, y=16: closest beacon is at x=10, y=16
+Sensor at x=13, y=2: closest beacon is at x=15, y=3
+Sensor at x=12, y=14: closest beacon is at x=10, y=16
+Sensor at x=10, y=20: closest beacon is at x=10, y=16
+Sensor at x=14, y=17: closest beacon is at x=10, y=16
+Sensor at x=8, y=7: closest beacon is at x=2, y=10
+Sensor at x=2, y=0: closest beacon is at x=2, y=10
+Sensor at x=0, y=11: closest beacon is at x=2, y=10
+Sensor at x=20, y=14: closest beacon is at x=25, y=17
+Sensor at x=17, y=20: closest beacon is at x=21, y=22
+Sensor at x=16, y=7: closest beacon is at x=15, y=3
+Sensor at x=14, y=3: closest beacon is at x=15, y=3
+Sensor at x=20, y=1: closest beacon is at x=15, y=3";
 
-        sensors.push(((sensor_x, sensor_y), manhattan_distance));
+    // The example only searches up to 20
+    #[test]
+    fn test_part2() {
+        let readings = parse(DATA).unwrap();
+        assert_eq!(
+            search(
+                &readings,
+                20,
+                &Cancel::never(),
+                &mut Checkpoint::open("unused", "")
+            ),
+            Search::Finished(56000011)
+        );
+    }
 
-        let drained: HashSet<_> = regions_without_beacon
-            .drain_filter(|r| region_in_range((sensor_x, sensor_y), manhattan_distance, *r))
-            .collect();
+    #[test]
+    fn test_resume() {
+        let readings = parse(DATA).unwrap();
+        let path = std::env::temp_dir().join(format!("aoc-day15b-{}", std::process::id()));
+        let checkpoint = || Checkpoint::at(path.clone(), &fingerprint(&readings, 20));
 
-        println!("Drained {}, {} remaining", drained.len(), regions_without_beacon.len());
-    }
+        // Stopped a few rows in
+        let cancel = Cancel::after_checks(3);
+        assert_eq!(
+            search(&readings, 20, &cancel, &mut checkpoint()),
+            Search::Cancelled(None)
+        );
+        assert_eq!(checkpoint().load().as_deref(), Some("3"));
 
-    let mut found_sensor = None;
-    for (i, region) in regions_without_beacon.iter().enumerate() {
-        if i % 100 == 0 {
-            println!("Scanning regions {}-{}", i, i+99);
-        }
-        for x in 0..=1000 {
-            'region: for y in 0..=1000 {
-                for (sensor, distance) in sensors.iter() {
-                    if (sensor.0 - region.0 - x).abs() + (sensor.1 - region.1 - y).abs()
-                        <= *distance
-                    {
-                        continue 'region;
-                    }
-                }
-                found_sensor = Some((region.0 + x, region.1 + y));
-                println!("Found sensor at  {},{}", region.0 + x, region.1 + y);
-            }
-        }
+        assert_eq!(
+            search(&readings, 20, &Cancel::never(), &mut checkpoint()),
+            Search::Finished(56000011)
+        );
+        // It's cleared away once the search is done
+        assert_eq!(checkpoint().load(), None);
     }
-    let found_sensor = found_sensor.unwrap();
-    found_sensor.0 as u64 * 4_000_000 + found_sensor.1 as u64
-}
-
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-    println!("{}", process(data));
 }
@@ -1,10 +1,12 @@
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::fs;
+
+use aoc_common::parse::{lines, ParseError};
+use aoc_common::pathfinding;
 
 #[derive(Debug)]
-struct Valve {
-    connections: Vec<String>,
-    flow_rate: usize,
+pub struct Valve {
+    pub connections: Vec<String>,
+    pub flow_rate: usize,
 }
 
 fn find_shortest_path<'a>(
@@ -12,30 +14,25 @@ fn find_shortest_path<'a>(
     origin: &'a str,
     destination: &str,
 ) -> Vec<&'a str> {
-    // breadth-first search
-    let mut nodes_to_check = VecDeque::new();
-    nodes_to_check.push_back((origin, vec![]));
-    while nodes_to_check.len() > 0 {
-        let (current_node, path_to_current_node) = nodes_to_check.pop_front().unwrap();
-        for connection in valves[&current_node].connections.iter() {
-            let mut path_to_connection = path_to_current_node.clone();
-            path_to_connection.push(connection.as_str());
-            if connection == destination {
-                return path_to_connection;
-            }
-            nodes_to_check.push_back((&connection, path_to_connection));
-        }
-    }
-    panic!(
-        "Could not find a route between {} and {}",
-        origin, destination
+    let path = pathfinding::bfs(
+        origin,
+        |node| valves[node].connections.iter().map(String::as_str),
+        |node| *node == destination,
     );
+    let Some(path) = path else {
+        panic!(
+            "Could not find a route between {} and {}",
+            origin, destination
+        );
+    };
+    // Leave off the origin, since it's already been reached
+    path.nodes[1..].to_vec()
 }
 
 // Note that this only finds paths between nodes with non-zero flow rate
-fn find_shortest_paths<'a>(
+pub(crate) fn find_shortest_paths<'a>(
     valves: &'a HashMap<&'a str, Valve>,
-) -> HashMap<(&'a str, &'a str), Vec<&str>> {
+) -> HashMap<(&'a str, &'a str), Vec<&'a str>> {
     let mut shortest_paths = HashMap::new();
     for (origin, _valve) in valves
         .iter()
@@ -54,38 +51,65 @@ fn find_shortest_paths<'a>(
     shortest_paths
 }
 
-fn parse(data: &str) -> HashMap<&str, Valve> {
-    let mut valves = HashMap::new();
-    for line in data.split("\n") {
-        let valve_name = &line[6..8];
-        let (flow_rate, mut rest) = line[23..].split_once(';').unwrap();
-        let flow_rate = flow_rate.parse().unwrap();
-        // Curse you single path with single tunnel for breaking my parsing
-        if rest.chars().nth(7).unwrap() == 's' {
-            rest = &rest[24..]; // " tunnels lead to valves "
-        } else {
-            rest = &rest[23..]; // " tunnel leads to valve "
+pub fn parse(data: &str) -> Result<HashMap<&str, Valve>, ParseError> {
+    let mut tunnels = Vec::new();
+    let valves: HashMap<&str, Valve> = lines(data, |line| {
+        line.expect("Valve ")?;
+        let valve_name = line.word()?;
+        line.expect(" has flow rate=")?;
+        let flow_rate = line.number()?;
+        line.expect("; ")?;
+        // A single tunnel is worded differently, and the wording has to agree
+        // with how many tunnels follow
+        let wording = line.clone();
+        let plural = line.eat("tunnels lead to valves ");
+        if !plural {
+            line.expect("tunnel leads to valve ")?;
         }
-        let connections = rest.split(", ").map(|s| s.to_owned()).collect();
-        valves.insert(
+        let connections = line.list(", ", |tunnel| {
+            let (line, column) = (tunnel.line(), tunnel.column());
+            let connection = tunnel.word()?;
+            tunnels.push((line, column, connection));
+            Ok(connection.to_owned())
+        })?;
+        if plural != (connections.len() > 1) {
+            return Err(wording.error(match connections.len() {
+                1 => "`tunnel leads to valve`",
+                _ => "`tunnels lead to valves`",
+            }));
+        }
+        Ok((
             valve_name,
             Valve {
                 connections: connections,
                 flow_rate: flow_rate,
             },
-        );
+        ))
+    })?
+    .into_iter()
+    .collect();
+
+    // Every search starts at AA, and can only follow tunnels to valves we know
+    if !valves.contains_key("AA") {
+        let end = data.split("\n").count() + 1;
+        return Err(ParseError::new(end, 1, "a valve `AA`", "end of input"));
+    }
+    for (line, column, connection) in tunnels {
+        if !valves.contains_key(connection) {
+            let found = format!("`{}`", connection);
+            return Err(ParseError::new(line, column, "a known valve", found));
+        }
     }
-    valves
+    Ok(valves)
 }
 
-fn process(data: &str) -> usize {
-    let valves = parse(data);
+pub fn process(valves: &HashMap<&str, Valve>) -> usize {
     let mut starting_closed_flowing_valves: HashSet<&str> = valves
         .iter()
         .filter(|(_, v)| v.flow_rate > 0)
         .map(|(k, _)| *k)
         .collect();
-    let shortest_paths = find_shortest_paths(&valves);
+    let shortest_paths = find_shortest_paths(valves);
 
     let mut most_pressure_released = 0;
 
@@ -129,12 +153,6 @@ fn process(data: &str) -> usize {
     most_pressure_released
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-    println!("{}", process(data));
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -150,8 +168,32 @@ Valve HH has flow rate=22; tunnel leads to valve GG
 Valve II has flow rate=0; tunnels lead to valves AA, JJ
 Valve JJ has flow rate=21; tunnel leads to valve II";
 
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("Valve AA has flow rate=0; tunnels lead to valve BB").unwrap_err(),
+            ParseError::new(1, 27, "`tunnel leads to valve `", "`tunnels lead to valve `")
+        );
+        assert_eq!(
+            parse("Valve AA has flow rate=0; tunnels lead to valves AA").unwrap_err(),
+            ParseError::new(1, 27, "`tunnel leads to valve`", "`tunnels`")
+        );
+        assert_eq!(
+            parse("Valve AA has flow rate=x; tunnel leads to valve BB").unwrap_err(),
+            ParseError::new(1, 24, "a number", "`x;`")
+        );
+        assert_eq!(
+            parse("Valve AA has flow rate=0; tunnel leads to valve BB").unwrap_err(),
+            ParseError::new(1, 49, "a known valve", "`BB`")
+        );
+        assert_eq!(
+            parse("Valve BB has flow rate=0; tunnel leads to valve BB").unwrap_err(),
+            ParseError::new(2, 1, "a valve `AA`", "end of input")
+        );
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(process(DATA), 1651);
+        assert_eq!(process(&parse(DATA).unwrap()), 1651);
     }
 }
@@ -1,108 +1,139 @@
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::fs;
 
-#[derive(Debug)]
-struct Valve {
-    connections: Vec<String>,
-    flow_rate: usize,
+use aoc_common::cancel::{Cancel, Search};
+use aoc_common::checkpoint::Checkpoint;
+use aoc_common::log::Progress;
+
+use crate::day16::{find_shortest_paths, Valve};
+
+pub fn process(valves: &HashMap<&str, Valve>) -> usize {
+    process_until(valves, &Cancel::never()).finished().unwrap()
 }
 
-fn find_shortest_path<'a>(
-    valves: &'a HashMap<&'a str, Valve>,
-    origin: &'a str,
-    destination: &str,
-) -> Vec<&'a str> {
-    // breadth-first search
-    let mut nodes_to_check = VecDeque::new();
-    nodes_to_check.push_back((origin, vec![]));
-    while nodes_to_check.len() > 0 {
-        let (current_node, path_to_current_node) = nodes_to_check.pop_front().unwrap();
-        for connection in valves[&current_node].connections.iter() {
-            let mut path_to_connection = path_to_current_node.clone();
-            path_to_connection.push(connection.as_str());
-            if connection == destination {
-                return path_to_connection;
-            }
-            nodes_to_check.push_back((&connection, path_to_connection));
-        }
-    }
-    panic!(
-        "Could not find a route between {} and {}",
-        origin, destination
-    );
+/// Like `process`, but stops once `cancel` says to, with the most pressure
+/// released by any of the routes tried so far.
+pub fn process_until(valves: &HashMap<&str, Valve>, cancel: &Cancel) -> Search<usize> {
+    let mut checkpoint = Checkpoint::open("2022-day16b", &fingerprint(valves));
+    search(valves, cancel, &mut checkpoint)
 }
 
-// Note that this only finds paths between nodes with non-zero flow rate, plus the starting node
-fn find_shortest_paths<'a>(
-    valves: &'a HashMap<&'a str, Valve>,
-) -> HashMap<(&'a str, &'a str), Vec<&str>> {
-    let mut shortest_paths = HashMap::new();
-    for (origin, _valve) in valves
+// Where each of us is, which valves are still closed, the pressure released
+// so far and how many minutes each of us has used
+type State<'a> = (&'a str, &'a str, HashSet<&'a str>, usize, usize, usize);
+
+// The valves in a fixed order, for telling whether a checkpoint came from
+// this network
+fn fingerprint(valves: &HashMap<&str, Valve>) -> String {
+    let mut lines: Vec<String> = valves
         .iter()
-        .filter(|(n, v)| v.flow_rate > 0 || **n == "AA")
-    {
-        for (destination, _valve) in valves.iter().filter(|(_, v)| v.flow_rate > 0) {
-            if origin == destination {
-                continue;
-            }
-            shortest_paths.insert(
-                (*origin, *destination),
-                find_shortest_path(valves, origin, destination),
-            );
-        }
+        .map(|(name, valve)| {
+            format!(
+                "{} {} {}",
+                name,
+                valve.flow_rate,
+                valve.connections.join(",")
+            )
+        })
+        .collect();
+    lines.sort();
+    lines.join("\n")
+}
+
+// A checkpoint is the most pressure released so far, then a line for each
+// state still to be checked
+fn save_states(most_pressure_released: usize, states: &VecDeque<State>) -> String {
+    let mut saved = most_pressure_released.to_string();
+    for (me, elephant, closed, pressure, me_minutes, elephant_minutes) in states {
+        let mut closed: Vec<&str> = closed.iter().copied().collect();
+        closed.sort();
+        saved.push_str(&format!(
+            "\n{} {} {} {} {} {}",
+            me,
+            elephant,
+            pressure,
+            me_minutes,
+            elephant_minutes,
+            closed.join(",")
+        ));
     }
-    shortest_paths
+    saved
 }
 
-fn parse(data: &str) -> HashMap<&str, Valve> {
-    let mut valves = HashMap::new();
-    for line in data.split("\n") {
-        let valve_name = &line[6..8];
-        let (flow_rate, mut rest) = line[23..].split_once(';').unwrap();
-        let flow_rate = flow_rate.parse().unwrap();
-        // Curse you single path with single tunnel for breaking my parsing
-        if rest.chars().nth(7).unwrap() == 's' {
-            rest = &rest[24..]; // " tunnels lead to valves "
-        } else {
-            rest = &rest[23..]; // " tunnel leads to valve "
-        }
-        let connections = rest.split(", ").map(|s| s.to_owned()).collect();
-        valves.insert(
-            valve_name,
-            Valve {
-                connections: connections,
-                flow_rate: flow_rate,
-            },
-        );
+fn load_states<'a>(
+    valves: &HashMap<&'a str, Valve>,
+    saved: &str,
+) -> Option<(usize, VecDeque<State<'a>>)> {
+    // Back to names borrowed from the valves, like the rest of the search uses
+    let valve = |name: &str| valves.get_key_value(name).map(|(name, _)| *name);
+    let mut lines = saved.split('\n');
+    let most_pressure_released = lines.next()?.parse().ok()?;
+    let mut states = VecDeque::new();
+    for line in lines {
+        let fields: Vec<&str> = line.split(' ').collect();
+        let [me, elephant, pressure, me_minutes, elephant_minutes, closed] = fields[..] else {
+            return None;
+        };
+        let closed = closed
+            .split(',')
+            .filter(|name| !name.is_empty())
+            .map(valve)
+            .collect::<Option<_>>()?;
+        states.push_back((
+            valve(me)?,
+            valve(elephant)?,
+            closed,
+            pressure.parse().ok()?,
+            me_minutes.parse().ok()?,
+            elephant_minutes.parse().ok()?,
+        ));
     }
-    valves
+    Some((most_pressure_released, states))
 }
 
-fn process(data: &str) -> usize {
-    let valves = parse(data);
+fn search(
+    valves: &HashMap<&str, Valve>,
+    cancel: &Cancel,
+    checkpoint: &mut Checkpoint,
+) -> Search<usize> {
     let starting_closed_flowing_valves: HashSet<&str> = valves
         .iter()
         .filter(|(_, v)| v.flow_rate > 0)
         .map(|(k, _)| *k)
         .collect();
-    let shortest_paths = find_shortest_paths(&valves);
-
-    let mut most_pressure_released = 0;
+    let shortest_paths = find_shortest_paths(valves);
 
     // breadth-first search
-    let mut nodes_to_check = VecDeque::new();
-    nodes_to_check.push_back(("AA", "AA", starting_closed_flowing_valves, 0, 0, 0));
-    let mut check_count = 0;
-    while let Some((
-        current_me_node,
-        current_elephant_node,
-        remaining_closed_valves,
-        pressure_released,
-        me_minutes_elapsed,
-        elephant_minutes_elapsed,
-    )) = nodes_to_check.pop_back()
-    // I originally tried `pop_front`, but memory use explodes.
-    {
+    let resumed = checkpoint
+        .load()
+        .and_then(|saved| load_states(valves, &saved));
+    let (mut most_pressure_released, mut nodes_to_check) = match resumed {
+        Some(resumed) => resumed,
+        None => {
+            let start = ("AA", "AA", starting_closed_flowing_valves, 0, 0, 0);
+            (0, VecDeque::from([start]))
+        }
+    };
+    let mut progress = Progress::counter("states checked");
+    loop {
+        let cancelled = cancel.is_cancelled();
+        if cancelled || checkpoint.due() {
+            checkpoint.save(&save_states(most_pressure_released, &nodes_to_check));
+        }
+        if cancelled {
+            return Search::Cancelled(Some(most_pressure_released));
+        }
+        // I originally tried `pop_front`, but memory use explodes.
+        let Some((
+            current_me_node,
+            current_elephant_node,
+            remaining_closed_valves,
+            pressure_released,
+            me_minutes_elapsed,
+            elephant_minutes_elapsed,
+        )) = nodes_to_check.pop_back()
+        else {
+            break;
+        };
         for target_me_node in remaining_closed_valves.iter() {
             for target_elephant_node in remaining_closed_valves.iter() {
                 if target_me_node == target_elephant_node {
@@ -155,23 +186,18 @@ fn process(data: &str) -> usize {
                 }
             }
         }
-        check_count += 1;
-        if check_count % 100_000 == 0 {
-            println!("{}", check_count);
-        }
+        progress.inc();
     }
-    most_pressure_released
-}
-
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-    println!("{}", process(data));
+    checkpoint.clear();
+    Search::Finished(most_pressure_released)
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::day16::parse;
+    use aoc_common::differential::{compare, shrink_vec};
+    use aoc_common::rng::Rng;
 
     const DATA: &str = "Valve AA has flow rate=0; tunnels lead to valves DD, II, BB
 Valve BB has flow rate=13; tunnels lead to valves CC, AA
@@ -186,6 +212,200 @@ Valve JJ has flow rate=21; tunnel leads to valve II";
 
     #[test]
     fn test_part2() {
-        assert_eq!(process(DATA), 1707);
+        assert_eq!(process(&parse(DATA).unwrap()), 1707);
+    }
+
+    #[test]
+    fn test_cancel() {
+        let cancel = Cancel::never();
+        cancel.cancel();
+        assert_eq!(
+            process_until(&parse(DATA).unwrap(), &cancel),
+            Search::Cancelled(Some(0))
+        );
+    }
+
+    #[test]
+    fn test_resume() {
+        let valves = parse(DATA).unwrap();
+        let path = std::env::temp_dir().join(format!("aoc-day16b-{}", std::process::id()));
+        let checkpoint = || Checkpoint::at(path.clone(), &fingerprint(&valves));
+
+        // Stopped after two states, which can't have opened the six valves
+        // the best route needs, whatever order they come out of the set in
+        let stopped = search(&valves, &Cancel::after_checks(2), &mut checkpoint());
+        assert!(matches!(stopped, Search::Cancelled(Some(best)) if best < 1707));
+        let (_, states) = load_states(&valves, &checkpoint().load().unwrap()).unwrap();
+        assert!(!states.is_empty());
+
+        assert_eq!(
+            search(&valves, &Cancel::never(), &mut checkpoint()),
+            Search::Finished(1707)
+        );
+        // It's cleared away once the search is done
+        assert_eq!(checkpoint().load(), None);
+    }
+
+    // A connected network of tunnels, each valve being its index's letter
+    // doubled. Valve 0 is AA.
+    #[derive(Clone, Debug)]
+    struct Network {
+        flow_rates: Vec<usize>,
+        tunnels: Vec<(usize, usize)>,
+    }
+
+    impl Network {
+        fn generate(rng: &mut Rng) -> Network {
+            let valves = 2 + rng.below(9);
+            let flow_rates = (0..valves)
+                .map(|_| if rng.chance(40) { 0 } else { 1 + rng.below(25) })
+                .collect();
+            // Hang each valve off an earlier one so that it's all connected,
+            // then add a few shortcuts
+            let mut tunnels: Vec<(usize, usize)> = (1..valves).map(|v| (rng.below(v), v)).collect();
+            for _ in 0..rng.below(valves) {
+                let (a, b) = (rng.below(valves), rng.below(valves));
+                if a != b && !tunnels.contains(&(a, b)) && !tunnels.contains(&(b, a)) {
+                    tunnels.push((a, b));
+                }
+            }
+            Network {
+                flow_rates,
+                tunnels,
+            }
+        }
+
+        fn connected(&self) -> bool {
+            let mut reached = vec![false; self.flow_rates.len()];
+            let mut to_visit = vec![0];
+            while let Some(valve) = to_visit.pop() {
+                if !std::mem::replace(&mut reached[valve], true) {
+                    for &(a, b) in &self.tunnels {
+                        if a == valve {
+                            to_visit.push(b);
+                        } else if b == valve {
+                            to_visit.push(a);
+                        }
+                    }
+                }
+            }
+            reached.into_iter().all(|r| r)
+        }
+
+        // Turn off valves and take out tunnels, so long as it stays connected
+        fn shrink(&self) -> Vec<Network> {
+            let mut smaller = Vec::new();
+            for (i, &flow_rate) in self.flow_rates.iter().enumerate() {
+                if flow_rate > 0 {
+                    let mut network = self.clone();
+                    network.flow_rates[i] = 0;
+                    smaller.push(network);
+                }
+            }
+            for tunnels in shrink_vec(&self.tunnels) {
+                let network = Network {
+                    tunnels,
+                    ..self.clone()
+                };
+                if network.connected() {
+                    smaller.push(network);
+                }
+            }
+            smaller
+        }
+
+        // Floyd-Warshall over the tunnels, for the same pairs of valves
+        // `find_shortest_paths` finds paths between: from AA or a flowing
+        // valve, to a different flowing valve
+        fn distances(&self) -> Vec<((String, String), usize)> {
+            let valves = self.flow_rates.len();
+            let mut distance = vec![vec![usize::MAX / 2; valves]; valves];
+            for valve in 0..valves {
+                distance[valve][valve] = 0;
+            }
+            for &(a, b) in &self.tunnels {
+                distance[a][b] = 1;
+                distance[b][a] = 1;
+            }
+            for via in 0..valves {
+                for from in 0..valves {
+                    for to in 0..valves {
+                        let through = distance[from][via] + distance[via][to];
+                        if through < distance[from][to] {
+                            distance[from][to] = through;
+                        }
+                    }
+                }
+            }
+            let mut distances = Vec::new();
+            for from in (0..valves).filter(|&v| v == 0 || self.flow_rates[v] > 0) {
+                for to in (0..valves).filter(|&v| v != from && self.flow_rates[v] > 0) {
+                    distances.push(((name(from), name(to)), distance[from][to]));
+                }
+            }
+            distances.sort();
+            distances
+        }
+
+        fn to_input(&self) -> String {
+            let mut lines = Vec::new();
+            for (valve, flow_rate) in self.flow_rates.iter().enumerate() {
+                let connections: Vec<String> = self
+                    .tunnels
+                    .iter()
+                    .filter_map(|&(a, b)| {
+                        if a == valve {
+                            Some(name(b))
+                        } else if b == valve {
+                            Some(name(a))
+                        } else {
+                            None
+                        }
+                    })
+                    .collect();
+                let tunnels = match connections.len() {
+                    1 => "tunnel leads to valve",
+                    _ => "tunnels lead to valves",
+                };
+                lines.push(format!(
+                    "Valve {} has flow rate={}; {} {}",
+                    name(valve),
+                    flow_rate,
+                    tunnels,
+                    connections.join(", ")
+                ));
+            }
+            lines.join("\n")
+        }
+    }
+
+    fn name(valve: usize) -> String {
+        ((b'A' + valve as u8) as char).to_string().repeat(2)
+    }
+
+    // How far it is between each pair of valves the searches care about
+    fn distances(paths: HashMap<(&str, &str), Vec<&str>>) -> Vec<((String, String), usize)> {
+        let mut distances: Vec<_> = paths
+            .into_iter()
+            .map(|((from, to), path)| ((from.to_string(), to.to_string()), path.len()))
+            .collect();
+        distances.sort();
+        distances
+    }
+
+    #[test]
+    fn test_paths_agree_with_floyd_warshall() {
+        compare(
+            0,
+            300,
+            Network::generate,
+            Network::shrink,
+            |network| {
+                let input = network.to_input();
+                distances(find_shortest_paths(&parse(&input).unwrap()))
+            },
+            Network::distances,
+        )
+        .unwrap();
     }
 }
@@ -1,5 +1,5 @@
-use std::collections::HashMap;
-use std::fs;
+use aoc_common::cycle::metric_after;
+use aoc_common::parse::{ParseError, Scanner};
 
 fn height(chamber: &Vec<[bool; 7]>) -> usize {
     for (i, row) in chamber.iter().enumerate().rev() {
@@ -13,106 +13,129 @@ fn height(chamber: &Vec<[bool; 7]>) -> usize {
 }
 
 #[derive(Debug)]
-enum JetDirection {
+pub enum JetDirection {
     Left,
     Right,
 }
 
-fn process(data: &str, rocks_to_fall: u64) -> u64 {
-    // I'd have loved to put rock_shapes as a global constant, but
-    // unfortunately since rocks are different sizes, rocks have to be
-    // `Vec`s not arrays, and that can't be done statically,
-    // apparently. Maybe there's a good workaround for this, but I
-    // haven't found it. So, we just put it here instead!
-    let rock_shapes = vec![
+// I'd have loved to put rock_shapes as a global constant, but
+// unfortunately since rocks are different sizes, rocks have to be
+// `Vec`s not arrays, and that can't be done statically,
+// apparently. Maybe there's a good workaround for this, but I
+// haven't found it. So, we just build them here instead!
+fn rock_shapes() -> Vec<Vec<(i64, i64)>> {
+    vec![
         vec![(0, 0), (1, 0), (2, 0), (3, 0)],
         vec![(1, 0), (0, 1), (1, 1), (2, 1), (1, 2)],
         vec![(0, 0), (1, 0), (2, 0), (2, 1), (2, 2)],
         vec![(0, 0), (0, 1), (0, 2), (0, 3)],
         vec![(0, 0), (1, 0), (0, 1), (1, 1)],
-    ];
-
-    let jets: Vec<JetDirection> = data
-        .chars()
-        .map(|c| match c {
-            '<' => JetDirection::Left,
-            '>' => JetDirection::Right,
-            _ => panic!("Unknown characters"),
-        })
-        .collect();
-
-    let mut falling_points = Vec::new();
-    let mut chamber = vec![[false; 7]; 4];
-    let mut current_falling_rock = 0;
-    let mut is_moving_sideways = true;
-    for point in &rock_shapes[0] {
-        falling_points.push((point.0 + 2, point.1 + 3));
-    }
-    let mut seen_states: HashMap<([[bool; 7]; 10], usize), (u64, u64)> = HashMap::new();
-    let mut jet_index = 0;
-    let mut height_offset = 0;
-    while current_falling_rock < rocks_to_fall {
-        let target_offset;
-        if is_moving_sideways {
-            let jet_direction = &jets[jet_index];
-            target_offset = match jet_direction {
-                JetDirection::Left => (-1, 0),
-                JetDirection::Right => (1, 0),
-            };
-            jet_index = (jet_index + 1) % jets.len();
-        } else {
-            target_offset = (0, -1);
+    ]
+}
+
+struct Chamber<'a> {
+    rock_shapes: Vec<Vec<(i64, i64)>>,
+    jets: &'a [JetDirection],
+    rows: Vec<[bool; 7]>,
+    fallen_rocks: usize,
+    jet_index: usize,
+}
+
+impl Chamber<'_> {
+    // Drops the next rock, blowing it about until it comes to rest
+    fn drop_rock(&mut self) {
+        let h = height(&self.rows);
+        for _ in 0..h + 7 - self.rows.len() {
+            self.rows.push([false; 7]);
         }
-        let can_move = falling_points.iter().all(|(x, y)| {
-            let new_x = x + target_offset.0;
-            let new_y = y + target_offset.1;
-            (0..7).contains(&new_x) && new_y >= 0 && !chamber[new_y as usize][new_x as usize]
-        });
-        if can_move {
-            for point in falling_points.iter_mut() {
-                *point = (point.0 + target_offset.0, point.1 + target_offset.1);
+        let mut falling_points: Vec<_> = self.rock_shapes[self.fallen_rocks % 5]
+            .iter()
+            .map(|point| (point.0 + 2, point.1 + h as i64 + 3))
+            .collect();
+
+        let mut is_moving_sideways = true;
+        loop {
+            let target_offset;
+            if is_moving_sideways {
+                target_offset = match self.jets[self.jet_index] {
+                    JetDirection::Left => (-1, 0),
+                    JetDirection::Right => (1, 0),
+                };
+                self.jet_index = (self.jet_index + 1) % self.jets.len();
+            } else {
+                target_offset = (0, -1);
             }
-        } else if !is_moving_sideways {
-            if height_offset == 0 && height(&chamber) > 10 {
-                let mut foo = [[false; 7]; 10];
-                foo.clone_from_slice(&chamber[chamber.len() - 10..chamber.len()]);
-                let state = (foo, jet_index);
-                if seen_states.contains_key(&state) {
-                    let remaining_falling_rocks = rocks_to_fall - current_falling_rock;
-                    let rocks_fall_per_cycle = current_falling_rock - seen_states[&state].0;
-                    let height_added_per_cycle = height(&chamber) as u64 - seen_states[&state].1;
-                    let cycles = remaining_falling_rocks / rocks_fall_per_cycle;
-                    height_offset = cycles * height_added_per_cycle;
-                    current_falling_rock += rocks_fall_per_cycle * cycles;
-                } else {
-                    seen_states.insert(state, (current_falling_rock, height(&chamber) as u64));
+            let can_move = falling_points.iter().all(|(x, y)| {
+                let new_x = x + target_offset.0;
+                let new_y = y + target_offset.1;
+                (0..7).contains(&new_x) && new_y >= 0 && !self.rows[new_y as usize][new_x as usize]
+            });
+            if can_move {
+                for point in falling_points.iter_mut() {
+                    *point = (point.0 + target_offset.0, point.1 + target_offset.1);
                 }
+            } else if !is_moving_sideways {
+                break;
             }
-            // Convert all falling points into stuck points
-            while let Some((x, y)) = falling_points.pop() {
-                chamber[y as usize][x as usize] = true;
-            }
+            is_moving_sideways = !is_moving_sideways;
+        }
 
-            current_falling_rock += 1;
+        // Convert all falling points into stuck points
+        for (x, y) in falling_points {
+            self.rows[y as usize][x as usize] = true;
+        }
+        self.fallen_rocks += 1;
+    }
 
-            for point in &rock_shapes[current_falling_rock as usize % 5] {
-                let h = height(&chamber);
-                for _ in 0..h + 7 - chamber.len() {
-                    chamber.push([false; 7]);
-                }
-                falling_points.push((point.0 + 2, point.1 + h as i64 + 3));
-            }
+    // The top of the tower, along with which rock and jet come next, which
+    // is all that decides how the tower grows from here. Short towers don't
+    // have a top yet.
+    fn state(&self) -> Option<([[bool; 7]; 10], usize, usize)> {
+        let h = height(&self.rows);
+        if h <= 10 {
+            return None;
         }
-        is_moving_sideways = !is_moving_sideways;
+        let mut top = [[false; 7]; 10];
+        top.clone_from_slice(&self.rows[h - 10..h]);
+        Some((top, self.fallen_rocks % 5, self.jet_index))
     }
+}
+
+fn jet(line: &mut Scanner) -> Result<JetDirection, ParseError> {
+    Ok(match line.one_of("<>")? {
+        '<' => JetDirection::Left,
+        _ => JetDirection::Right,
+    })
+}
 
-    height(&chamber) as u64 + height_offset
+/// The jet pattern, which is a single line of `<` and `>`. There has to be
+/// at least one jet, since the pattern repeats forever.
+pub fn parse_jets(data: &str) -> Result<Vec<JetDirection>, ParseError> {
+    let mut line = Scanner::new(1, data);
+    let mut jets = vec![jet(&mut line)?];
+    while !line.is_empty() {
+        jets.push(jet(&mut line)?);
+    }
+    Ok(jets)
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-    println!("{}", process(data, 1_000_000_000_000)); // 2022 for part 1
+pub fn process(jets: &[JetDirection], rocks_to_fall: u64) -> u64 {
+    let chamber = Chamber {
+        rock_shapes: rock_shapes(),
+        jets,
+        rows: Vec::new(),
+        fallen_rocks: 0,
+        jet_index: 0,
+    };
+    // The tower soon starts repeating itself, so only the first few cycles'
+    // worth of rocks actually need dropping
+    metric_after(
+        chamber,
+        Chamber::drop_rock,
+        Chamber::state,
+        |chamber| height(&chamber.rows) as i64,
+        rocks_to_fall,
+    ) as u64
 }
 
 #[cfg(test)]
@@ -123,11 +146,44 @@ mod test {
 
     #[test]
     fn test_part1() {
-        assert_eq!(process(DATA, 2022), 3068);
+        assert_eq!(process(&parse_jets(DATA).unwrap(), 2022), 3068);
+    }
+
+    #[test]
+    fn test_skipping_ahead() {
+        let jets = parse_jets(DATA).unwrap();
+        let mut chamber = Chamber {
+            rock_shapes: rock_shapes(),
+            jets: &jets,
+            rows: Vec::new(),
+            fallen_rocks: 0,
+            jet_index: 0,
+        };
+        for rocks in 1..=3000 {
+            chamber.drop_rock();
+            if rocks % 250 == 0 {
+                assert_eq!(process(&jets, rocks), height(&chamber.rows) as u64);
+            }
+        }
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(process(DATA, 1_000_000_000_000), 1514285714288);
+        assert_eq!(
+            process(&parse_jets(DATA).unwrap(), 1_000_000_000_000),
+            1514285714288
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_jets(">>x<").unwrap_err(),
+            ParseError::new(1, 3, "one of `<>`", "`x<`")
+        );
+        assert_eq!(
+            parse_jets("").unwrap_err(),
+            ParseError::new(1, 1, "one of `<>`", "end of line")
+        );
     }
 }
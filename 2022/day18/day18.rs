@@ -1,5 +1,6 @@
 use std::collections::HashSet;
-use std::fs;
+
+use aoc_common::parse::{lines, ParseError};
 
 // TODO: this should be calculable via something like the number of
 // points to the (2/3) power over 2 times a constant I'm too lazy to
@@ -43,19 +44,19 @@ fn is_air_bubble(droplets: &HashSet<(i32, i32, i32)>, start: (i32, i32, i32)) ->
     true
 }
 
-fn process(data: &str, include_air_bubbles: bool) -> usize {
-    let droplets: HashSet<(i32, i32, i32)> = data
-        .split('\n')
-        .map(|line| {
-            let mut nums = line.split(',').map(|i| i.parse().unwrap());
-            (
-                nums.next().unwrap(),
-                nums.next().unwrap(),
-                nums.next().unwrap(),
-            )
-        })
-        .collect();
+/// Every cube of lava in the droplet.
+pub fn parse(data: &str) -> Result<HashSet<(i32, i32, i32)>, ParseError> {
+    let cubes = lines(data, |line| {
+        let x = line.number()?;
+        line.expect(",")?;
+        let y = line.number()?;
+        line.expect(",")?;
+        Ok((x, y, line.number()?))
+    })?;
+    Ok(cubes.into_iter().collect())
+}
 
+pub fn process(droplets: &HashSet<(i32, i32, i32)>, include_air_bubbles: bool) -> usize {
     let mut surface_area = 0;
     for droplet in droplets.iter() {
         for offset in [
@@ -72,7 +73,7 @@ fn process(data: &str, include_air_bubbles: bool) -> usize {
                 droplet.2 + offset.2,
             );
             if !droplets.contains(&neighbor)
-                && (include_air_bubbles || !is_air_bubble(&droplets, neighbor))
+                && (include_air_bubbles || !is_air_bubble(droplets, neighbor))
             {
                 surface_area += 1;
             }
@@ -81,12 +82,6 @@ fn process(data: &str, include_air_bubbles: bool) -> usize {
     surface_area
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-    println!("{}", process(data, false));
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -107,16 +102,24 @@ mod test {
 
     #[test]
     fn trivial_part1() {
-        assert_eq!(process("1,1,1\n2,1,1", true), 10);
+        assert_eq!(process(&parse("1,1,1\n2,1,1").unwrap(), true), 10);
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(process(DATA, true), 64);
+        assert_eq!(process(&parse(DATA).unwrap(), true), 64);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(process(DATA, false), 58);
+        assert_eq!(process(&parse(DATA).unwrap(), false), 58);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("1,1,1\n2,1").unwrap_err(),
+            ParseError::new(2, 4, "`,`", "end of line")
+        );
     }
 }
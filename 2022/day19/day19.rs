@@ -1,7 +1,10 @@
-use std::fs;
+use aoc_common::cancel::{Cancel, Search};
+use aoc_common::log::Progress;
+use aoc_common::parse::{lines, ParseError, Scanner};
+use aoc_common::trace;
 
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
-struct Blueprint {
+pub struct Blueprint {
     id: u8,
     ore_robot_ore_cost: u32,
     clay_robot_ore_cost: u32,
@@ -27,22 +30,23 @@ struct Robots {
     geode_cracking: u32,
 }
 
-fn parse_blueprint(data: &str) -> Blueprint {
-    let (id, remaining) = data[10..].split_once(": ").unwrap();
-    let id = id.parse().unwrap();
-    let (ore_robot_ore_cost, remaining) = remaining[21..].split_once(" ").unwrap();
-    let ore_robot_ore_cost = ore_robot_ore_cost.parse().unwrap();
-    let (clay_robot_ore_cost, remaining) = remaining[27..].split_once(" ").unwrap();
-    let clay_robot_ore_cost = clay_robot_ore_cost.parse().unwrap();
-    let (obsidian_robot_ore_cost, remaining) = remaining[31..].split_once(" ").unwrap();
-    let obsidian_robot_ore_cost = obsidian_robot_ore_cost.parse().unwrap();
-    let (obsidian_robot_clay_cost, remaining) = remaining[8..].split_once(" ").unwrap();
-    let obsidian_robot_clay_cost = obsidian_robot_clay_cost.parse().unwrap();
-    let (geode_robot_ore_cost, remaining) = remaining[29..].split_once(" ").unwrap();
-    let geode_robot_ore_cost = geode_robot_ore_cost.parse().unwrap();
-    let (geode_robot_obsidian_cost, _remaining) = remaining[8..].split_once(" ").unwrap();
-    let geode_robot_obsidian_cost = geode_robot_obsidian_cost.parse().unwrap();
-    Blueprint {
+fn parse_blueprint(line: &mut Scanner) -> Result<Blueprint, ParseError> {
+    line.expect("Blueprint ")?;
+    let id = line.number()?;
+    line.expect(": Each ore robot costs ")?;
+    let ore_robot_ore_cost = line.number()?;
+    line.expect(" ore. Each clay robot costs ")?;
+    let clay_robot_ore_cost = line.number()?;
+    line.expect(" ore. Each obsidian robot costs ")?;
+    let obsidian_robot_ore_cost = line.number()?;
+    line.expect(" ore and ")?;
+    let obsidian_robot_clay_cost = line.number()?;
+    line.expect(" clay. Each geode robot costs ")?;
+    let geode_robot_ore_cost = line.number()?;
+    line.expect(" ore and ")?;
+    let geode_robot_obsidian_cost = line.number()?;
+    line.expect(" obsidian.")?;
+    Ok(Blueprint {
         id: id,
         ore_robot_ore_cost: ore_robot_ore_cost,
         clay_robot_ore_cost: clay_robot_ore_cost,
@@ -50,10 +54,14 @@ fn parse_blueprint(data: &str) -> Blueprint {
         obsidian_robot_clay_cost: obsidian_robot_clay_cost,
         geode_robot_ore_cost: geode_robot_ore_cost,
         geode_robot_obsidian_cost: geode_robot_obsidian_cost,
-    }
+    })
+}
+
+pub fn parse(data: &str) -> Result<Vec<Blueprint>, ParseError> {
+    lines(data, parse_blueprint)
 }
 
-fn geodes_opened(blueprint: Blueprint, remaining_time: usize) -> usize {
+fn geodes_opened(blueprint: Blueprint, remaining_time: usize, cancel: &Cancel) -> Search<usize> {
     let robots = Robots {
         ore_collecting: 1,
         clay_collecting: 0,
@@ -66,18 +74,37 @@ fn geodes_opened(blueprint: Blueprint, remaining_time: usize) -> usize {
         obsidian: 0,
         geodes: 0,
     };
-    rec_geodes_opened(blueprint, remaining_time, robots, inventory, &mut 0)
+    let mut max_overall_geodes = 0;
+    let geodes = rec_geodes_opened(
+        blueprint,
+        remaining_time,
+        robots,
+        inventory,
+        &mut max_overall_geodes,
+        cancel,
+    );
+    if cancel.is_cancelled() {
+        Search::Cancelled(Some(max_overall_geodes))
+    } else {
+        Search::Finished(geodes)
+    }
 }
 
+// Checking at every step would slow the search down noticeably, and the
+// branches this close to the end don't take long anyway
+const CHECK_CANCEL_FROM: usize = 8;
+
 fn rec_geodes_opened(
     blueprint: Blueprint,
     remaining_time: usize,
     robots: Robots,
     inventory: Inventory,
     max_overall_geodes: &mut usize,
+    cancel: &Cancel,
 ) -> usize {
-    if remaining_time > 26 {
-        println!("{}", remaining_time);
+    trace!("{} minutes left", remaining_time);
+    if remaining_time >= CHECK_CANCEL_FROM && cancel.is_cancelled() {
+        return 0;
     }
     let starting_inventory = inventory;
 
@@ -117,6 +144,7 @@ fn rec_geodes_opened(
                 robots,
                 inventory,
                 max_overall_geodes,
+                cancel,
             );
             if geodes > max_geodes {
                 max_geodes = geodes;
@@ -137,6 +165,7 @@ fn rec_geodes_opened(
                 robots,
                 inventory,
                 max_overall_geodes,
+                cancel,
             );
             if geodes > max_geodes {
                 max_geodes = geodes;
@@ -155,6 +184,7 @@ fn rec_geodes_opened(
                 robots,
                 inventory,
                 max_overall_geodes,
+                cancel,
             );
             if geodes > max_geodes {
                 max_geodes = geodes;
@@ -173,6 +203,7 @@ fn rec_geodes_opened(
                 robots,
                 inventory,
                 max_overall_geodes,
+                cancel,
             );
             if geodes > max_geodes {
                 max_geodes = geodes;
@@ -184,6 +215,7 @@ fn rec_geodes_opened(
             robots,
             inventory,
             max_overall_geodes,
+            cancel,
         );
         if geodes > max_geodes {
             max_geodes = geodes;
@@ -198,32 +230,57 @@ fn rec_geodes_opened(
     max_geodes
 }
 
-fn process(data: &str) -> usize {
-    let blueprints: Vec<Blueprint> = data.split("\n").map(|s| parse_blueprint(s)).collect();
-
-    blueprints
-        .iter()
-        .map(|b| b.id as usize * geodes_opened(*b, 24) as usize)
-        .sum()
+pub fn process(blueprints: &[Blueprint]) -> usize {
+    process_until(blueprints, &Cancel::never()).finished().unwrap()
 }
 
-fn process2(data: &str) -> usize {
-    let blueprints: Vec<Blueprint> = data
-        .split("\n")
-        .take(3)
-        .map(|s| parse_blueprint(s))
-        .collect();
+/// Like `process`, but stops once `cancel` says to. The best so far assumes
+/// no geodes from the blueprints that weren't finished.
+pub fn process_until(blueprints: &[Blueprint], cancel: &Cancel) -> Search<usize> {
+    let mut progress = Progress::new("blueprints", blueprints.len() as u64);
+    let mut quality_levels = 0;
+    for b in blueprints {
+        match geodes_opened(*b, 24, cancel) {
+            Search::Finished(geodes) => quality_levels += b.id as usize * geodes,
+            Search::Cancelled(geodes) => {
+                return Search::Cancelled(Some(quality_levels + b.id as usize * geodes.unwrap()))
+            }
+        }
+        progress.inc();
+    }
+    Search::Finished(quality_levels)
+}
 
-    blueprints
-        .iter()
-        .map(|b| geodes_opened(*b, 32) as usize)
-        .product()
+pub fn process2(blueprints: &[Blueprint]) -> usize {
+    process2_until(blueprints, &Cancel::never()).finished().unwrap()
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-    println!("{}", process2(data)); // process(data) for part 1
+// Unlike the sum in part one, a product has nothing useful to go on until
+// every blueprint has at least one geode
+pub fn process2_until(blueprints: &[Blueprint], cancel: &Cancel) -> Search<usize> {
+    let blueprints = &blueprints[..blueprints.len().min(3)];
+    let mut progress = Progress::new("blueprints", blueprints.len() as u64);
+    let mut best = Vec::new();
+    let mut cancelled = false;
+    for b in blueprints {
+        match geodes_opened(*b, 32, cancel) {
+            Search::Finished(geodes) => best.push(geodes),
+            Search::Cancelled(geodes) => {
+                best.push(geodes.unwrap());
+                cancelled = true;
+                break;
+            }
+        }
+        progress.inc();
+    }
+    let product = best.iter().product();
+    if !cancelled {
+        Search::Finished(product)
+    } else if best.len() == blueprints.len() && product > 0 {
+        Search::Cancelled(Some(product))
+    } else {
+        Search::Cancelled(None)
+    }
 }
 
 #[cfg(test)]
@@ -235,7 +292,6 @@ Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsid
 
     #[test]
     fn test_parse() {
-        let mut lines = DATA.split("\n");
         let line_1 = Blueprint {
             id: 1,
             ore_robot_ore_cost: 4,
@@ -254,22 +310,35 @@ Blueprint 2: Each ore robot costs 2 ore. Each clay robot costs 3 ore. Each obsid
             geode_robot_ore_cost: 3,
             geode_robot_obsidian_cost: 12,
         };
-        assert_eq!(parse_blueprint(lines.next().unwrap()), line_1);
-        assert_eq!(parse_blueprint(lines.next().unwrap()), line_2);
+        assert_eq!(parse(DATA), Ok(vec![line_1, line_2]));
+        assert_eq!(
+            parse("Blueprint 1: Each ore robot costs 4 ore. Each clay robot costs 2 ore."),
+            Err(ParseError::new(1, 65, "` ore. Each obsidian robot costs `", "` ore.`"))
+        );
+        assert_eq!(
+            parse("Blueprint one: Each ore robot costs 4 ore."),
+            Err(ParseError::new(1, 11, "a number", "`one:`"))
+        );
     }
 
     #[test]
     fn test_find_largest_number_of_geodes_that_can_be_opened() {
-        let mut lines = DATA.split("\n");
-        assert_eq!(geodes_opened(parse_blueprint(lines.next().unwrap()), 24), 9);
-        assert_eq!(
-            geodes_opened(parse_blueprint(lines.next().unwrap()), 24),
-            12
-        );
+        let blueprints = parse(DATA).unwrap();
+        assert_eq!(geodes_opened(blueprints[0], 24, &Cancel::never()), Search::Finished(9));
+        assert_eq!(geodes_opened(blueprints[1], 24, &Cancel::never()), Search::Finished(12));
     }
 
     #[test]
     fn test_part1() {
-        assert_eq!(process(DATA), 33);
+        assert_eq!(process(&parse(DATA).unwrap()), 33);
+    }
+
+    #[test]
+    fn test_cancel() {
+        let blueprints = parse(DATA).unwrap();
+        let cancel = Cancel::never();
+        cancel.cancel();
+        assert_eq!(process_until(&blueprints, &cancel), Search::Cancelled(Some(0)));
+        assert_eq!(process2_until(&blueprints, &cancel), Search::Cancelled(None));
     }
 }
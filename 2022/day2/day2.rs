@@ -1,25 +1,33 @@
-use std::fs;
+use aoc_common::parse::{lines, ParseError};
 
-fn main() {
-    let data = fs::read_to_string("input.txt").expect("Could not read input.txt");
-    let data = data.trim();
+/// The opponent's shape (`A`, `B` or `C`) and the second column (`X`, `Y`
+/// or `Z`) of one line of the strategy guide.
+pub type Round = (char, char);
 
+pub fn parse(data: &str) -> Result<Vec<Round>, ParseError> {
+    lines(data, |line| {
+        let opponent = line.one_of("ABC")?;
+        line.expect(" ")?;
+        Ok((opponent, line.one_of("XYZ")?))
+    })
+}
+
+pub fn process(rounds: &[Round]) -> u32 {
     let mut score = 0;
 
-    for line in data.split("\n") {
-        let choices: Vec<&str> = line.split(" ").collect();
-        score += match (choices[0], choices[1]) {
-            ("A", "X") => 1 + 3,
-            ("A", "Y") => 2 + 6,
-            ("A", "Z") => 3 + 0,
-            ("B", "X") => 1 + 0,
-            ("B", "Y") => 2 + 3,
-            ("B", "Z") => 3 + 6,
-            ("C", "X") => 1 + 6,
-            ("C", "Y") => 2 + 0,
-            ("C", "Z") => 3 + 3,
-            _ => panic!("Unknown combination {:?}", choices),
+    for &choices in rounds {
+        score += match choices {
+            ('A', 'X') => 1 + 3,
+            ('A', 'Y') => 2 + 6,
+            ('A', 'Z') => 3 + 0,
+            ('B', 'X') => 1 + 0,
+            ('B', 'Y') => 2 + 3,
+            ('B', 'Z') => 3 + 6,
+            ('C', 'X') => 1 + 6,
+            ('C', 'Y') => 2 + 0,
+            ('C', 'Z') => 3 + 3,
+            _ => unreachable!("Unknown combination {:?}", choices),
         };
     }
-    println!("{}", score);
+    score
 }
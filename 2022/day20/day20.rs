@@ -1,8 +1,9 @@
-use std::fs;
+use aoc_common::parse::{lines, ParseError};
+use aoc_common::{debug, trace};
 
 #[allow(dead_code)]
 fn print_current_order(original_order: &Vec<i64>, positions: &Vec<usize>) {
-    println!(
+    trace!(
         "{}\n",
         positions
             .iter()
@@ -12,11 +13,13 @@ fn print_current_order(original_order: &Vec<i64>, positions: &Vec<usize>) {
     );
 }
 
-fn process(data: &str, decryption_key: i64, mix_count: usize) -> i64 {
-    let original_order: Vec<i64> = data
-        .split("\n")
-        .map(|n| n.parse::<i64>().unwrap() * decryption_key)
-        .collect();
+/// The encrypted file, one number per line.
+pub fn parse(data: &str) -> Result<Vec<i64>, ParseError> {
+    lines(data, |line| line.number())
+}
+
+pub fn process(file: &[i64], decryption_key: i64, mix_count: usize) -> i64 {
+    let original_order: Vec<i64> = file.iter().map(|n| n * decryption_key).collect();
     let mut positions: Vec<usize> = (0..original_order.len()).collect();
 
     for _ in 0..mix_count {
@@ -35,8 +38,8 @@ fn process(data: &str, decryption_key: i64, mix_count: usize) -> i64 {
         .position(|x| *x == original_zero_pos)
         .unwrap();
 
-    println!(
-        "{} {} {}",
+    debug!(
+        "grove coordinates {} {} {}",
         original_order[positions[(start + 1000) % positions.len()]],
         original_order[positions[(start + 2000) % positions.len()]],
         original_order[positions[(start + 3000) % positions.len()]]
@@ -47,12 +50,6 @@ fn process(data: &str, decryption_key: i64, mix_count: usize) -> i64 {
         + original_order[positions[(start + 3000) % positions.len()]]
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-    println!("{}", process(data, 811589153, 10));
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -67,11 +64,19 @@ mod test {
 
     #[test]
     fn test_part1() {
-        assert_eq!(process(DATA, 1, 1), 3);
+        assert_eq!(process(&parse(DATA).unwrap(), 1, 1), 3);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(process(DATA, 811589153, 10), 1623178306);
+        assert_eq!(process(&parse(DATA).unwrap(), 811589153, 10), 1623178306);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("1\n2.5").unwrap_err(),
+            ParseError::new(2, 2, "end of line", "`.5`")
+        );
     }
 }
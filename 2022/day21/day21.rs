@@ -1,8 +1,10 @@
 use std::collections::HashMap;
-use std::fs;
 
-#[derive(Clone, Copy)]
-enum Monkey<'a> {
+use aoc_common::parse::{Lines, ParseError};
+use aoc_common::Part;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Monkey<'a> {
     Const(i64),
     Add(&'a str, &'a str),
     Sub(&'a str, &'a str),
@@ -75,50 +77,48 @@ fn get_value_for(
     }
 }
 
-fn parse(data: &str) -> (HashMap<&str, Monkey>, HashMap<&str, &str>) {
+/// Every monkey's job, and which monkey uses each one's number.
+pub type Troop<'a> = (HashMap<&'a str, Monkey<'a>>, HashMap<&'a str, &'a str>);
+
+pub fn parse(data: &str) -> Result<Troop<'_>, ParseError> {
     let mut monkeys: HashMap<&str, Monkey> = HashMap::new();
     let mut monkeys_used_by: HashMap<&str, &str> = HashMap::new();
-    for row in data.split("\n") {
-        let (monkey, op) = row.split_once(": ").unwrap();
-        let op: Vec<&str> = op.split(" ").collect();
-        monkeys.insert(
-            monkey,
-            match op.len() {
-                1 => Monkey::Const(op[0].parse().unwrap()),
-                3 => {
-                    assert_eq!(monkeys_used_by.insert(op[0], monkey), None);
-                    assert_eq!(monkeys_used_by.insert(op[2], monkey), None);
-                    match op[1] {
-                        "+" => Monkey::Add(op[0], op[2]),
-                        "-" => Monkey::Sub(op[0], op[2]),
-                        "*" => Monkey::Mul(op[0], op[2]),
-                        "/" => Monkey::Div(op[0], op[2]),
-                        other => panic!("Unknown operator {}", other),
-                    }
-                }
-                _ => panic!("Unknown operator {:?}", op),
-            },
-        );
+    for mut row in Lines::new(data) {
+        let monkey = row.word()?;
+        row.expect(": ")?;
+        let job = if row.remaining().starts_with(|c: char| c == '-' || c.is_ascii_digit()) {
+            Monkey::Const(row.number()?)
+        } else {
+            let a = row.word()?;
+            row.expect(" ")?;
+            let op = row.clone();
+            let operator = row.take_while(|c| c != ' ');
+            row.expect(" ")?;
+            let b = row.word()?;
+            assert_eq!(monkeys_used_by.insert(a, monkey), None);
+            assert_eq!(monkeys_used_by.insert(b, monkey), None);
+            match operator {
+                "+" => Monkey::Add(a, b),
+                "-" => Monkey::Sub(a, b),
+                "*" => Monkey::Mul(a, b),
+                "/" => Monkey::Div(a, b),
+                _ => return Err(op.error("one of `+`, `-`, `*` or `/`")),
+            }
+        };
+        row.end()?;
+        monkeys.insert(monkey, job);
     }
 
-    (monkeys, monkeys_used_by)
-}
-
-fn process1(data: &str) -> i64 {
-    let (monkeys, _monkeys_used_by) = parse(data);
-    eval_monkey(&monkeys, "root")
+    Ok((monkeys, monkeys_used_by))
 }
 
-fn process2(data: &str) -> i64 {
-    let (monkeys, monkeys_used_by) = parse(data);
-
-    get_value_for(&monkeys, &monkeys_used_by, "humn")
-}
-
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-    println!("{}", process2(data));
+pub fn process((monkeys, monkeys_used_by): &Troop, part: Part) -> i64 {
+    match part {
+        // What root yells
+        Part::One => eval_monkey(monkeys, "root"),
+        // What we have to yell for root's two numbers to match
+        Part::Two => get_value_for(monkeys, monkeys_used_by, "humn"),
+    }
 }
 
 #[cfg(test)]
@@ -141,13 +141,25 @@ lgvd: ljgn * ptdq
 drzm: hmdt - zczc
 hmdt: 32";
 
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("root: pppw % sjmn").unwrap_err(),
+            ParseError::new(1, 12, "one of `+`, `-`, `*` or `/`", "`%`")
+        );
+        assert_eq!(
+            parse("root: pppw + sjmn\ndbpl 5").unwrap_err(),
+            ParseError::new(2, 5, "`: `", "` 5`")
+        );
+    }
+
     #[test]
     fn test_part1() {
-        assert_eq!(process1(DATA), 152);
+        assert_eq!(process(&parse(DATA).unwrap(), Part::One), 152);
     }
 
     #[test]
     fn test_part2() {
-        assert_eq!(process2(DATA), 301);
+        assert_eq!(process(&parse(DATA).unwrap(), Part::Two), 301);
     }
 }
@@ -1,4 +1,18 @@
-use std::fs;
+use aoc_common::parse::{lines, ParseError};
+
+/// Each rucksack's items, one letter apiece.
+pub fn parse(data: &str) -> Result<Vec<&str>, ParseError> {
+    lines(data, |line| {
+        let items = line.take_while(|c| c.is_ascii_alphabetic());
+        if !line.is_empty() {
+            return Err(line.error("a letter"));
+        }
+        if items.len() % 2 == 1 {
+            return Err(line.error("an even number of items"));
+        }
+        Ok(items)
+    })
+}
 
 fn find_duplicated_char(first: &str, second: &str) -> char {
     for char in first.chars() {
@@ -17,9 +31,9 @@ fn priority(c: char) -> u32 {
     }
 }
 
-fn process(data: &str) -> u32 {
+pub fn process(rucksacks: &[&str]) -> u32 {
     let mut score = 0;
-    for line in data.split("\n") {
+    for line in rucksacks {
         let first_half = &line[0..line.len() / 2];
         let second_half = &line[line.len() / 2..line.len()];
         score += priority(find_duplicated_char(first_half, second_half));
@@ -27,13 +41,6 @@ fn process(data: &str) -> u32 {
     score
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").expect("Could not read input.txt");
-    let data = data.trim();
-
-    println!("{}", process(data));
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -45,6 +52,18 @@ PmmdzqPrVvPwwTWBwg
 wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
 ttgJtRGJQctTZtZT
 CrZsJsPPZsGzwwsLwLmpwMDw";
-        assert_eq!(process(data), 157);
+        assert_eq!(process(&parse(data).unwrap()), 157);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("vJrwpWtwJgWr\nab1c").unwrap_err(),
+            ParseError::new(2, 3, "a letter", "`1c`")
+        );
+        assert_eq!(
+            parse("abc").unwrap_err(),
+            ParseError::new(1, 4, "an even number of items", "end of line")
+        );
     }
 }
@@ -1,5 +1,3 @@
-use std::fs;
-
 fn find_duplicated_char(first: &str, second: &str, third: &str) -> char {
     for char in first.chars() {
         if second.contains(char) && third.contains(char) {
@@ -17,9 +15,8 @@ fn priority(c: char) -> u32 {
     }
 }
 
-fn process(data: &str) -> u32 {
+pub fn process(lines: &[&str]) -> u32 {
     let mut score = 0;
-    let lines: Vec<&str> = data.split("\n").collect();
     let mut i = 0;
     while i < lines.len() {
         score += priority(find_duplicated_char(lines[i], lines[i + 1], lines[i + 2]));
@@ -28,16 +25,10 @@ fn process(data: &str) -> u32 {
     score
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").expect("Could not read input.txt");
-    let data = data.trim();
-
-    println!("{}", process(data));
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::day3::parse;
     #[test]
     fn test() {
         let data = "vJrwpWtwJgWrhcsFMMfFFhFp
@@ -46,6 +37,6 @@ PmmdzqPrVvPwwTWBwg
 wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn
 ttgJtRGJQctTZtZT
 CrZsJsPPZsGzwwsLwLmpwMDw";
-        assert_eq!(process(data), 70);
+        assert_eq!(process(&parse(data).unwrap()), 70);
     }
 }
@@ -1,29 +1,28 @@
-use std::fs;
+use aoc_common::interval::IntervalSet;
+use aoc_common::parse::{lines, ParseError, Scanner};
 
-fn process(data: &str) -> u32 {
-    let mut contains_count = 0;
-    for line in data.split("\n") {
-        let ranges: Vec<&str> = line.split(",").collect();
-        let first: Vec<&str> = ranges[0].split("-").collect();
-        let second: Vec<&str> = ranges[1].split("-").collect();
-        let first_start: u32 = first[0].parse().unwrap();
-        let first_end: u32 = first[1].parse().unwrap();
-        let second_start: u32 = second[0].parse().unwrap();
-        let second_end: u32 = second[1].parse().unwrap();
-        if (first_start <= second_start && first_end >= second_end)
-            || (second_start <= first_start && second_end >= first_end)
-        {
-            contains_count += 1;
-        }
-    }
-    contains_count
+/// The sections assigned to each elf in a pair.
+pub type Pair = (IntervalSet, IntervalSet);
+
+fn sections(line: &mut Scanner) -> Result<IntervalSet, ParseError> {
+    let start = line.number()?;
+    line.expect("-")?;
+    Ok(IntervalSet::from(start..=line.number()?))
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").expect("Could not read input.txt");
-    let data = data.trim();
+pub fn parse(data: &str) -> Result<Vec<Pair>, ParseError> {
+    lines(data, |line| {
+        let first = sections(line)?;
+        line.expect(",")?;
+        Ok((first, sections(line)?))
+    })
+}
 
-    println!("{}", process(data));
+pub fn process(pairs: &[Pair]) -> usize {
+    pairs
+        .iter()
+        .filter(|(first, second)| first.is_superset(second) || second.is_superset(first))
+        .count()
 }
 
 #[cfg(test)]
@@ -37,6 +36,14 @@ mod tests {
 2-8,3-7
 6-6,4-6
 2-6,4-8";
-        assert_eq!(process(data), 2);
+        assert_eq!(process(&parse(data).unwrap()), 2);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("2-4,6-8\n2-3;4-5"),
+            Err(ParseError::new(2, 4, "`,`", "`;`"))
+        );
+    }
+}
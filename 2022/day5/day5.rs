@@ -1,44 +1,92 @@
 use std::collections::VecDeque;
-use std::fs;
 
-fn process(data: &str) -> String {
-    let parts: Vec<&str> = data.split("\n\n").collect();
+use aoc_common::parse::{Lines, ParseError, Scanner};
 
-    let mut stack_lines: Vec<&str> = parts[0].split("\n").collect();
-    stack_lines.truncate(stack_lines.len() - 1); // Remove indexing line
+/// One step of the rearrangement: how many crates move, and between which
+/// stacks (counting from 0).
+#[derive(Clone, Copy, Debug)]
+pub struct Move {
+    pub count: usize,
+    pub from: usize,
+    pub to: usize,
+}
+
+/// The stacks, each from its top crate down, and the moves to make.
+pub type Drawing = (Vec<VecDeque<char>>, Vec<Move>);
 
-    let instructions: Vec<&str> = parts[1].trim().split("\n").collect();
+// A stack's number, which counts from 1 in the input
+fn stack(line: &mut Scanner, stack_count: usize) -> Result<usize, ParseError> {
+    let column = line.column();
+    match line.number()? {
+        n @ 1.. if n <= stack_count => Ok(n - 1),
+        n => Err(ParseError::new(
+            line.line(),
+            column,
+            format!("a stack from 1 to {}", stack_count),
+            format!("`{}`", n),
+        )),
+    }
+}
 
-    let mut stacks = Vec::new();
-    for _ in 0..(stack_lines[0].len() + 1) / 4 {
-        stacks.push(VecDeque::new());
+pub fn parse(data: &str) -> Result<Drawing, ParseError> {
+    let mut lines = Lines::new(data);
+    let mut stack_lines = Vec::new();
+    let mut line = lines.next_line()?;
+    while line.remaining().contains('[') {
+        stack_lines.push(line);
+        line = lines.next_line()?;
     }
 
-    for line in stack_lines {
-        for i in 0..stacks.len() {
-            let c = line.chars().nth(i * 4 + 1).unwrap();
-            if c != ' ' {
-                stacks[i].push_back(c);
+    // The indexing line says how many stacks there are
+    line.take_while(|c| c == ' ');
+    let stack_count = line.list("   ", |n| n.number::<usize>())?.len();
+    line.take_while(|c| c == ' ');
+    line.end()?;
+
+    let mut stacks = vec![VecDeque::new(); stack_count];
+    for mut line in stack_lines {
+        for (i, stack) in stacks.iter_mut().enumerate() {
+            // Trailing spaces are often lost when copying the drawing
+            if line.is_empty() {
+                break;
+            }
+            if i > 0 {
+                line.expect(" ")?;
+            }
+            if line.eat("[") {
+                stack.push_back(line.one_of("ABCDEFGHIJKLMNOPQRSTUVWXYZ")?);
+                line.expect("]")?;
+            } else {
+                line.expect("   ")?;
             }
         }
+        line.end()?;
     }
+    lines.next_line()?.end()?;
 
-    for instruction in instructions {
-        let instruction: Vec<&str> = instruction.split(" ").collect();
-        let source: usize = instruction[3].parse().unwrap();
-        let target: usize = instruction[5].parse().unwrap();
-        for _ in 0..instruction[1].parse().unwrap() {
-            let c = stacks[source-1].pop_front().unwrap();
-            stacks[target-1].push_front(c);
-        }
+    let mut moves = Vec::new();
+    for mut line in lines {
+        line.expect("move ")?;
+        let count = line.number()?;
+        line.expect(" from ")?;
+        let from = stack(&mut line, stack_count)?;
+        line.expect(" to ")?;
+        let to = stack(&mut line, stack_count)?;
+        line.end()?;
+        moves.push(Move { count, from, to });
     }
-    stacks.iter().map(|s| s.front().unwrap()).collect()
+    Ok((stacks, moves))
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-
-    println!("{}", process(&data));
+pub fn process((stacks, moves): &Drawing) -> String {
+    let mut stacks = stacks.clone();
+    for instruction in moves {
+        for _ in 0..instruction.count {
+            let c = stacks[instruction.from].pop_front().unwrap();
+            stacks[instruction.to].push_front(c);
+        }
+    }
+    stacks.iter().filter_map(|s| s.front()).collect()
 }
 
 #[cfg(test)]
@@ -55,6 +103,6 @@ move 1 from 2 to 1
 move 3 from 1 to 3
 move 2 from 2 to 1
 move 1 from 1 to 2";
-        assert!(process(data) == "CMZ");
+        assert!(process(&parse(data).unwrap()) == "CMZ");
     }
 }
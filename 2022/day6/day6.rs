@@ -1,7 +1,16 @@
-use std::fs;
+use aoc_common::parse::{ParseError, Scanner};
 
-fn process(data: &str, len: usize) -> usize {
-    let chars: Vec<char> = data.chars().collect();
+/// The datastream, which is a single line of letters.
+pub fn parse(data: &str) -> Result<Vec<char>, ParseError> {
+    let mut line = Scanner::new(1, data);
+    let chars = line.take_while(|c| c.is_ascii_lowercase()).chars().collect();
+    if !line.is_empty() {
+        return Err(line.error("a lowercase letter"));
+    }
+    Ok(chars)
+}
+
+pub fn process(chars: &[char], len: usize) -> usize {
     for i in len..chars.len() {
         let mut chars = chars[i-len..i].to_vec(); // TODO: this seems unidiomatic?
         chars.sort();
@@ -14,12 +23,6 @@ fn process(data: &str, len: usize) -> usize {
     panic!("No valid marker found");
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-    println!("{}", process(data, 14)); // 4 for part A
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -33,7 +36,7 @@ mod test {
             ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 11),
         ];
         for (datum, result) in data {
-            assert!(process(datum, 4) == result);
+            assert!(process(&parse(datum).unwrap(), 4) == result);
         }
     }
     #[test]
@@ -46,7 +49,15 @@ mod test {
             ("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw", 26),
         ];
         for (datum, result) in data {
-            assert!(process(datum, 14) == result);
+            assert!(process(&parse(datum).unwrap(), 14) == result);
         }
     }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("abc1").unwrap_err(),
+            ParseError::new(1, 4, "a lowercase letter", "`1`")
+        );
+    }
 }
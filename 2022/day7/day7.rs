@@ -1,13 +1,23 @@
 use std::collections::HashMap;
-use std::fs;
 
-#[derive(Debug)]
-struct Directory {
+use aoc_common::parse::{Lines, ParseError, Scanner};
+use aoc_common::{debug, Part};
+
+#[derive(Debug, Default)]
+pub struct Directory {
     children: HashMap<String, Directory>,
     files: HashMap<String, u32>,
 }
 
 impl Directory {
+    fn at(&mut self, path: &[&str]) -> &mut Directory {
+        let mut pwd = self;
+        for dir in path {
+            pwd = pwd.children.get_mut(*dir).unwrap();
+        }
+        pwd
+    }
+
     fn size(&self) -> u32 {
         let mut size = 0;
         for (_name, child) in &self.children {
@@ -42,58 +52,66 @@ impl Directory {
     }
 }
 
-fn process(data: &str) -> u32 {
-    let lines: Vec<&str> = data.split("\n").collect();
-    let mut root = Directory {
-        children: HashMap::new(),
-        files: HashMap::new(),
-    };
+fn name<'a>(line: &mut Scanner<'a>) -> Result<&'a str, ParseError> {
+    let name = line.take_while(|c| !c.is_whitespace());
+    if name.is_empty() {
+        return Err(line.error("a name"));
+    }
+    line.end()?;
+    Ok(name)
+}
+
+/// The filesystem, as pieced together from the terminal output.
+pub fn parse(data: &str) -> Result<Directory, ParseError> {
+    let mut root = Directory::default();
     let mut path = Vec::new();
-    for line in lines {
-        let line: Vec<&str> = line.split(" ").collect();
-        if line[0] == "$" {
-            match line[1] {
-                "cd" => match line[2] {
-                    "/" => path = Vec::new(),
-                    ".." => path.truncate(path.len() - 1),
-                    dir => path.push(dir),
-                },
-                "ls" => {}
-                _ => panic!("Unknown command"),
+    for mut line in Lines::new(data) {
+        if line.eat("$ ") {
+            if line.eat("cd ") {
+                if line.eat("/") {
+                    path = Vec::new();
+                    line.end()?;
+                } else if line.eat("..") {
+                    path.pop();
+                    line.end()?;
+                } else if root.at(&path).children.contains_key(line.remaining()) {
+                    path.push(name(&mut line)?);
+                } else {
+                    return Err(line.error("a directory that `ls` has listed"));
+                }
+            } else {
+                line.expect("ls")?;
+                line.end()?;
             }
         } else {
             // Part of `ls` output
-            let mut pwd = &mut root;
-            for dir in path.iter() {
-                pwd = pwd.children.get_mut(&dir.to_string()).unwrap();
-            }
-            if line[0] == "dir" {
-                if pwd.children.contains_key(line[1]) {
-                    println!("We've seen {} before", line[1]);
+            let pwd = root.at(&path);
+            if line.eat("dir ") {
+                let dir = name(&mut line)?;
+                if pwd.children.contains_key(dir) {
+                    debug!("We've seen {} before", dir);
                 } else {
-                    pwd.children.insert(
-                        line[1].to_string(),
-                        Directory {
-                            children: HashMap::new(),
-                            files: HashMap::new(),
-                        },
-                    );
+                    pwd.children.insert(dir.to_string(), Directory::default());
                 }
             } else {
-                pwd.files
-                    .insert(line[1].to_string(), line[0].parse().unwrap());
+                let size = line.number()?;
+                line.expect(" ")?;
+                pwd.files.insert(name(&mut line)?.to_string(), size);
             }
         }
     }
-    let size_available = 70_000_000 - root.size();
-    let size_needed = 30_000_000 - size_available;
-    root.smallest_directory_at_least(size_needed) // directories_at_most_size() for part 1
+    Ok(root)
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-    println!("{}", process(data));
+pub fn process(root: &Directory, part: Part) -> u32 {
+    match part {
+        Part::One => root.directories_at_most_size(100_000),
+        Part::Two => {
+            let size_available = 70_000_000 - root.size();
+            let size_needed = 30_000_000 - size_available;
+            root.smallest_directory_at_least(size_needed)
+        }
+    }
 }
 
 #[cfg(test)]
@@ -125,7 +143,24 @@ $ ls
 7214296 k";
 
     #[test]
-    fn test() {
-        assert!(process(DATA) == 24933642); // 95437 for part 1
+    fn test_part1() {
+        assert_eq!(process(&parse(DATA).unwrap(), Part::One), 95437);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(process(&parse(DATA).unwrap(), Part::Two), 24933642);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("$ cd /\n$ cd a").unwrap_err(),
+            ParseError::new(2, 6, "a directory that `ls` has listed", "`a`")
+        );
+        assert_eq!(
+            parse("$ cd /\n$ rm a").unwrap_err(),
+            ParseError::new(2, 3, "`ls`", "`rm`")
+        );
     }
 }
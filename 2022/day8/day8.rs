@@ -1,65 +1,32 @@
-use std::fs;
+use aoc_common::grid::Grid;
+use aoc_common::parse::ParseError;
 
-fn is_visible(forest: &Vec<Vec<u8>>, row: usize, column: usize) -> bool {
-    let height = forest[row][column];
-    let mut hidden_from_north = false;
-    for i in 0..row {
-        if forest[i][column] >= height {
-            hidden_from_north = true;
-            break;
-        }
-    }
-    let mut hidden_from_south = false;
-    for i in row + 1..forest.len() {
-        if forest[i][column] >= height {
-            hidden_from_south = true;
-            break;
-        }
-    }
-    let mut hidden_from_west = false;
-    for i in 0..column {
-        if forest[row][i] >= height {
-            hidden_from_west = true;
-            break;
-        }
-    }
-    let mut hidden_from_east = false;
-    for i in column + 1..forest[0].len() {
-        if forest[row][i] >= height {
-            hidden_from_east = true;
-            break;
-        }
-    }
+pub fn parse(data: &str) -> Result<Grid<u8>, ParseError> {
+    Grid::digits(data)
+}
+
+fn is_visible(forest: &Grid<u8>, row: usize, column: usize) -> bool {
+    let height = forest[(row, column)];
+    let trees_in_row = forest.row(row);
+    let trees_in_column: Vec<u8> = forest.column(column).copied().collect();
+    let hidden_behind = |trees: &[u8]| trees.iter().any(|&tree| tree >= height);
+    let hidden_from_north = hidden_behind(&trees_in_column[..row]);
+    let hidden_from_south = hidden_behind(&trees_in_column[row + 1..]);
+    let hidden_from_west = hidden_behind(&trees_in_row[..column]);
+    let hidden_from_east = hidden_behind(&trees_in_row[column + 1..]);
     !hidden_from_north || !hidden_from_south || !hidden_from_west || !hidden_from_east
 }
 
-fn process(data: &str) -> u32 {
-    let mut forest: Vec<Vec<u8>> = Vec::new();
-    for line in data.split("\n") {
-        let mut tree_row = Vec::new();
-        for c in line.chars() {
-            tree_row.push(c.to_digit(10).unwrap() as u8);
-        }
-        forest.push(tree_row);
-    }
+pub fn process(forest: &Grid<u8>) -> u32 {
     let mut visible_count = 0;
-    for row in 0..forest.len() {
-        for column in 0..forest[0].len() {
-            if is_visible(&forest, row, column) {
-                visible_count += 1;
-            }
+    for (row, column) in forest.points() {
+        if is_visible(forest, row, column) {
+            visible_count += 1;
         }
     }
     visible_count
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-
-    println!("{}", process(data));
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -72,6 +39,6 @@ mod test {
 
     #[test]
     fn test() {
-        assert!(process(DATA) == 21);
+        assert!(process(&parse(DATA).unwrap()) == 21);
     }
 }
@@ -1,14 +1,26 @@
 use std::cmp::Ordering;
-use std::collections::HashMap;
-use std::fs;
 
-fn do_move(direction: &str, positions: &mut Vec<(i32, i32)>) {
+use aoc_common::parse::{lines, ParseError};
+use aoc_common::sparse::{Point, SparseGrid};
+
+/// Which way the head moves (`U`, `D`, `L` or `R`), and how far.
+pub type Motion = (char, u32);
+
+pub fn parse(data: &str) -> Result<Vec<Motion>, ParseError> {
+    lines(data, |line| {
+        let direction = line.one_of("UDLR")?;
+        line.expect(" ")?;
+        Ok((direction, line.number()?))
+    })
+}
+
+fn do_move(direction: char, positions: &mut Vec<Point>) {
     match direction {
-        "U" => positions[0].1 += 1,
-        "D" => positions[0].1 -= 1,
-        "L" => positions[0].0 -= 1,
-        "R" => positions[0].0 += 1,
-        _ => panic!("Illegal direction {}", direction),
+        'U' => positions[0].1 += 1,
+        'D' => positions[0].1 -= 1,
+        'L' => positions[0].0 -= 1,
+        'R' => positions[0].0 += 1,
+        _ => unreachable!("Illegal direction {}", direction),
     }
 
     for i in 1..positions.len() {
@@ -29,29 +41,21 @@ fn do_move(direction: &str, positions: &mut Vec<(i32, i32)>) {
     }
 }
 
-fn process(data: &str, knot_count: usize) -> usize {
+pub fn process(motions: &[Motion], knot_count: usize) -> usize {
     let mut positions = Vec::new();
     for _ in 0..knot_count {
         positions.push((0, 0));
     }
-    let mut visited_positions = HashMap::new();
-    for row in data.split("\n") {
-        let command: Vec<&str> = row.split(" ").collect();
-        let direction = command[0];
-        for _ in 0..command[1].parse().unwrap() {
+    let mut visited_positions = SparseGrid::new();
+    for &(direction, distance) in motions {
+        for _ in 0..distance {
             do_move(direction, &mut positions);
-            visited_positions.insert(positions[positions.len() - 1], true);
+            visited_positions.add(positions[positions.len() - 1]);
         }
     }
     visited_positions.len()
 }
 
-fn main() {
-    let data = fs::read_to_string("input.txt").unwrap();
-    let data = data.trim();
-    println!("{}", process(data, 10));
-}
-
 #[cfg(test)]
 mod test {
     use super::*;
@@ -69,9 +73,9 @@ R 2";
     fn test_move() {
         let cases = vec![
             // positions, dir, new_positions
-            (vec![(1, 0), (0, 0)], "R", vec![(2, 0), (1, 0)]),
-            (vec![(1, -2), (1, -1)], "D", vec![(1, -3), (1, -2)]),
-            (vec![(2, -2), (1, -3)], "R", vec![(3, -2), (2, -2)]),
+            (vec![(1, 0), (0, 0)], 'R', vec![(2, 0), (1, 0)]),
+            (vec![(1, -2), (1, -1)], 'D', vec![(1, -3), (1, -2)]),
+            (vec![(2, -2), (1, -3)], 'R', vec![(3, -2), (2, -2)]),
         ];
         for (original_positions, dir, new_positions) in cases {
             let mut positions = original_positions.clone();
@@ -83,8 +87,8 @@ R 2";
 
     #[test]
     fn test_full() {
-        assert!(process(DATA, 2) == 13);
-        assert!(process(DATA, 10) == 1);
+        assert!(process(&parse(DATA).unwrap(), 2) == 13);
+        assert!(process(&parse(DATA).unwrap(), 10) == 1);
     }
 
     #[test]
@@ -97,6 +101,18 @@ R 17
 D 10
 L 25
 U 20";
-        assert!(process(data, 10) == 36);
+        assert!(process(&parse(data).unwrap(), 10) == 36);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("R 4\nX 2").unwrap_err(),
+            ParseError::new(2, 1, "one of `UDLR`", "`X`")
+        );
+        assert_eq!(
+            parse("R x").unwrap_err(),
+            ParseError::new(1, 3, "a number", "`x`")
+        );
     }
 }
@@ -0,0 +1,90 @@
+//! The 2022 solutions. Each day's first part lives in `dayN/dayN.rs`, and
+//! where the second part needed more than a different argument, it's in
+//! `dayN/dayNb.rs` alongside.
+// The original solutions predate running clippy over them; keep the lints
+// they trip quiet rather than rewriting every day.
+#![allow(
+    clippy::clone_on_copy,
+    clippy::comparison_to_empty,
+    clippy::for_kv_map,
+    clippy::identity_op,
+    clippy::len_zero,
+    clippy::manual_is_multiple_of,
+    clippy::needless_borrow,
+    clippy::needless_range_loop,
+    clippy::ptr_arg,
+    clippy::redundant_field_names,
+    clippy::unnecessary_cast,
+    clippy::unnecessary_to_owned,
+    clippy::unwrap_or_default,
+    clippy::useless_vec,
+    clippy::while_let_loop,
+)]
+#[path = "../day1/day1.rs"]
+pub mod day1;
+#[path = "../day1/day1b.rs"]
+pub mod day1b;
+#[path = "../day2/day2.rs"]
+pub mod day2;
+#[path = "../day2/day2b.rs"]
+pub mod day2b;
+#[path = "../day3/day3.rs"]
+pub mod day3;
+#[path = "../day3/day3b.rs"]
+pub mod day3b;
+#[path = "../day4/day4.rs"]
+pub mod day4;
+#[path = "../day4/day4b.rs"]
+pub mod day4b;
+#[path = "../day5/day5.rs"]
+pub mod day5;
+#[path = "../day5/day5b.rs"]
+pub mod day5b;
+#[path = "../day6/day6.rs"]
+pub mod day6;
+#[path = "../day7/day7.rs"]
+pub mod day7;
+#[path = "../day8/day8.rs"]
+pub mod day8;
+#[path = "../day8/day8b.rs"]
+pub mod day8b;
+#[path = "../day9/day9.rs"]
+pub mod day9;
+#[path = "../day10/day10.rs"]
+pub mod day10;
+#[path = "../day10/day10b.rs"]
+pub mod day10b;
+#[path = "../day11/day11.rs"]
+pub mod day11;
+#[path = "../day12/day12.rs"]
+pub mod day12;
+#[path = "../day12/day12b.rs"]
+pub mod day12b;
+#[path = "../day13/day13.rs"]
+pub mod day13;
+#[path = "../day14/day14.rs"]
+pub mod day14;
+#[path = "../day14/day14b.rs"]
+pub mod day14b;
+#[path = "../day15/day15.rs"]
+pub mod day15;
+#[path = "../day15/day15b.rs"]
+pub mod day15b;
+#[path = "../day16/day16.rs"]
+pub mod day16;
+#[path = "../day16/day16b.rs"]
+pub mod day16b;
+#[path = "../day17/day17.rs"]
+pub mod day17;
+#[path = "../day18/day18.rs"]
+pub mod day18;
+#[path = "../day19/day19.rs"]
+pub mod day19;
+#[path = "../day20/day20.rs"]
+pub mod day20;
+#[path = "../day21/day21.rs"]
+pub mod day21;
+#[path = "../day22/day22.rs"]
+pub mod day22;
+#[path = "../day23/day23.rs"]
+pub mod day23;
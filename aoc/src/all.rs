@@ -0,0 +1,233 @@
+// Runs every part of every day at once on a pool of worker threads. Each part
+// runs on a thread of its own so that one that's still going when its time
+// is up can be given up on. The slow searches are told to stop, and get a
+// moment to hand back the best answer they'd found; anything else is left to
+// finish in the background, since there's no way to stop a thread from
+// outside. The worker that started such a part leaves the pool for good, so
+// there are never more parts running than workers.
+use std::collections::VecDeque;
+use std::sync::{mpsc, Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use aoc_common::cancel::Cancel;
+
+use crate::ledger::Ledger;
+use crate::verify::{run_part, Check, Status};
+use crate::Puzzle;
+
+// How long a cancelled search gets to wind down
+const GRACE: Duration = Duration::from_millis(500);
+
+/// Like `verify::verify`, but with up to `workers` parts running at a time
+/// and any part that takes longer than `timeout` marked as timed out. Also
+/// returns how many timed out parts were left running; once they've used up
+/// every worker, the parts still waiting are skipped.
+pub fn run_all<F>(
+    puzzles: impl Iterator<Item = &'static Puzzle>,
+    ledger: &Ledger,
+    input: F,
+    workers: usize,
+    timeout: Duration,
+) -> (Vec<Check>, usize)
+where
+    F: Fn(u16, u8) -> Option<String>,
+{
+    let mut checks = Vec::new();
+    let mut jobs = VecDeque::new();
+    for puzzle in puzzles {
+        let input = input(puzzle.year, puzzle.day).map(Arc::new);
+        for part in 1..=2 {
+            if let Some(input) = &input {
+                jobs.push_back((checks.len(), puzzle, part, Arc::clone(input)));
+            }
+            checks.push(Check::new(puzzle, part));
+        }
+    }
+
+    let jobs = Mutex::new(jobs);
+    let checks = Mutex::new(checks);
+    let leaked = Mutex::new(0);
+    thread::scope(|scope| {
+        for _ in 0..workers.max(1) {
+            scope.spawn(|| loop {
+                let Some((i, puzzle, part, input)) = jobs.lock().unwrap().pop_front() else {
+                    break;
+                };
+                let (sender, receiver) = mpsc::channel();
+                let cancel = Cancel::never();
+                let search = cancel.clone();
+                thread::spawn(move || {
+                    // The receiver is gone if this took too long
+                    let _ = sender.send(run_part(puzzle, &input, part, &search));
+                });
+                let result = receiver.recv_timeout(timeout).or_else(|_| {
+                    cancel.cancel();
+                    receiver.recv_timeout(GRACE)
+                });
+                let mut checks = checks.lock().unwrap();
+                match result {
+                    Ok((run, elapsed)) => checks[i].record(run, elapsed, ledger),
+                    Err(_) => {
+                        checks[i].status = Status::TimedOut;
+                        checks[i].elapsed = Some(timeout);
+                        // The part's thread is still going, and has this
+                        // worker's place until it's done
+                        *leaked.lock().unwrap() += 1;
+                        break;
+                    }
+                }
+            });
+        }
+    });
+
+    let mut checks = checks.into_inner().unwrap();
+    for (i, ..) in jobs.into_inner().unwrap() {
+        checks[i].status = Status::Skipped;
+    }
+    (checks, leaked.into_inner().unwrap())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Answer, Solution};
+    use aoc_common::cancel::Search;
+    use aoc_common::parse::ParseError;
+
+    struct Example;
+
+    impl Solution for Example {
+        type Input<'a> = u64;
+
+        fn parse(input: &str) -> Result<u64, ParseError> {
+            Ok(input.parse().unwrap())
+        }
+
+        fn part1(input: &u64) -> Option<Answer> {
+            Some((input * 2).into())
+        }
+
+        // Sleeps for the input's number of milliseconds
+        fn part2(input: &u64) -> Option<Answer> {
+            if *input == 0 {
+                panic!("can't handle zero");
+            }
+            thread::sleep(Duration::from_millis(*input));
+            Some((*input).into())
+        }
+    }
+
+    // Keeps improving on its answer until it's told to stop
+    struct Improving;
+
+    impl Solution for Improving {
+        type Input<'a> = ();
+
+        fn parse(_input: &str) -> Result<(), ParseError> {
+            Ok(())
+        }
+
+        fn part1(_input: &()) -> Option<Answer> {
+            None
+        }
+
+        fn part2(_input: &()) -> Option<Answer> {
+            None
+        }
+
+        fn part2_until(_input: &(), cancel: &Cancel) -> Option<Search<Answer>> {
+            let mut best = 0;
+            while !cancel.is_cancelled() {
+                thread::sleep(Duration::from_millis(1));
+                best += 1;
+            }
+            Some(Search::Cancelled(Some(best.into())))
+        }
+    }
+
+    static PUZZLES: &[Puzzle] = &[
+        Puzzle::new(2022, 1, &Example),
+        Puzzle::new(2022, 2, &Example),
+        Puzzle::new(2022, 3, &Example),
+        Puzzle::new(2022, 4, &Example),
+    ];
+
+    #[test]
+    fn test_run_all() {
+        let ledger = Ledger::parse("2022 1 1 2\n2022 1 2 5\n").unwrap();
+        let (checks, leaked) = run_all(
+            PUZZLES.iter(),
+            &ledger,
+            |_, day| match day {
+                1 => Some(String::from("1")),
+                2 => Some(String::from("0")),
+                3 => Some(String::from("10000")),
+                _ => None,
+            },
+            3,
+            Duration::from_millis(200),
+        );
+
+        let statuses: Vec<(u8, u8, &Status)> =
+            checks.iter().map(|c| (c.day, c.part, &c.status)).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                (1, 1, &Status::Ok),
+                (1, 2, &Status::Mismatch(String::from("5"))),
+                (2, 1, &Status::Unverified),
+                (2, 2, &Status::Panicked(String::from("can't handle zero"))),
+                (3, 1, &Status::Unverified),
+                (3, 2, &Status::TimedOut),
+                (4, 1, &Status::NoInput),
+                (4, 2, &Status::NoInput),
+            ]
+        );
+        assert_eq!(checks[5].elapsed, Some(Duration::from_millis(200)));
+        assert_eq!(checks[4].answer, Some(Answer::Number(20000)));
+        assert_eq!(checks.iter().filter(|c| c.failed()).count(), 3);
+        assert_eq!(leaked, 1);
+    }
+
+    #[test]
+    fn test_leaked() {
+        // Day 1's second part is still sleeping when it times out, which
+        // leaves the only worker tied up
+        let (checks, leaked) = run_all(
+            PUZZLES[..2].iter(),
+            &Ledger::default(),
+            |_, day| Some(String::from(if day == 1 { "10000" } else { "1" })),
+            1,
+            Duration::from_millis(50),
+        );
+        let statuses: Vec<&Status> = checks.iter().map(|c| &c.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                &Status::Unverified,
+                &Status::TimedOut,
+                &Status::Skipped,
+                &Status::Skipped,
+            ]
+        );
+        assert_eq!(leaked, 1);
+        assert!(checks[2].failed());
+    }
+
+    #[test]
+    fn test_cancelled() {
+        static PUZZLES: &[Puzzle] = &[Puzzle::new(2022, 1, &Improving)];
+        let (checks, leaked) = run_all(
+            PUZZLES.iter(),
+            &Ledger::default(),
+            |_, _| Some(String::new()),
+            1,
+            Duration::from_millis(50),
+        );
+        assert_eq!(checks[1].status, Status::TimedOut);
+        assert!(checks[1].answer.is_some());
+        // It stopped when told to, so its worker carried on
+        assert_eq!(leaked, 0);
+    }
+}
@@ -0,0 +1,228 @@
+// Repeatedly times each stage of a solver, and compares the results against an
+// earlier run so slowdowns (and speedups) stand out.
+use std::time::Duration;
+
+use aoc_common::parse::ParseError;
+use serde::{Deserialize, Serialize};
+
+use crate::report;
+use crate::Puzzle;
+
+#[derive(Clone, Copy, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Stage {
+    Parse,
+    Part1,
+    Part2,
+}
+
+impl Stage {
+    fn name(self) -> &'static str {
+        match self {
+            Stage::Parse => "parse",
+            Stage::Part1 => "part1",
+            Stage::Part2 => "part2",
+        }
+    }
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Stats {
+    pub year: u16,
+    pub day: u8,
+    pub stage: Stage,
+    pub runs: usize,
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub max_ns: u64,
+}
+
+impl Stats {
+    fn new(year: u16, day: u8, stage: Stage, mut samples: Vec<Duration>) -> Stats {
+        samples.sort();
+        let nanos = |d: Duration| d.as_nanos() as u64;
+        Stats {
+            year,
+            day,
+            stage,
+            runs: samples.len(),
+            min_ns: nanos(samples[0]),
+            median_ns: nanos(samples[samples.len() / 2]),
+            max_ns: nanos(samples[samples.len() - 1]),
+        }
+    }
+}
+
+/// Times `runs` full runs of a puzzle. Unsolved parts are left out.
+pub fn bench(puzzle: &Puzzle, input: &str, runs: usize) -> Result<Vec<Stats>, ParseError> {
+    let timings = (0..runs.max(1))
+        .map(|_| puzzle.solver.time(input))
+        .collect::<Result<Vec<_>, _>>()?;
+    let parse: Vec<Duration> = timings.iter().map(|t| t.parse).collect();
+    let part1: Vec<Duration> = timings.iter().filter_map(|t| t.part1).collect();
+    let part2: Vec<Duration> = timings.iter().filter_map(|t| t.part2).collect();
+    let mut stats = Vec::new();
+    for (stage, samples) in [
+        (Stage::Parse, parse),
+        (Stage::Part1, part1),
+        (Stage::Part2, part2),
+    ] {
+        if !samples.is_empty() {
+            stats.push(Stats::new(puzzle.year, puzzle.day, stage, samples));
+        }
+    }
+    Ok(stats)
+}
+
+pub fn to_json(results: &[Stats]) -> String {
+    serde_json::to_string_pretty(results).unwrap()
+}
+
+pub fn from_json(json: &str) -> Result<Vec<Stats>, String> {
+    serde_json::from_str(json).map_err(|e| e.to_string())
+}
+
+// "+12.5%" when `new` is slower than `old`, "-40.0%" when it's faster.
+fn format_change(old: u64, new: u64) -> String {
+    if old == 0 {
+        return String::new();
+    }
+    format!("{:+.1}%", (new as f64 - old as f64) / old as f64 * 100.0)
+}
+
+/// A table of `results`, with the median from `previous` (where there is
+/// one for the same stage) alongside for comparison.
+pub fn format_table(results: &[Stats], previous: Option<&[Stats]>) -> String {
+    let mut header: Vec<String> = ["year", "day", "stage", "min", "median", "max"]
+        .iter()
+        .map(|h| h.to_string())
+        .collect();
+    if previous.is_some() {
+        header.push(String::from("previous"));
+        header.push(String::from("change"));
+    }
+
+    let mut rows = vec![header];
+    for stats in results {
+        let duration = |nanos| report::format_duration(Duration::from_nanos(nanos));
+        let mut row = vec![
+            stats.year.to_string(),
+            stats.day.to_string(),
+            stats.stage.name().to_string(),
+            duration(stats.min_ns),
+            duration(stats.median_ns),
+            duration(stats.max_ns),
+        ];
+        let old = previous.and_then(|previous| {
+            previous
+                .iter()
+                .find(|p| p.year == stats.year && p.day == stats.day && p.stage == stats.stage)
+        });
+        if let Some(old) = old {
+            row.push(duration(old.median_ns));
+            row.push(format_change(old.median_ns, stats.median_ns));
+        }
+        rows.push(row);
+    }
+    report::table(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{Answer, Solution};
+    use aoc_common::parse::Scanner;
+
+    struct Example;
+
+    impl Solution for Example {
+        type Input<'a> = Vec<u64>;
+
+        fn parse(input: &str) -> Result<Vec<u64>, ParseError> {
+            Scanner::new(1, input).list(",", |n| n.number())
+        }
+
+        fn part1(input: &Vec<u64>) -> Option<Answer> {
+            Some(input.iter().sum::<u64>().into())
+        }
+
+        fn part2(_input: &Vec<u64>) -> Option<Answer> {
+            None
+        }
+    }
+
+    fn stats(day: u8, stage: Stage, median_ns: u64) -> Stats {
+        Stats {
+            year: 2022,
+            day,
+            stage,
+            runs: 5,
+            min_ns: median_ns / 2,
+            median_ns,
+            max_ns: median_ns * 2,
+        }
+    }
+
+    #[test]
+    fn test_bench() {
+        let puzzle = Puzzle::new(2022, 1, &Example);
+        let results = bench(&puzzle, "1,2,3", 5).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].stage, Stage::Parse);
+        assert_eq!(results[1].stage, Stage::Part1);
+        for stats in results {
+            assert_eq!(stats.runs, 5);
+            assert!(stats.min_ns <= stats.median_ns && stats.median_ns <= stats.max_ns);
+        }
+        assert_eq!(bench(&puzzle, "1,x", 5).unwrap_err().column, 3);
+
+        // 2021 days parse once up front too
+        let results = bench(crate::find(2021, 1).unwrap(), "1\n2\n3", 1).unwrap();
+        let stages: Vec<Stage> = results.iter().map(|s| s.stage).collect();
+        assert_eq!(stages, vec![Stage::Parse, Stage::Part1, Stage::Part2]);
+    }
+
+    #[test]
+    fn test_stats() {
+        let samples = [30, 10, 50, 20, 40].map(Duration::from_nanos).to_vec();
+        let stats = Stats::new(2022, 3, Stage::Part2, samples);
+        assert_eq!((stats.min_ns, stats.median_ns, stats.max_ns), (10, 30, 50));
+    }
+
+    #[test]
+    fn test_json_round_trip() {
+        let results = vec![stats(1, Stage::Parse, 1000), stats(1, Stage::Part1, 5000)];
+        let json = to_json(&results);
+        assert!(json.contains("\"stage\": \"part1\""));
+        assert_eq!(from_json(&json).unwrap(), results);
+        assert!(from_json("[{\"year\": 2022}]").is_err());
+    }
+
+    #[test]
+    fn test_format_table() {
+        let results = vec![
+            stats(16, Stage::Part1, 2_000_000),
+            stats(16, Stage::Part2, 1_000),
+        ];
+        let previous = vec![stats(16, Stage::Part1, 4_000_000)];
+
+        let table = format_table(&results, None);
+        assert_eq!(
+            table.lines().next(),
+            Some("year  day  stage  min    median  max")
+        );
+
+        let table = format_table(&results, Some(&previous));
+        let lines: Vec<&str> = table.lines().collect();
+        assert!(lines[0].ends_with("previous  change"));
+        assert!(lines[1].ends_with("4.0ms     -50.0%"));
+        assert!(lines[2].ends_with("2µs"));
+    }
+
+    #[test]
+    fn test_format_change() {
+        assert_eq!(format_change(100, 125), "+25.0%");
+        assert_eq!(format_change(100, 60), "-40.0%");
+        assert_eq!(format_change(0, 60), "");
+    }
+}
@@ -0,0 +1,543 @@
+// Checks on an input's overall shape, run before it's handed to a solver. The
+// parsers stop at the first thing they don't understand, and some solvers
+// assume things no parser looks at (a grid being rectangular, every name that's
+// referred to being defined). These look at the whole input and report every
+// problem they find. Every other day is checked by parsing it, which is all
+// there is to check for most of them.
+use std::collections::HashMap;
+
+use aoc_common::parse::{ParseError, Scanner};
+
+use crate::{Puzzle, Solver};
+
+type Shape = fn(&str, &mut Problems);
+
+// The days with a check of their own
+static SHAPES: &[(u16, u8, Shape)] = &[
+    (2021, 4, bingo),
+    (2021, 9, digit_grid),
+    (2021, 11, digit_grid),
+    (2021, 12, caves),
+    (2021, 15, digit_grid),
+    (2022, 8, digit_grid),
+    (2022, 12, heightmap),
+    (2022, 16, valves),
+    (2022, 18, cubes),
+    (2022, 21, monkeys),
+    (2022, 22, monkey_map),
+    (2022, 23, elves),
+];
+
+pub struct Check {
+    pub year: u16,
+    pub day: u8,
+    shape: Option<Shape>,
+    solver: &'static dyn Solver,
+}
+
+/// The check for a solved day.
+pub fn find(year: u16, day: u8) -> Option<Check> {
+    crate::find(year, day).map(Check::new)
+}
+
+/// The check for every solved day, in the same order as `crate::puzzles`.
+pub fn all() -> impl Iterator<Item = Check> {
+    crate::puzzles().map(Check::new)
+}
+
+impl Check {
+    fn new(puzzle: &Puzzle) -> Check {
+        let shape = SHAPES
+            .iter()
+            .find(|&&(year, day, _)| (year, day) == (puzzle.year, puzzle.day))
+            .map(|&(_, _, shape)| shape);
+        Check {
+            year: puzzle.year,
+            day: puzzle.day,
+            shape,
+            solver: puzzle.solver,
+        }
+    }
+
+    /// Every problem with `input`, in the order they come up. Trailing
+    /// whitespace is ignored; leading whitespace isn't, as it's part of the
+    /// first row of some maps. An input that passes its day's own check
+    /// still has to parse.
+    pub fn check(&self, input: &str) -> Vec<ParseError> {
+        let mut problems = Problems(Vec::new());
+        if let Some(shape) = self.shape {
+            shape(input.trim_end(), &mut problems);
+        }
+        if problems.0.is_empty() {
+            if let Err(problem) = self.solver.parses(input) {
+                problems.add(problem);
+            }
+        }
+        problems.0
+    }
+}
+
+struct Problems(Vec<ParseError>);
+
+impl Problems {
+    fn add(&mut self, problem: ParseError) {
+        self.0.push(problem);
+    }
+
+    // Runs `check` over each line, which has to use up all of it. A line
+    // stops being checked at its first problem, but `check` can report others
+    // along the way. `first_line` is where `text` starts in the whole input.
+    fn lines<'a>(
+        &mut self,
+        text: &'a str,
+        first_line: usize,
+        mut check: impl FnMut(&mut Scanner<'a>, &mut Problems) -> Result<(), ParseError>,
+    ) {
+        for (i, line) in text.split('\n').enumerate() {
+            let mut line = Scanner::new(first_line + i, line.strip_suffix('\r').unwrap_or(line));
+            if let Err(problem) = check(&mut line, self).and_then(|()| line.end()) {
+                self.add(problem);
+            }
+        }
+    }
+
+    // Every row has to be as wide as the first, and made up of cells that
+    // pass `valid`.
+    fn grid(&mut self, text: &str, first_line: usize, expected: &str, valid: fn(char) -> bool) {
+        let rows: Vec<&str> = text.split('\n').collect();
+        let width = rows[0].chars().count();
+        for (i, row) in rows.iter().enumerate() {
+            for (j, cell) in row.chars().enumerate() {
+                if !valid(cell) {
+                    self.add(ParseError::new(
+                        first_line + i,
+                        j + 1,
+                        expected,
+                        format!("`{}`", cell),
+                    ));
+                }
+            }
+            let row_width = row.chars().count();
+            if row_width != width {
+                self.add(ParseError::new(
+                    first_line + i,
+                    row_width.min(width) + 1,
+                    format!("a row {} wide, like the first", width),
+                    format!("a row {} wide", row_width),
+                ));
+            }
+        }
+    }
+
+    fn missing(&mut self, text: &str, expected: &str) {
+        let end = text.split('\n').count() + 1;
+        self.add(ParseError::new(end, 1, expected, "end of input"));
+    }
+}
+
+// Where each name was defined, and where each one was referred to, so that
+// duplicates and dangling references can be reported together at the end.
+#[derive(Default)]
+struct Names<'a> {
+    defined: HashMap<&'a str, (usize, usize)>,
+    used: Vec<(usize, usize, &'a str)>,
+}
+
+impl<'a> Names<'a> {
+    // Reads a name being defined.
+    fn define(
+        &mut self,
+        line: &mut Scanner<'a>,
+        problems: &mut Problems,
+    ) -> Result<&'a str, ParseError> {
+        let (line_number, column) = (line.line(), line.column());
+        let name = line.word()?;
+        if let Some((first, _)) = self.defined.insert(name, (line_number, column)) {
+            problems.add(ParseError::new(
+                line_number,
+                column,
+                "a name not used before",
+                format!("`{}`, already defined on line {}", name, first),
+            ));
+        }
+        Ok(name)
+    }
+
+    // Reads a name being referred to.
+    fn refer(&mut self, line: &mut Scanner<'a>) -> Result<&'a str, ParseError> {
+        let (line_number, column) = (line.line(), line.column());
+        let name = line.word()?;
+        self.used.push((line_number, column, name));
+        Ok(name)
+    }
+
+    fn check_references(&self, what: &str, problems: &mut Problems) {
+        for &(line, column, name) in &self.used {
+            if !self.defined.contains_key(name) {
+                problems.add(ParseError::new(
+                    line,
+                    column,
+                    format!("a known {}", what),
+                    format!("`{}`", name),
+                ));
+            }
+        }
+    }
+}
+
+fn digit_grid(input: &str, problems: &mut Problems) {
+    problems.grid(input, 1, "a digit", |cell| cell.is_ascii_digit());
+}
+
+// Drawn numbers, then boards of five rows of five, each after a blank line
+fn bingo(input: &str, problems: &mut Problems) {
+    let mut sections = input.split("\n\n");
+    let draws = sections.next().unwrap();
+    problems.lines(draws, 1, |line, _| {
+        line.list(",", |n| n.number::<u8>()).map(|_| ())
+    });
+
+    let mut first_line = draws.split('\n').count() + 2;
+    for board in sections {
+        problems.lines(board, first_line, |line, _| {
+            line.take_while(|c| c == ' ');
+            let row = line.list(" ", |n| {
+                n.take_while(|c| c == ' ');
+                n.number::<u8>()
+            })?;
+            if row.len() != 5 {
+                return Err(ParseError::new(
+                    line.line(),
+                    1,
+                    "five numbers",
+                    row.len().to_string(),
+                ));
+            }
+            Ok(())
+        });
+        let rows = board.split('\n').count();
+        if rows != 5 {
+            problems.add(ParseError::new(
+                first_line,
+                1,
+                "a board of five rows",
+                format!("{} rows", rows),
+            ));
+        }
+        first_line += rows + 1;
+    }
+}
+
+fn caves(input: &str, problems: &mut Problems) {
+    let mut caves = Vec::new();
+    problems.lines(input, 1, |line, _| {
+        caves.push(line.word()?);
+        line.expect("-")?;
+        caves.push(line.word()?);
+        Ok(())
+    });
+    for cave in ["start", "end"] {
+        if !caves.contains(&cave) {
+            problems.missing(input, &format!("a path to or from `{}`", cave));
+        }
+    }
+}
+
+// Exactly one start and one end
+fn heightmap(input: &str, problems: &mut Problems) {
+    problems.grid(
+        input,
+        1,
+        "an elevation from `a` to `z`, `S` or `E`",
+        |cell| cell.is_ascii_lowercase() || cell == 'S' || cell == 'E',
+    );
+    for (marker, what) in [('S', "start"), ('E', "end")] {
+        let found = input.matches(marker).count();
+        if found != 1 {
+            problems.add(ParseError::new(
+                1,
+                1,
+                format!("one {} (`{}`)", what, marker),
+                found.to_string(),
+            ));
+        }
+    }
+}
+
+fn valves(input: &str, problems: &mut Problems) {
+    let mut names = Names::default();
+    problems.lines(input, 1, |line, problems| {
+        line.expect("Valve ")?;
+        names.define(line, problems)?;
+        line.expect(" has flow rate=")?;
+        line.number::<u32>()?;
+        line.expect("; ")?;
+        let wording = line.clone();
+        let plural = line.eat("tunnels lead to valves ");
+        if !plural {
+            line.expect("tunnel leads to valve ")?;
+        }
+        let connections = line.list(", ", |tunnel| names.refer(tunnel))?;
+        if plural != (connections.len() > 1) {
+            let expected = match connections.len() {
+                1 => "`tunnel leads to valve`",
+                _ => "`tunnels lead to valves`",
+            };
+            problems.add(wording.error(expected));
+        }
+        Ok(())
+    });
+    names.check_references("valve", problems);
+    if !names.defined.contains_key("AA") {
+        problems.missing(input, "a valve `AA`");
+    }
+}
+
+fn cubes(input: &str, problems: &mut Problems) {
+    let mut seen = HashMap::new();
+    problems.lines(input, 1, |line, _| {
+        let cube = line.list(",", |n| n.number::<i32>())?;
+        if cube.len() != 3 {
+            return Err(line.error("a third coordinate"));
+        }
+        if let Some(first) = seen.insert(cube, line.line()) {
+            return Err(ParseError::new(
+                line.line(),
+                1,
+                "a cube not listed before",
+                format!("a repeat of line {}", first),
+            ));
+        }
+        Ok(())
+    });
+}
+
+// Every monkey is waited on by at most one other, which part two relies on
+// to work backwards from `root` to `humn`
+fn monkeys(input: &str, problems: &mut Problems) {
+    let mut names = Names::default();
+    let mut waited_on = HashMap::new();
+    problems.lines(input, 1, |line, problems| {
+        let monkey = names.define(line, problems)?;
+        line.expect(": ")?;
+        if line
+            .remaining()
+            .starts_with(|c: char| c == '-' || c.is_ascii_digit())
+        {
+            line.number::<i64>()?;
+            return Ok(());
+        }
+        for operand in 0..2 {
+            if operand == 1 {
+                line.expect(" ")?;
+                let op = line.clone();
+                if !["+", "-", "*", "/"].contains(&line.take_while(|c| c != ' ')) {
+                    return Err(op.error("one of `+`, `-`, `*` or `/`"));
+                }
+                line.expect(" ")?;
+            }
+            let position = line.clone();
+            let waiting_for = names.refer(line)?;
+            if let Some(other) = waited_on.insert(waiting_for, monkey) {
+                problems.add(ParseError::new(
+                    position.line(),
+                    position.column(),
+                    "a monkey no one else is waiting on",
+                    format!("`{}`, already waited on by `{}`", waiting_for, other),
+                ));
+            }
+        }
+        Ok(())
+    });
+    names.check_references("monkey", problems);
+    for monkey in ["root", "humn"] {
+        if !names.defined.contains_key(monkey) {
+            problems.missing(input, &format!("a monkey `{}`", monkey));
+        }
+    }
+}
+
+// The map, then the path: distances with turns in between, ending on a
+// distance
+fn monkey_map(input: &str, problems: &mut Problems) {
+    let Some((map, path)) = input.split_once("\n\n") else {
+        problems.missing(input, "a blank line, then the path");
+        return;
+    };
+    for (i, row) in map.split('\n').enumerate() {
+        for (j, cell) in row.chars().enumerate() {
+            if !matches!(cell, ' ' | '.' | '#') {
+                problems.add(ParseError::new(
+                    i + 1,
+                    j + 1,
+                    "`.`, `#` or a space",
+                    format!("`{}`", cell),
+                ));
+            }
+        }
+    }
+    if !map.split('\n').next().unwrap().contains('.') {
+        problems.add(ParseError::new(1, 1, "an open tile to start on", "none"));
+    }
+    problems.lines(path, map.split('\n').count() + 2, |line, _| {
+        line.number::<usize>()?;
+        while line.eat("R") || line.eat("L") {
+            line.number::<usize>()?;
+        }
+        Ok(())
+    });
+}
+
+fn elves(input: &str, problems: &mut Problems) {
+    problems.grid(input, 1, "`#` or `.`", |cell| cell == '#' || cell == '.');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problems(year: u16, day: u8, input: &str) -> Vec<String> {
+        find(year, day)
+            .unwrap()
+            .check(input)
+            .iter()
+            .map(ParseError::to_string)
+            .collect()
+    }
+
+    #[test]
+    fn test_registered() {
+        for &(year, day, _) in SHAPES {
+            assert!(find(year, day).unwrap().shape.is_some());
+        }
+        assert_eq!(all().count(), crate::puzzles().count());
+        assert!(find(2022, 1).unwrap().shape.is_none());
+        assert!(find(2020, 1).is_none());
+    }
+
+    // Days without a check of their own are still parsed
+    #[test]
+    fn test_parsed() {
+        assert!(problems(2022, 1, "1000\n2000\n\n3000\n").is_empty());
+        assert_eq!(
+            problems(2022, 1, "1000\n2x00"),
+            ["line 2, column 2: expected end of line, found `x00`"]
+        );
+        // Including days with one, once it finds nothing
+        assert_eq!(
+            problems(2021, 12, "start-A\nA-end\nA-b-c"),
+            ["line 3, column 4: expected end of line, found `-c`"]
+        );
+    }
+
+    #[test]
+    fn test_grids() {
+        assert!(problems(2021, 9, "2199\n3987\n").is_empty());
+        assert_eq!(
+            problems(2021, 9, "2199\n39x7\n398"),
+            [
+                "line 2, column 3: expected a digit, found `x`",
+                "line 3, column 4: expected a row 4 wide, like the first, found a row 3 wide",
+            ]
+        );
+        assert!(problems(2022, 12, "Sabqponm\nabcryxxl\naccszExk").is_empty());
+        assert_eq!(
+            problems(2022, 12, "Sab\nabS"),
+            [
+                "line 1, column 1: expected one start (`S`), found 2",
+                "line 1, column 1: expected one end (`E`), found 0",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_valves() {
+        let input = "Valve AA has flow rate=0; tunnels lead to valves DD, BB
+Valve BB has flow rate=13; tunnel leads to valve AA
+Valve DD has flow rate=20; tunnels lead to valves AA";
+        assert_eq!(
+            problems(2022, 16, input),
+            ["line 3, column 28: expected `tunnel leads to valve`, found `tunnels`"]
+        );
+
+        // Everything wrong is reported, not just the first thing
+        let input = "Valve BB has flow rate=13; tunnel leads to valve CC
+Valve BB has flow rate=x; tunnel leads to valve BB";
+        assert_eq!(
+            problems(2022, 16, input),
+            [
+                "line 2, column 7: expected a name not used before, found `BB`, already defined on line 1",
+                "line 2, column 24: expected a number, found `x;`",
+                "line 1, column 50: expected a known valve, found `CC`",
+                "line 3, column 1: expected a valve `AA`, found end of input",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_monkeys() {
+        let input = "root: pppw + sjmn\npppw: humn * humn\nsjmn: 4\nhumn: 5";
+        assert_eq!(
+            problems(2022, 21, input),
+            ["line 2, column 14: expected a monkey no one else is waiting on, found `humn`, already waited on by `pppw`"]
+        );
+        assert_eq!(
+            problems(2022, 21, "root: aaaa % humn"),
+            [
+                "line 1, column 12: expected one of `+`, `-`, `*` or `/`, found `%`",
+                "line 1, column 7: expected a known monkey, found `aaaa`",
+                "line 2, column 1: expected a monkey `humn`, found end of input",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_monkey_map() {
+        assert!(problems(2022, 22, "  ..#\n  .#.\n\n10R5L2\n").is_empty());
+        assert_eq!(
+            problems(2022, 22, "  ##x\n\n10R5L"),
+            [
+                "line 1, column 5: expected `.`, `#` or a space, found `x`",
+                "line 1, column 1: expected an open tile to start on, found none",
+                "line 3, column 6: expected a number, found end of line",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_other_days() {
+        assert!(problems(2022, 18, "1,1,1\n2,1,1").is_empty());
+        assert_eq!(
+            problems(2022, 18, "1,1,1\n1,1\n1,1,1"),
+            [
+                "line 2, column 4: expected a third coordinate, found end of line",
+                "line 3, column 1: expected a cube not listed before, found a repeat of line 1",
+            ]
+        );
+        assert_eq!(
+            problems(2021, 12, "start-A\nA-b"),
+            ["line 3, column 1: expected a path to or from `end`, found end of input"]
+        );
+        assert_eq!(
+            problems(2021, 4, "7,4,9\n\n1 2 3 4 5\n 6  7  8  9 10\n1 2 3 4"),
+            [
+                "line 5, column 1: expected five numbers, found 4",
+                "line 3, column 1: expected a board of five rows, found 3 rows",
+            ]
+        );
+    }
+
+    // Real inputs are all fine
+    #[test]
+    fn test_real_inputs() {
+        for check in all().filter(|c| c.year == 2021) {
+            let path = format!(
+                "{}/../aoc2021/inputs/{}.txt",
+                env!("CARGO_MANIFEST_DIR"),
+                check.day
+            );
+            let input = std::fs::read_to_string(path).unwrap();
+            assert_eq!(check.check(&input), [], "2021 day {}", check.day);
+        }
+    }
+}
@@ -0,0 +1,192 @@
+// Talks to the Advent of Code site. Inputs and answers are tied to an
+// account, so requests need the `session` cookie from a logged-in browser.
+// Inputs never change, so each one is downloaded once and then served from a
+// cache directory.
+use std::env;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+pub const BASE_URL: &str = "https://adventofcode.com";
+const USER_AGENT: &str = "github.com/ChandlerSwift/aoc";
+
+#[derive(Debug)]
+pub enum ClientError {
+    NoSession,
+    Status(u16),
+    Network(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for ClientError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ClientError::NoSession => write!(
+                f,
+                "no session token; set AOC_SESSION or save it to ~/.config/aoc/session"
+            ),
+            ClientError::Status(status) => write!(f, "server responded with {}", status),
+            ClientError::Network(e) => write!(f, "request failed: {}", e),
+            ClientError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<io::Error> for ClientError {
+    fn from(e: io::Error) -> Self {
+        ClientError::Io(e)
+    }
+}
+
+pub struct Client {
+    pub base_url: String,
+    pub cache_dir: PathBuf,
+    pub session: Option<String>,
+}
+
+impl Client {
+    /// A client for the real site, caching under `~/.cache/aoc` and reading
+    /// the session token from `AOC_SESSION` or `~/.config/aoc/session`.
+    pub fn from_env() -> Client {
+        Client {
+            base_url: BASE_URL.to_string(),
+            cache_dir: user_dir("XDG_CACHE_HOME", ".cache").join("aoc"),
+            session: session_from_env(),
+        }
+    }
+
+    pub fn cache_path(&self, year: u16, day: u8) -> PathBuf {
+        self.cache_dir
+            .join(year.to_string())
+            .join(format!("{}.txt", day))
+    }
+
+    /// Returns the input for the given day, downloading it only if it isn't
+    /// already cached.
+    pub fn input(&self, year: u16, day: u8) -> Result<String, ClientError> {
+        let path = self.cache_path(year, day);
+        if path.exists() {
+            return Ok(fs::read_to_string(path)?);
+        }
+
+        let request = self.request("GET", &format!("/{}/day/{}/input", year, day))?;
+        let input = read_response(request.call())?;
+        write_atomically(&path, &input)?;
+        Ok(input)
+    }
+
+    // An authenticated request for a path on the site.
+    pub(crate) fn request(&self, method: &str, path: &str) -> Result<ureq::Request, ClientError> {
+        let session = self.session.as_ref().ok_or(ClientError::NoSession)?;
+        Ok(ureq::request(method, &format!("{}{}", self.base_url, path))
+            .set("Cookie", &format!("session={}", session))
+            .set("User-Agent", USER_AGENT))
+    }
+}
+
+pub(crate) fn read_response(
+    response: Result<ureq::Response, ureq::Error>,
+) -> Result<String, ClientError> {
+    match response {
+        Ok(response) => Ok(response.into_string()?),
+        Err(ureq::Error::Status(status, _)) => Err(ClientError::Status(status)),
+        Err(e) => Err(ClientError::Network(e.to_string())),
+    }
+}
+
+// `$<var>`, falling back to `~/<fallback>`.
+pub(crate) fn user_dir(var: &str, fallback: &str) -> PathBuf {
+    match env::var_os(var) {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => PathBuf::from(env::var_os("HOME").unwrap_or_default()).join(fallback),
+    }
+}
+
+fn session_from_env() -> Option<String> {
+    let session = match env::var("AOC_SESSION") {
+        Ok(session) => session,
+        Err(_) => {
+            let path = user_dir("XDG_CONFIG_HOME", ".config").join("aoc/session");
+            fs::read_to_string(path).ok()?
+        }
+    };
+    let session = session.trim();
+    if session.is_empty() {
+        None
+    } else {
+        Some(session.to_string())
+    }
+}
+
+// Write to a temporary file first so an interrupted download can't leave a
+// truncated input behind that would then be treated as cached.
+pub(crate) fn write_atomically(path: &Path, contents: &str) -> io::Result<()> {
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let tmp = path.with_extension("tmp");
+    fs::write(&tmp, contents)?;
+    fs::rename(tmp, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::{temp_dir, TestServer};
+
+    fn client(server: &TestServer, name: &str) -> Client {
+        Client {
+            base_url: server.url.clone(),
+            cache_dir: temp_dir(name),
+            session: Some(String::from("abc123")),
+        }
+    }
+
+    #[test]
+    fn test_download_and_cache() {
+        let server = TestServer::start(|_| (200, String::from("1\n2\n3\n")));
+        let client = client(&server, "download-and-cache");
+
+        assert_eq!(client.input(2022, 1).unwrap(), "1\n2\n3\n");
+        assert_eq!(client.input(2022, 1).unwrap(), "1\n2\n3\n");
+        assert_eq!(
+            fs::read_to_string(client.cache_path(2022, 1)).unwrap(),
+            "1\n2\n3\n"
+        );
+
+        let requests = server.requests();
+        assert_eq!(requests.len(), 1);
+        assert_eq!(requests[0].method, "GET");
+        assert_eq!(requests[0].path, "/2022/day/1/input");
+        assert_eq!(requests[0].header("Cookie"), Some("session=abc123"));
+    }
+
+    #[test]
+    fn test_errors_are_not_cached() {
+        let server = TestServer::start(|_| (400, String::from("Puzzle inputs differ by user.")));
+        let client = client(&server, "errors-not-cached");
+
+        assert!(matches!(
+            client.input(2022, 2),
+            Err(ClientError::Status(400))
+        ));
+        assert!(!client.cache_path(2022, 2).exists());
+        assert!(client.input(2022, 2).is_err());
+        assert_eq!(server.requests().len(), 2);
+    }
+
+    #[test]
+    fn test_no_session() {
+        let server = TestServer::start(|_| (200, String::new()));
+        let mut client = client(&server, "no-session");
+        client.session = None;
+
+        assert!(matches!(client.input(2022, 3), Err(ClientError::NoSession)));
+        assert!(server.requests().is_empty());
+
+        // A cached input doesn't need a session at all
+        write_atomically(&client.cache_path(2022, 3), "cached").unwrap();
+        assert_eq!(client.input(2022, 3).unwrap(), "cached");
+    }
+}
@@ -0,0 +1,169 @@
+// Fixtures are the worked examples from the puzzle pages, kept as one file
+// per part under `<dir>/<year>/day<day>-part<part>.txt`. The first line is
+// the expected answer and everything after it is the example input:
+//
+//     95437
+//     $ cd /
+//     ...
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::find;
+use crate::ledger::{escape, unescape};
+use crate::page::Example;
+
+#[derive(Debug, PartialEq)]
+pub struct Fixture {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub answer: String,
+    pub input: String,
+}
+
+impl Fixture {
+    pub fn new(year: u16, day: u8, example: &Example) -> Fixture {
+        Fixture {
+            year,
+            day,
+            part: example.part,
+            answer: example.answer.clone(),
+            input: example.input.clone(),
+        }
+    }
+
+    pub fn path(dir: &Path, year: u16, day: u8, part: u8) -> PathBuf {
+        dir.join(year.to_string())
+            .join(format!("day{}-part{}.txt", day, part))
+    }
+
+    pub fn save(&self, dir: &Path) -> io::Result<PathBuf> {
+        let path = Fixture::path(dir, self.year, self.day, self.part);
+        fs::create_dir_all(path.parent().unwrap())?;
+        fs::write(&path, format!("{}\n{}", escape(&self.answer), self.input))?;
+        Ok(path)
+    }
+
+    /// Runs the registered solver on the example. A part that hasn't been
+    /// solved yet passes.
+    pub fn check(&self) -> Result<(), String> {
+        let puzzle = find(self.year, self.day).ok_or("no solution registered")?;
+        match puzzle.solver.solve(&self.input, self.part) {
+            Ok(None) => Ok(()),
+            Ok(Some(answer)) if answer.to_string() == self.answer => Ok(()),
+            Ok(Some(answer)) => Err(format!("got {}, expected {}", answer, self.answer)),
+            Err(e) => Err(format!("invalid input at {}", e)),
+        }
+    }
+}
+
+/// Every fixture under `dir`, in order. A missing directory just means there
+/// aren't any yet.
+pub fn load(dir: &Path) -> Result<Vec<Fixture>, String> {
+    let read_dir = |dir: &Path| -> Result<Vec<PathBuf>, String> {
+        match fs::read_dir(dir) {
+            Ok(entries) => entries
+                .map(|entry| entry.map(|e| e.path()))
+                .collect::<io::Result<_>>()
+                .map_err(|e| format!("could not read {}: {}", dir.display(), e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Vec::new()),
+            Err(e) => Err(format!("could not read {}: {}", dir.display(), e)),
+        }
+    };
+
+    let mut fixtures = Vec::new();
+    for year_dir in read_dir(dir)? {
+        for path in read_dir(&year_dir)? {
+            let name = |path: &Path| Some(path.file_name()?.to_str()?.to_string());
+            let key = name(&year_dir)
+                .zip(name(&path))
+                .and_then(|(year, file)| parse_key(&year, &file));
+            let (year, day, part) =
+                key.ok_or_else(|| format!("unexpected fixture {}", path.display()))?;
+            let contents = fs::read_to_string(&path)
+                .map_err(|e| format!("could not read {}: {}", path.display(), e))?;
+            let (answer, input) = contents.split_once('\n').unwrap_or((&contents, ""));
+            fixtures.push(Fixture {
+                year,
+                day,
+                part,
+                answer: unescape(answer),
+                input: input.to_string(),
+            });
+        }
+    }
+    fixtures.sort_by_key(|f| (f.year, f.day, f.part));
+    Ok(fixtures)
+}
+
+// The year, day and part from `2022` and `day7-part1.txt`.
+fn parse_key(year: &str, file: &str) -> Option<(u16, u8, u8)> {
+    let (day, part) = file
+        .strip_prefix("day")?
+        .strip_suffix(".txt")?
+        .split_once("-part")?;
+    Some((year.parse().ok()?, day.parse().ok()?, part.parse().ok()?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::temp_dir;
+
+    #[test]
+    fn test_round_trip() {
+        let dir = temp_dir("fixtures");
+        let example = Example {
+            part: 2,
+            input: String::from("  leading space\n\nand a blank line\n"),
+            answer: String::from("CMZ"),
+        };
+        let fixture = Fixture::new(2022, 5, &example);
+        let path = fixture.save(&dir).unwrap();
+        assert_eq!(path, dir.join("2022").join("day5-part2.txt"));
+        assert_eq!(load(&dir).unwrap(), vec![fixture]);
+
+        fs::write(dir.join("2022").join("notes.txt"), "").unwrap();
+        assert!(load(&dir).is_err());
+        assert_eq!(load(&dir.join("missing")).unwrap(), vec![]);
+    }
+
+    #[test]
+    fn test_check() {
+        let fixture = |answer: &str, input: &str| Fixture {
+            year: 2022,
+            day: 6,
+            part: 1,
+            answer: answer.to_string(),
+            input: input.to_string(),
+        };
+        assert_eq!(
+            fixture("7", "mjqjpqmgbljsphdztnvjfqwrfgjlzrjh").check(),
+            Ok(())
+        );
+        assert_eq!(
+            fixture("8", "mjqjpqmgbljsphdztnvjfqwrfgjlzrjh").check(),
+            Err(String::from("got 7, expected 8"))
+        );
+        let mut unregistered = fixture("1", "");
+        unregistered.year = 2015;
+        assert!(unregistered.check().is_err());
+    }
+
+    // Every saved example should still give the answer from the puzzle
+    #[test]
+    fn test_fixtures() {
+        let fixtures = load(&Path::new(env!("CARGO_MANIFEST_DIR")).join("fixtures")).unwrap();
+        assert!(!fixtures.is_empty());
+        let failures: Vec<String> = fixtures
+            .iter()
+            .filter_map(|f| {
+                f.check()
+                    .err()
+                    .map(|e| format!("{} day {} part {}: {}", f.year, f.day, f.part, e))
+            })
+            .collect();
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+}
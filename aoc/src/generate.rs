@@ -0,0 +1,244 @@
+// Made-up inputs in the same format as the real ones, as large as needed,
+// for profiling and fuzzing the solvers. Each generator takes a size (what it
+// counts depends on the puzzle) and is reproducible from its seed.
+use aoc_common::rng::Rng;
+
+pub struct Generator {
+    pub year: u16,
+    pub day: u8,
+    /// What the size is a count of.
+    pub size: &'static str,
+    pub default_size: usize,
+    pub generate: fn(&mut Rng, usize) -> String,
+}
+
+pub static GENERATORS: &[Generator] = &[
+    Generator {
+        year: 2021,
+        day: 4,
+        size: "boards",
+        default_size: 100,
+        generate: bingo,
+    },
+    Generator {
+        year: 2022,
+        day: 9,
+        size: "moves",
+        default_size: 2000,
+        generate: rope_moves,
+    },
+    Generator {
+        year: 2022,
+        day: 15,
+        size: "sensors",
+        default_size: 30,
+        generate: sensors,
+    },
+    Generator {
+        year: 2022,
+        day: 16,
+        size: "valves",
+        default_size: 60,
+        generate: valves,
+    },
+    Generator {
+        year: 2022,
+        day: 19,
+        size: "blueprints",
+        default_size: 30,
+        generate: blueprints,
+    },
+];
+
+pub fn find(year: u16, day: u8) -> Option<&'static Generator> {
+    GENERATORS.iter().find(|g| g.year == year && g.day == day)
+}
+
+impl Generator {
+    pub fn generate(&self, seed: u64, size: usize) -> String {
+        (self.generate)(&mut Rng::new(seed), size)
+    }
+}
+
+// Every number from 0 to 99 is drawn, so every board wins eventually
+fn bingo(rng: &mut Rng, boards: usize) -> String {
+    let mut numbers: Vec<u8> = (0..100).collect();
+    rng.shuffle(&mut numbers);
+    let draws: Vec<String> = numbers.iter().map(|n| n.to_string()).collect();
+
+    let mut input = draws.join(",");
+    for _ in 0..boards {
+        rng.shuffle(&mut numbers);
+        input.push('\n');
+        for row in numbers[..25].chunks(5) {
+            let row: Vec<String> = row.iter().map(|n| format!("{:>2}", n)).collect();
+            input.push_str(&format!("\n{}", row.join(" ")));
+        }
+    }
+    input
+}
+
+fn rope_moves(rng: &mut Rng, moves: usize) -> String {
+    let lines: Vec<String> = (0..moves)
+        .map(|_| {
+            format!(
+                "{} {}",
+                rng.choose(&["U", "D", "L", "R"]),
+                rng.between(1, 20)
+            )
+        })
+        .collect();
+    lines.join("\n")
+}
+
+// Spread over the area part two searches. There's no guarantee of part two's
+// single uncovered spot.
+fn sensors(rng: &mut Rng, sensors: usize) -> String {
+    let lines: Vec<String> = (0..sensors)
+        .map(|_| {
+            let (x, y) = (rng.between(0, 4_000_000), rng.between(0, 4_000_000));
+            let (dx, dy) = (
+                rng.between(-500_000, 500_000),
+                rng.between(-500_000, 500_000),
+            );
+            format!(
+                "Sensor at x={}, y={}: closest beacon is at x={}, y={}",
+                x,
+                y,
+                x + dx,
+                y + dy
+            )
+        })
+        .collect();
+    lines.join("\n")
+}
+
+// A connected network starting from AA, with about a third of the valves
+// worth opening. Names are two letters, so there are at most 676 valves.
+fn valves(rng: &mut Rng, valves: usize) -> String {
+    let valves = valves.clamp(2, 26 * 26);
+    let name = |valve: usize| {
+        let letter = |n: usize| (b'A' + n as u8) as char;
+        format!("{}{}", letter(valve / 26), letter(valve % 26))
+    };
+    let mut names: Vec<usize> = (1..26 * 26).collect();
+    rng.shuffle(&mut names);
+    names.insert(0, 0);
+
+    // Hang each valve off an earlier one, then add some shortcuts
+    let mut tunnels = vec![Vec::new(); valves];
+    for valve in 1..valves {
+        let earlier = rng.below(valve);
+        tunnels[valve].push(earlier);
+        tunnels[earlier].push(valve);
+    }
+    for _ in 0..valves / 2 {
+        let (a, b) = (rng.below(valves), rng.below(valves));
+        if a != b && !tunnels[a].contains(&b) {
+            tunnels[a].push(b);
+            tunnels[b].push(a);
+        }
+    }
+
+    let lines: Vec<String> = tunnels
+        .iter()
+        .enumerate()
+        .map(|(valve, connections)| {
+            let flow_rate = if valve > 0 && rng.chance(33) {
+                rng.between(1, 25)
+            } else {
+                0
+            };
+            let connections: Vec<String> = connections.iter().map(|&c| name(names[c])).collect();
+            let tunnels = match connections.len() {
+                1 => "tunnel leads to valve",
+                _ => "tunnels lead to valves",
+            };
+            format!(
+                "Valve {} has flow rate={}; {} {}",
+                name(names[valve]),
+                flow_rate,
+                tunnels,
+                connections.join(", ")
+            )
+        })
+        .collect();
+    lines.join("\n")
+}
+
+// Costs in the same ranges as the real blueprints
+fn blueprints(rng: &mut Rng, blueprints: usize) -> String {
+    let lines: Vec<String> = (1..=blueprints)
+        .map(|id| {
+            format!(
+                "Blueprint {}: Each ore robot costs {} ore. Each clay robot costs {} ore. \
+                 Each obsidian robot costs {} ore and {} clay. \
+                 Each geode robot costs {} ore and {} obsidian.",
+                id,
+                rng.between(2, 4),
+                rng.between(2, 4),
+                rng.between(2, 4),
+                rng.between(5, 20),
+                rng.between(2, 4),
+                rng.between(5, 20)
+            )
+        })
+        .collect();
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Answer;
+
+    #[test]
+    fn test_reproducible() {
+        for generator in GENERATORS {
+            assert_eq!(generator.generate(7, 10), generator.generate(7, 10));
+            assert_ne!(generator.generate(7, 10), generator.generate(8, 10));
+            assert!(crate::find(generator.year, generator.day).is_some());
+        }
+        assert!(find(2022, 1).is_none());
+    }
+
+    // Every generated input should be accepted by the day's parser
+    #[test]
+    fn test_parses() {
+        for seed in 0..20 {
+            let input = bingo(&mut Rng::new(seed), 5);
+            assert_eq!(aoc2021::day4a::parse(&input).unwrap().1.len(), 5);
+
+            let input = rope_moves(&mut Rng::new(seed), 50);
+            assert_eq!(input.lines().count(), 50);
+
+            let input = sensors(&mut Rng::new(seed), 20);
+            assert_eq!(aoc2022::day15::parse(&input).unwrap().len(), 20);
+
+            let input = valves(&mut Rng::new(seed), 40);
+            assert_eq!(aoc2022::day16::parse(&input).unwrap().len(), 40);
+
+            let input = blueprints(&mut Rng::new(seed), 10);
+            assert_eq!(aoc2022::day19::parse(&input).unwrap().len(), 10);
+        }
+        assert_eq!(valves(&mut Rng::new(0), 1000).lines().count(), 676);
+    }
+
+    #[test]
+    fn test_solves() {
+        // Big enough to be interesting, small enough for a debug build
+        let solver = crate::find(2022, 9).unwrap().solver;
+        let input = rope_moves(&mut Rng::new(1), 500);
+        assert!(matches!(
+            solver.solve(&input, 2),
+            Ok(Some(Answer::Number(_)))
+        ));
+
+        let solver = crate::find(2022, 16).unwrap().solver;
+        let input = valves(&mut Rng::new(1), 12);
+        assert!(matches!(
+            solver.solve(&input, 1),
+            Ok(Some(Answer::Number(_)))
+        ));
+    }
+}
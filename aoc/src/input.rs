@@ -0,0 +1,172 @@
+// Where a puzzle's input comes from. Whatever the source, the text is handed
+// to the solver untouched; each solver trims it the way its puzzle needs.
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+use crate::client::Client;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Source {
+    Stdin,
+    Path(PathBuf),
+    /// The input downloaded from the site, or the cached copy if it's been
+    /// downloaded before.
+    Download {
+        year: u16,
+        day: u8,
+    },
+    Inline(String),
+}
+
+impl Source {
+    /// The source an `--input` argument names for the given puzzle: `-` for
+    /// stdin, `download` for the downloaded input, `inline:` followed by the
+    /// input itself, or anything else for a path (so `./download` for a file
+    /// that happens to be called that).
+    pub fn from_arg(arg: &str, year: u16, day: u8) -> Source {
+        if let Some(input) = arg.strip_prefix("inline:") {
+            return Source::Inline(input.to_string());
+        }
+        match arg {
+            "-" => Source::Stdin,
+            "download" => Source::Download { year, day },
+            path => Source::Path(PathBuf::from(path)),
+        }
+    }
+
+    /// The input saved next to the day's solution if there is one, or else
+    /// the downloaded one.
+    pub fn default_for(year: u16, day: u8) -> Source {
+        match default_path(year, day) {
+            Some(path) if path.exists() => Source::Path(path),
+            _ => Source::Download { year, day },
+        }
+    }
+
+    pub fn read(&self, client: &Client) -> Result<String, String> {
+        match self {
+            Source::Stdin => {
+                let mut input = String::new();
+                io::stdin()
+                    .read_to_string(&mut input)
+                    .map_err(|e| format!("could not read stdin: {}", e))?;
+                Ok(input)
+            }
+            Source::Path(path) => fs::read_to_string(path)
+                .map_err(|e| format!("could not read {}: {}", path.display(), e)),
+            Source::Download { year, day } => client
+                .input(*year, *day)
+                .map_err(|e| format!("could not download input: {}", e)),
+            Source::Inline(input) => Ok(input.clone()),
+        }
+    }
+
+    /// Like `read`, but never goes to the network: a download that isn't
+    /// cached yet is treated as missing.
+    pub fn read_local(&self, client: &Client) -> Option<String> {
+        match self {
+            Source::Download { year, day } => {
+                fs::read_to_string(client.cache_path(*year, *day)).ok()
+            }
+            source => source.read(client).ok(),
+        }
+    }
+}
+
+fn default_path(year: u16, day: u8) -> Option<PathBuf> {
+    let path = match year {
+        2021 => format!("aoc2021/inputs/{}.txt", day),
+        2022 => format!("2022/day{}/input.txt", day),
+        _ => return None,
+    };
+    Some(PathBuf::from(path))
+}
+
+/// How much whitespace around an input a solver can do without. Nearly all
+/// of them can lose it all, but a few puzzles (like 2022 day 22's map) have
+/// leading whitespace that means something.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Trim {
+    Both,
+    End,
+}
+
+impl Trim {
+    pub fn apply(self, input: &str) -> &str {
+        match self {
+            Trim::Both => input.trim(),
+            Trim::End => input.trim_end(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::{temp_dir, TestServer};
+
+    #[test]
+    fn test_source() {
+        assert_eq!(Source::from_arg("-", 2022, 1), Source::Stdin);
+        assert_eq!(
+            Source::from_arg("input.txt", 2022, 1),
+            Source::Path(PathBuf::from("input.txt"))
+        );
+        assert_eq!(
+            Source::from_arg("download", 2022, 3),
+            Source::Download { year: 2022, day: 3 }
+        );
+        assert_eq!(
+            Source::from_arg("./download", 2022, 3),
+            Source::Path(PathBuf::from("./download"))
+        );
+        assert_eq!(
+            Source::from_arg("inline:1,2:3", 2022, 1),
+            Source::Inline(String::from("1,2:3"))
+        );
+        assert_eq!(
+            Source::default_for(2020, 1),
+            Source::Download { year: 2020, day: 1 }
+        );
+
+        let client = Client {
+            base_url: String::new(),
+            cache_dir: std::env::temp_dir().join("aoc-no-cache"),
+            session: None,
+        };
+        let inline = Source::Inline(String::from("1\n2\n"));
+        assert_eq!(inline.read(&client), Ok(String::from("1\n2\n")));
+        assert_eq!(inline.read_local(&client), Some(String::from("1\n2\n")));
+        assert_eq!(
+            Source::Download { year: 2020, day: 1 }.read_local(&client),
+            None
+        );
+        assert!(Source::from_arg("/no/such/input", 2022, 1)
+            .read(&client)
+            .is_err());
+    }
+
+    #[test]
+    fn test_download() {
+        let server = TestServer::start(|_| (200, String::from("1\n2\n3\n")));
+        let client = Client {
+            base_url: server.url.clone(),
+            cache_dir: temp_dir("input-download"),
+            session: Some(String::from("abc123")),
+        };
+        let source = Source::from_arg("download", 2022, 4);
+        // Nothing's been downloaded yet, so there's nothing local to read
+        assert_eq!(source.read_local(&client), None);
+        assert_eq!(source.read(&client), Ok(String::from("1\n2\n3\n")));
+        assert_eq!(source.read_local(&client), Some(String::from("1\n2\n3\n")));
+        assert_eq!(server.requests().len(), 1);
+        assert_eq!(server.requests()[0].path, "/2022/day/4/input");
+    }
+
+    #[test]
+    fn test_trim() {
+        assert_eq!(Trim::Both.apply("\n  ..#\n#..\n\n"), "..#\n#..");
+        assert_eq!(Trim::End.apply("  ..#\n#..\n\n"), "  ..#\n#..");
+    }
+}
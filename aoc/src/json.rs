@@ -0,0 +1,123 @@
+// Results as JSON, one object to a line, for scripts and dashboards to read
+// rather than scraping the tables.
+use std::time::Duration;
+
+use serde::Serialize;
+
+use crate::verify::{Check, Status};
+use crate::Answer;
+
+#[derive(Debug, PartialEq, Serialize)]
+pub struct Record {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    /// "solved" from `run`, and the verification status (e.g. "ok" or
+    /// "mismatch") from `verify` and `all`.
+    pub status: &'static str,
+    pub answer: Option<String>,
+    /// Whether the answer is a "number" or "text".
+    #[serde(rename = "type")]
+    pub kind: Option<&'static str>,
+    pub time_ns: Option<u64>,
+    /// The expected answer for a mismatch, or what went wrong for a panic or
+    /// an invalid input.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub detail: Option<String>,
+}
+
+impl Record {
+    pub fn new(
+        year: u16,
+        day: u8,
+        part: u8,
+        status: &'static str,
+        answer: Option<&Answer>,
+        elapsed: Option<Duration>,
+    ) -> Record {
+        Record {
+            year,
+            day,
+            part,
+            status,
+            answer: answer.map(Answer::to_string),
+            kind: answer.map(Answer::kind),
+            time_ns: elapsed.map(|elapsed| elapsed.as_nanos() as u64),
+            detail: None,
+        }
+    }
+
+    pub fn from_check(check: &Check) -> Record {
+        let (status, detail) = match &check.status {
+            Status::Ok => ("ok", None),
+            Status::Mismatch(expected) => ("mismatch", Some(expected)),
+            Status::Unverified => ("unverified", None),
+            Status::Panicked(message) => ("panicked", Some(message)),
+            Status::TimedOut => ("timed_out", None),
+            Status::Invalid(message) => ("invalid", Some(message)),
+            Status::Skipped => ("skipped", None),
+            Status::Unsolved => ("unsolved", None),
+            Status::NoInput => ("no_input", None),
+        };
+        Record {
+            detail: detail.cloned(),
+            ..Record::new(
+                check.year,
+                check.day,
+                check.part,
+                status,
+                check.answer.as_ref(),
+                check.elapsed,
+            )
+        }
+    }
+
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_records() {
+        let record = Record::new(
+            2022,
+            10,
+            2,
+            "solved",
+            Some(&Answer::Text(String::from("EHPZPJGL"))),
+            Some(Duration::from_micros(1500)),
+        );
+        assert_eq!(
+            record.to_json(),
+            r#"{"year":2022,"day":10,"part":2,"status":"solved","answer":"EHPZPJGL","type":"text","time_ns":1500000}"#
+        );
+
+        let check = Check {
+            year: 2021,
+            day: 1,
+            part: 1,
+            status: Status::Mismatch(String::from("1791")),
+            answer: Some(Answer::Number(1790)),
+            elapsed: Some(Duration::from_nanos(20)),
+        };
+        assert_eq!(
+            Record::from_check(&check).to_json(),
+            r#"{"year":2021,"day":1,"part":1,"status":"mismatch","answer":"1790","type":"number","time_ns":20,"detail":"1791"}"#
+        );
+
+        let check = Check {
+            status: Status::NoInput,
+            answer: None,
+            elapsed: None,
+            ..check
+        };
+        assert_eq!(
+            Record::from_check(&check).to_json(),
+            r#"{"year":2021,"day":1,"part":1,"status":"no_input","answer":null,"type":null,"time_ns":null}"#
+        );
+    }
+}
@@ -0,0 +1,188 @@
+// The ledger records the accepted answer for each solved part, one per line:
+//
+//     2022 1 1 69883
+//
+// Answers drawn on a screen are recorded as the letters they spell. Any that
+// can't be read, and so span several lines, have their newlines written as
+// `\n`. Lines starting with `#` are comments, and are kept with the answer
+// below them when the ledger is saved (the ones above the first answer stay
+// at the top, and any after the last stay at the bottom). Blank lines aren't
+// kept.
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Ledger {
+    answers: BTreeMap<(u16, u8, u8), String>,
+    header: Vec<String>,
+    // The comments just above each answer
+    notes: BTreeMap<(u16, u8, u8), Vec<String>>,
+    footer: Vec<String>,
+}
+
+impl Ledger {
+    pub fn parse(contents: &str) -> Result<Ledger, String> {
+        let mut ledger = Ledger::default();
+        let mut comments = Vec::new();
+        for (i, line) in contents.lines().enumerate() {
+            if line.starts_with('#') {
+                comments.push(line.to_string());
+                continue;
+            }
+            if line.trim().is_empty() {
+                continue;
+            }
+            let fields: Vec<&str> = line.splitn(4, ' ').collect();
+            let parsed = match fields[..] {
+                [year, day, part, answer] => year
+                    .parse()
+                    .ok()
+                    .zip(day.parse().ok())
+                    .zip(part.parse().ok())
+                    .map(|((year, day), part)| (year, day, part, unescape(answer))),
+                _ => None,
+            };
+            let (year, day, part, answer) = parsed.ok_or_else(|| {
+                format!("line {}: expected `<year> <day> <part> <answer>`", i + 1)
+            })?;
+            ledger.insert(year, day, part, &answer);
+            if ledger.answers.len() == 1 && ledger.header.is_empty() {
+                ledger.header = std::mem::take(&mut comments);
+            } else if !comments.is_empty() {
+                ledger
+                    .notes
+                    .insert((year, day, part), std::mem::take(&mut comments));
+            }
+        }
+        if ledger.answers.is_empty() {
+            ledger.header = comments;
+        } else {
+            ledger.footer = comments;
+        }
+        Ok(ledger)
+    }
+
+    /// An empty ledger if there's no file yet.
+    pub fn load(path: &Path) -> Result<Ledger, String> {
+        match fs::read_to_string(path) {
+            Ok(contents) => Ledger::parse(&contents),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Ledger::default()),
+            Err(e) => Err(format!("could not read {}: {}", path.display(), e)),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn get(&self, year: u16, day: u8, part: u8) -> Option<&str> {
+        self.answers.get(&(year, day, part)).map(|a| a.as_str())
+    }
+
+    pub fn insert(&mut self, year: u16, day: u8, part: u8, answer: &str) {
+        self.answers.insert((year, day, part), answer.to_string());
+    }
+}
+
+impl fmt::Display for Ledger {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for comment in &self.header {
+            writeln!(f, "{}", comment)?;
+        }
+        for (key @ (year, day, part), answer) in &self.answers {
+            for comment in self.notes.get(key).into_iter().flatten() {
+                writeln!(f, "{}", comment)?;
+            }
+            writeln!(f, "{} {} {} {}", year, day, part, escape(answer))?;
+        }
+        for comment in &self.footer {
+            writeln!(f, "{}", comment)?;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) fn escape(answer: &str) -> String {
+    answer.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+pub(crate) fn unescape(answer: &str) -> String {
+    let mut unescaped = String::new();
+    let mut chars = answer.chars();
+    while let Some(ch) = chars.next() {
+        match (ch, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                unescaped.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                unescaped.push('\\');
+                chars.next();
+            }
+            _ => unescaped.push(ch),
+        }
+    }
+    unescaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let mut ledger = Ledger::default();
+        ledger.insert(2022, 10, 2, "#..#\n#\\.#");
+        ledger.insert(2022, 1, 1, "69883");
+        ledger.insert(2021, 25, 1, "1");
+
+        let written = ledger.to_string();
+        assert_eq!(
+            written,
+            "2021 25 1 1\n2022 1 1 69883\n2022 10 2 #..#\\n#\\\\.#\n"
+        );
+        let read = Ledger::parse(&written).unwrap();
+        assert_eq!(read, ledger);
+        assert_eq!(read.get(2022, 10, 2), Some("#..#\n#\\.#"));
+        assert_eq!(read.get(2022, 10, 1), None);
+    }
+
+    #[test]
+    fn test_parse() {
+        let ledger = Ledger::parse("# year day part answer\n\n2022 5 1 CMZ\n").unwrap();
+        assert_eq!(ledger.get(2022, 5, 1), Some("CMZ"));
+        assert!(Ledger::parse("2022 5 1").is_err());
+        assert!(Ledger::parse("2022 five 1 CMZ").is_err());
+    }
+
+    #[test]
+    fn test_comments() {
+        let mut ledger = Ledger::parse(
+            "# year day part answer\n\
+             2022 1 1 69883\n\
+             \n\
+             # read off the screen\n\
+             2022 10 2 PLEFULPB\n\
+             # the end\n",
+        )
+        .unwrap();
+        ledger.insert(2021, 25, 1, "1");
+        ledger.insert(2022, 5, 1, "CMZ");
+        assert_eq!(
+            ledger.to_string(),
+            "# year day part answer\n\
+             2021 25 1 1\n\
+             2022 1 1 69883\n\
+             2022 5 1 CMZ\n\
+             # read off the screen\n\
+             2022 10 2 PLEFULPB\n\
+             # the end\n"
+        );
+
+        let ledger = Ledger::parse("# nothing yet\n").unwrap();
+        assert_eq!(ledger.to_string(), "# nothing yet\n");
+    }
+}
@@ -0,0 +1,246 @@
+use std::fmt;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use aoc_common::cancel::{Cancel, Search};
+use aoc_common::parse::ParseError;
+use aoc_common::screen;
+
+use crate::input::Trim;
+
+pub mod all;
+pub mod bench;
+pub mod check;
+pub mod client;
+pub mod fixture;
+pub mod generate;
+pub mod input;
+pub mod json;
+pub mod ledger;
+pub mod page;
+pub mod report;
+pub mod scaffold;
+pub mod submit;
+#[cfg(test)]
+mod test_server;
+pub mod verify;
+pub mod year2021;
+pub mod year2022;
+
+/// A puzzle answer. Most are numbers, but a few (e.g. the ones that spell
+/// something out on a screen) are text.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Answer {
+    Number(i128),
+    Text(String),
+}
+
+impl Answer {
+    /// "number" or "text", for anything that needs to tell them apart
+    /// without the `Answer` itself.
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Answer::Number(_) => "number",
+            Answer::Text(_) => "text",
+        }
+    }
+
+    /// What's drawn on a screen of `#` and `.` cells: the letters it spells,
+    /// or the drawing itself if they can't be made out.
+    pub fn screen(drawn: String) -> Answer {
+        Answer::Text(screen::read(&drawn).unwrap_or(drawn))
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+macro_rules! impl_answer_from_number {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(n: $t) -> Self {
+                Answer::Number(n as i128)
+            }
+        })*
+    };
+}
+
+impl_answer_from_number!(i32, i64, isize, u32, u64, usize);
+
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::Text(s)
+    }
+}
+
+/// A single day's puzzle. The raw input is parsed once, and both parts are
+/// answered from the parsed form. A part that hasn't been solved returns
+/// `None`.
+pub trait Solution {
+    type Input<'a>;
+
+    /// What's trimmed off the input before it's parsed.
+    const TRIM: Trim = Trim::Both;
+
+    fn parse(input: &str) -> Result<Self::Input<'_>, ParseError>;
+    fn part1(input: &Self::Input<'_>) -> Option<Answer>;
+    fn part2(input: &Self::Input<'_>) -> Option<Answer>;
+
+    /// Like `part1`, but gives up once `cancel` says to. Only the slow
+    /// searches bother doing anything with it.
+    fn part1_until(input: &Self::Input<'_>, _cancel: &Cancel) -> Option<Search<Answer>> {
+        Self::part1(input).map(Search::Finished)
+    }
+
+    fn part2_until(input: &Self::Input<'_>, _cancel: &Cancel) -> Option<Search<Answer>> {
+        Self::part2(input).map(Search::Finished)
+    }
+}
+
+/// How long each stage of a single run took. Unsolved parts are `None`.
+#[derive(Clone, Copy, Debug)]
+pub struct Timing {
+    pub parse: Duration,
+    pub part1: Option<Duration>,
+    pub part2: Option<Duration>,
+}
+
+/// Object-safe view of a `Solution`, so that days with different input
+/// types can live side by side in the registry.
+pub trait Solver: Sync {
+    fn solve(&self, input: &str, part: u8) -> Result<Option<Answer>, ParseError>;
+
+    /// Like `solve`, but stops early once `cancel` says to, with the best
+    /// answer found by then.
+    fn solve_until(
+        &self,
+        input: &str,
+        part: u8,
+        _cancel: &Cancel,
+    ) -> Result<Option<Search<Answer>>, ParseError> {
+        Ok(self.solve(input, part)?.map(Search::Finished))
+    }
+
+    /// Parses the input once and answers both parts, timing each stage.
+    fn time(&self, input: &str) -> Result<Timing, ParseError>;
+
+    /// Just parses the input, to see whether it can be.
+    fn parses(&self, input: &str) -> Result<(), ParseError>;
+}
+
+impl<S: Solution + Sync> Solver for S {
+    fn solve(&self, input: &str, part: u8) -> Result<Option<Answer>, ParseError> {
+        let parsed = S::parse(S::TRIM.apply(input))?;
+        Ok(match part {
+            1 => S::part1(&parsed),
+            2 => S::part2(&parsed),
+            _ => None,
+        })
+    }
+
+    fn solve_until(
+        &self,
+        input: &str,
+        part: u8,
+        cancel: &Cancel,
+    ) -> Result<Option<Search<Answer>>, ParseError> {
+        let parsed = S::parse(S::TRIM.apply(input))?;
+        Ok(match part {
+            1 => S::part1_until(&parsed, cancel),
+            2 => S::part2_until(&parsed, cancel),
+            _ => None,
+        })
+    }
+
+    fn time(&self, input: &str) -> Result<Timing, ParseError> {
+        let start = Instant::now();
+        let parsed = black_box(S::parse(S::TRIM.apply(input))?);
+        let parse = start.elapsed();
+        Ok(Timing {
+            parse,
+            part1: time_part(|| S::part1(&parsed)),
+            part2: time_part(|| S::part2(&parsed)),
+        })
+    }
+
+    fn parses(&self, input: &str) -> Result<(), ParseError> {
+        S::parse(S::TRIM.apply(input)).map(|_| ())
+    }
+}
+
+fn time_part(part: impl FnOnce() -> Option<Answer>) -> Option<Duration> {
+    let start = Instant::now();
+    black_box(part()).map(|_| start.elapsed())
+}
+
+pub struct Puzzle {
+    pub year: u16,
+    pub day: u8,
+    pub solver: &'static dyn Solver,
+}
+
+impl Puzzle {
+    pub const fn new(year: u16, day: u8, solver: &'static dyn Solver) -> Self {
+        Puzzle { year, day, solver }
+    }
+}
+
+/// Every solved day, in chronological order.
+pub fn puzzles() -> impl Iterator<Item = &'static Puzzle> {
+    year2021::PUZZLES.iter().chain(year2022::PUZZLES.iter())
+}
+
+pub fn find(year: u16, day: u8) -> Option<&'static Puzzle> {
+    puzzles().find(|p| p.year == year && p.day == day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_registry() {
+        assert_eq!(puzzles().filter(|p| p.year == 2021).count(), 18);
+        assert_eq!(puzzles().filter(|p| p.year == 2022).count(), 23);
+        for puzzle in puzzles() {
+            assert!(std::ptr::eq(find(puzzle.year, puzzle.day).unwrap(), puzzle));
+        }
+        assert!(find(2020, 1).is_none());
+    }
+
+    #[test]
+    fn test_solve() {
+        let input = "199\n200\n208\n210\n200\n207\n240\n269\n260\n263";
+        let solver = find(2021, 1).unwrap().solver;
+        assert_eq!(solver.solve(input, 1), Ok(Some(Answer::Number(7))));
+        assert_eq!(solver.solve(input, 2), Ok(Some(Answer::Number(5))));
+        assert_eq!(solver.solve(input, 3), Ok(None));
+
+        let solver = find(2022, 6).unwrap().solver;
+        assert_eq!(
+            solver.solve("mjqjpqmgbljsphdztnvjfqwrfgjlzrjh", 2),
+            Ok(Some(Answer::Number(19)))
+        );
+
+        // Only the end is trimmed, so the first row of crates stays lined
+        // up with its stack
+        let solver = find(2022, 5).unwrap().solver;
+        let crates = "    [D]    \n[N] [C]    \n[Z] [M] [P]\n 1   2   3 \n\n\
+                      move 1 from 2 to 1\nmove 3 from 1 to 3\n\
+                      move 2 from 2 to 1\nmove 1 from 1 to 2\n";
+        assert_eq!(solver.solve(crates, 1), Ok(Some(Answer::Text("CMZ".into()))));
+        assert_eq!(solver.solve(crates, 2), Ok(Some(Answer::Text("MCD".into()))));
+
+        let solver = find(2022, 15).unwrap().solver;
+        assert_eq!(
+            solver.solve("Sensor at x=2, y=18: closest beacon", 1),
+            Err(ParseError::new(1, 20, "`: closest beacon is at x=`", "`: closest beacon`"))
+        );
+    }
+}
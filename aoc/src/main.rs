@@ -0,0 +1,691 @@
+use std::env;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use aoc::all;
+use aoc::bench;
+use aoc::check;
+use aoc::client::Client;
+use aoc::fixture::Fixture;
+use aoc::generate;
+use aoc::input::Source;
+use aoc::json::Record;
+use aoc::ledger::Ledger;
+use aoc::page;
+use aoc::scaffold;
+use aoc::submit::Outcome;
+use aoc::verify::{self, Check};
+use aoc_common::cancel::{Cancel, Search};
+use aoc_common::checkpoint;
+use aoc_common::log::{self, Level};
+use aoc_common::parse::ParseError;
+
+const USAGE: &str = "usage:
+    aoc run --year <YEAR> --day <DAY> [--part <1|2>] [--input <PATH|-|download|inline:TEXT>] [--timeout <SECONDS>] [--checkpoint <DIR>] [--format <text|json>]
+    aoc download --year <YEAR> --day <DAY>
+    aoc submit --year <YEAR> --day <DAY> --part <1|2> [--answer <ANSWER>] [--input <PATH|-|download|inline:TEXT>]
+    aoc verify [--year <YEAR>] [--day <DAY>] [--format <text|json>]
+    aoc check [--year <YEAR>] [--day <DAY>] [--input <PATH|-|download|inline:TEXT>]
+    aoc all [--year <YEAR>] [--day <DAY>] [--workers <N>] [--timeout <SECONDS>] [--format <text|json>]
+    aoc bench [--year <YEAR>] [--day <DAY>] [--runs <N>] [--output <PATH>] [--compare <PATH>]
+    aoc new --year <YEAR> --day <DAY> [--page <PATH>]
+    aoc examples --year <YEAR> --day <DAY> --page <PATH>
+    aoc generate --year <YEAR> --day <DAY> [--seed <N>] [--size <N>]
+every command also takes --log <quiet|info|debug|trace> (default quiet)";
+
+// Accepted answers, checked by `verify` and added to by `submit`
+const LEDGER_PATH: &str = "answers.txt";
+
+// Examples from the puzzle pages, checked by the aoc crate's tests
+const FIXTURES_PATH: &str = "aoc/fixtures";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Format {
+    Text,
+    /// A JSON object for each result, one to a line.
+    Json,
+}
+
+struct Args {
+    year: Option<u16>,
+    day: Option<u8>,
+    part: Option<u8>,
+    input: Option<String>,
+    answer: Option<String>,
+    runs: Option<usize>,
+    output: Option<String>,
+    compare: Option<String>,
+    page: Option<String>,
+    workers: Option<usize>,
+    timeout: Option<u64>,
+    log: Option<Level>,
+    seed: Option<u64>,
+    size: Option<usize>,
+    checkpoint: Option<String>,
+    format: Option<Format>,
+}
+
+// Only the flags in `allowed` (and --log, which goes with anything) are
+// accepted.
+fn parse_args(args: &[String], allowed: &[&str]) -> Result<Args, String> {
+    let mut year = None;
+    let mut day = None;
+    let mut part = None;
+    let mut input = None;
+    let mut answer = None;
+    let mut runs = None;
+    let mut output = None;
+    let mut compare = None;
+    let mut page = None;
+    let mut workers = None;
+    let mut timeout = None;
+    let mut log = None;
+    let mut seed = None;
+    let mut size = None;
+    let mut checkpoint = None;
+    let mut format = None;
+
+    let mut args = args.iter();
+    while let Some(flag) = args.next() {
+        if !allowed.contains(&flag.as_str()) && flag != "--log" {
+            return Err(format!("unknown argument: {}", flag));
+        }
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing value for {}", flag))?;
+        match flag.as_str() {
+            "--year" => {
+                year = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("invalid year: {}", value))?,
+                )
+            }
+            "--day" => {
+                day = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("invalid day: {}", value))?,
+                )
+            }
+            "--part" => match value.as_str() {
+                "1" => part = Some(1),
+                "2" => part = Some(2),
+                _ => return Err(format!("invalid part: {}", value)),
+            },
+            "--input" => input = Some(value.clone()),
+            "--answer" => answer = Some(value.clone()),
+            "--runs" => {
+                runs = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("invalid number of runs: {}", value))?,
+                )
+            }
+            "--output" => output = Some(value.clone()),
+            "--compare" => compare = Some(value.clone()),
+            "--page" => page = Some(value.clone()),
+            "--workers" => {
+                workers = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("invalid number of workers: {}", value))?,
+                )
+            }
+            "--log" => log = Some(value.parse()?),
+            "--checkpoint" => checkpoint = Some(value.clone()),
+            "--format" => match value.as_str() {
+                "text" => format = Some(Format::Text),
+                "json" => format = Some(Format::Json),
+                _ => return Err(format!("invalid format: {}", value)),
+            },
+            "--seed" => {
+                seed = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("invalid seed: {}", value))?,
+                )
+            }
+            "--size" => {
+                size = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("invalid size: {}", value))?,
+                )
+            }
+            "--timeout" => {
+                timeout = Some(
+                    value
+                        .parse()
+                        .map_err(|_| format!("invalid timeout: {}", value))?,
+                )
+            }
+            _ => return Err(format!("unknown argument: {}", flag)),
+        }
+    }
+
+    Ok(Args {
+        year,
+        day,
+        part,
+        input,
+        answer,
+        runs,
+        output,
+        compare,
+        page,
+        workers,
+        timeout,
+        log,
+        seed,
+        size,
+        checkpoint,
+        format,
+    })
+}
+
+impl Args {
+    fn json(&self) -> bool {
+        self.format == Some(Format::Json)
+    }
+
+    fn puzzle(&self) -> Result<(u16, u8), String> {
+        Ok((
+            self.year.ok_or("missing --year")?,
+            self.day.ok_or("missing --day")?,
+        ))
+    }
+
+    // Where the puzzle's input comes from. An explicit --input wins (`-` for
+    // stdin, `download` to download it even if there's a copy next to the
+    // solution, or `inline:<text>` for the input itself), then an input saved
+    // next to the solution, and failing that it's downloaded (or read from
+    // the download cache).
+    fn source(&self) -> Result<Source, String> {
+        let (year, day) = self.puzzle()?;
+        Ok(match &self.input {
+            Some(input) => Source::from_arg(input, year, day),
+            None => Source::default_for(year, day),
+        })
+    }
+
+    // Every registered puzzle, narrowed down by --year and --day if given.
+    fn puzzles(&self) -> impl Iterator<Item = &'static aoc::Puzzle> + '_ {
+        aoc::puzzles()
+            .filter(|p| self.year.is_none_or(|year| p.year == year))
+            .filter(|p| self.day.is_none_or(|day| p.day == day))
+    }
+}
+
+fn load_input(args: &Args) -> Result<String, String> {
+    args.source()?.read(&Client::from_env())
+}
+
+// Like `load_input`, but never goes to the network.
+fn local_input(client: &Client, year: u16, day: u8) -> Option<String> {
+    Source::default_for(year, day).read_local(client)
+}
+
+fn invalid_input(year: u16, day: u8, error: ParseError) -> String {
+    format!("{} day {}: invalid input at {}", year, day, error)
+}
+
+// Everything wrong with an input, if it has a check and fails it.
+fn check_problems(year: u16, day: u8, input: &str) -> Option<String> {
+    let problems = check::find(year, day)?.check(input);
+    if problems.is_empty() {
+        return None;
+    }
+    let mut report = format!("{} day {}: invalid input", year, day);
+    for problem in problems {
+        report.push_str(&format!("\n    {}", problem));
+    }
+    Some(report)
+}
+
+fn run(args: Args) -> Result<(), String> {
+    let (year, day) = args.puzzle()?;
+    let puzzle =
+        aoc::find(year, day).ok_or_else(|| format!("no solution for {} day {}", year, day))?;
+    let input = load_input(&args)?;
+    if let Some(problems) = check_problems(year, day, &input) {
+        return Err(problems);
+    }
+
+    let parts = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    // The slow searches save their progress to --checkpoint every so often,
+    // and pick up from there if they were stopped before
+    checkpoint::set_dir(args.checkpoint.as_ref().map(PathBuf::from));
+    if let Some(dir) = &args.checkpoint {
+        fs::create_dir_all(dir).map_err(|e| format!("could not create {}: {}", dir, e))?;
+    }
+
+    // With --timeout, the slow searches stop when time's up and report the
+    // best answer they'd found
+    let cancel = match args.timeout {
+        Some(seconds) => Cancel::after(Duration::from_secs(seconds)),
+        None => Cancel::never(),
+    };
+    for part in parts {
+        let start = Instant::now();
+        let answer = puzzle
+            .solver
+            .solve_until(&input, part, &cancel)
+            .map_err(|e| invalid_input(year, day, e))?;
+        let elapsed = start.elapsed();
+        let record = |status, answer| Record::new(year, day, part, status, answer, Some(elapsed));
+        match answer {
+            Some(Search::Finished(answer)) if args.json() => {
+                println!("{}", record("solved", Some(&answer)).to_json())
+            }
+            Some(Search::Finished(answer)) => println!("{}", answer),
+            Some(Search::Cancelled(best)) => {
+                if args.json() {
+                    println!("{}", record("timed_out", best.as_ref()).to_json());
+                }
+                let best = best.map_or_else(String::new, |b| format!("; best so far: {}", b));
+                return Err(format!(
+                    "{} day {} part {} timed out{}",
+                    year, day, part, best
+                ));
+            }
+            None => {
+                return Err(format!(
+                    "no solution for {} day {} part {}",
+                    year, day, part
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+fn download(args: Args) -> Result<(), String> {
+    let (year, day) = args.puzzle()?;
+    let client = Client::from_env();
+    client
+        .input(year, day)
+        .map_err(|e| format!("could not download input: {}", e))?;
+    println!("{}", client.cache_path(year, day).display());
+    Ok(())
+}
+
+// Submits --answer, or if there isn't one, whatever the solver comes up with.
+fn submit(args: Args) -> Result<(), String> {
+    let (year, day) = args.puzzle()?;
+    let part = args.part.ok_or("submit needs --part")?;
+    let answer = match &args.answer {
+        Some(answer) => answer.clone(),
+        None => {
+            let puzzle = aoc::find(year, day)
+                .ok_or_else(|| format!("no solution for {} day {}", year, day))?;
+            let input = load_input(&args)?;
+            puzzle
+                .solver
+                .solve(&input, part)
+                .map_err(|e| invalid_input(year, day, e))?
+                .ok_or_else(|| format!("no solution for {} day {} part {}", year, day, part))?
+                .to_string()
+        }
+    };
+
+    let client = Client::from_env();
+    let previous = client
+        .previous_outcome(year, day, part, &answer)
+        .map_err(|e| format!("could not read earlier submissions: {}", e))?;
+    if let Some(outcome) = previous {
+        println!(
+            "{}: {} (known from an earlier submission; not sent)",
+            answer, outcome
+        );
+        return Ok(());
+    }
+    let outcome = client
+        .submit(year, day, part, &answer)
+        .map_err(|e| format!("could not submit answer: {}", e))?;
+    println!("{}: {}", answer, outcome);
+
+    if outcome == Outcome::Correct {
+        let path = Path::new(LEDGER_PATH);
+        let mut ledger = Ledger::load(path)?;
+        ledger.insert(year, day, part, &answer);
+        ledger
+            .save(path)
+            .map_err(|e| format!("could not write {}: {}", LEDGER_PATH, e))?;
+    }
+    Ok(())
+}
+
+// Checks the inputs for every day that has checks, without solving anything.
+// With --input, just that one day's input is checked.
+fn check(args: Args) -> Result<(), String> {
+    let client = Client::from_env();
+    let mut failures = 0;
+    for check in check::all() {
+        let (year, day) = (check.year, check.day);
+        if args.year.is_some_and(|y| y != year) || args.day.is_some_and(|d| d != day) {
+            continue;
+        }
+        let input = match &args.input {
+            Some(_) => Some(load_input(&args)?),
+            None => local_input(&client, year, day),
+        };
+        let Some(input) = input else {
+            eprintln!("skipping {} day {}: no input", year, day);
+            continue;
+        };
+        match check_problems(year, day, &input) {
+            Some(problems) => {
+                println!("{}", problems);
+                failures += 1;
+            }
+            None => println!("{} day {}: ok", year, day),
+        }
+    }
+    if failures > 0 {
+        return Err(format!("{} input(s) failed their checks", failures));
+    }
+    Ok(())
+}
+
+fn print_checks(args: &Args, checks: &[Check]) {
+    if args.json() {
+        for check in checks {
+            println!("{}", Record::from_check(check).to_json());
+        }
+    } else {
+        print!("{}", verify::format_table(checks));
+    }
+}
+
+fn verify(args: Args) -> Result<(), String> {
+    let ledger = Ledger::load(Path::new(LEDGER_PATH))?;
+    let client = Client::from_env();
+    let checks = verify::verify(args.puzzles(), &ledger, |year, day| {
+        local_input(&client, year, day)
+    });
+
+    print_checks(&args, &checks);
+    let failures = checks.iter().filter(|c| c.failed()).count();
+    if failures > 0 {
+        return Err(format!("{} part(s) failed verification", failures));
+    }
+    Ok(())
+}
+
+// Like `verify`, but runs everything at once and gives up on any part that
+// takes longer than --timeout seconds (a minute by default).
+fn all(args: Args) -> Result<(), String> {
+    let ledger = Ledger::load(Path::new(LEDGER_PATH))?;
+    let client = Client::from_env();
+    let workers = args
+        .workers
+        .unwrap_or_else(|| thread::available_parallelism().map_or(1, |n| n.get()));
+    let timeout = Duration::from_secs(args.timeout.unwrap_or(60));
+    let (checks, leaked) = all::run_all(
+        args.puzzles(),
+        &ledger,
+        |year, day| local_input(&client, year, day),
+        workers,
+        timeout,
+    );
+
+    print_checks(&args, &checks);
+    if leaked > 0 {
+        eprintln!("{} timed out part(s) were still running", leaked);
+    }
+    let failures = checks.iter().filter(|c| c.failed()).count();
+    if failures > 0 {
+        return Err(format!("{} part(s) failed", failures));
+    }
+    Ok(())
+}
+
+fn bench(args: Args) -> Result<(), String> {
+    let previous = match &args.compare {
+        Some(path) => {
+            let json =
+                fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path, e))?;
+            Some(bench::from_json(&json).map_err(|e| format!("could not parse {}: {}", path, e))?)
+        }
+        None => None,
+    };
+
+    let client = Client::from_env();
+    let mut results = Vec::new();
+    for puzzle in args.puzzles() {
+        let Some(input) = local_input(&client, puzzle.year, puzzle.day) else {
+            eprintln!("skipping {} day {}: no input", puzzle.year, puzzle.day);
+            continue;
+        };
+        match bench::bench(puzzle, &input, args.runs.unwrap_or(10)) {
+            Ok(stats) => results.extend(stats),
+            Err(e) => eprintln!("skipping {}", invalid_input(puzzle.year, puzzle.day, e)),
+        }
+    }
+
+    print!("{}", bench::format_table(&results, previous.as_deref()));
+    if let Some(path) = &args.output {
+        fs::write(path, bench::to_json(&results))
+            .map_err(|e| format!("could not write {}: {}", path, e))?;
+    }
+    Ok(())
+}
+
+fn read_page(path: &str) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path, e))
+}
+
+// Saves the examples on a puzzle page as fixtures.
+fn save_examples(year: u16, day: u8, html: &str) -> Result<(), String> {
+    for example in page::examples(html) {
+        let path = Fixture::new(year, day, &example)
+            .save(Path::new(FIXTURES_PATH))
+            .map_err(|e| format!("could not write fixture: {}", e))?;
+        println!("{}", path.display());
+    }
+    Ok(())
+}
+
+// Sets up a new day, with the example taken from --page (the puzzle's page,
+// saved from the site) if given.
+fn new(args: Args) -> Result<(), String> {
+    let (year, day) = args.puzzle()?;
+    let html = args.page.as_deref().map(read_page).transpose()?;
+    let examples = html.as_deref().map(page::examples).unwrap_or_default();
+    if html.is_some() && examples.is_empty() {
+        return Err(String::from("no examples with answers on the puzzle page"));
+    }
+    for path in scaffold::scaffold(Path::new("."), year, day, &examples)? {
+        println!("{}", path.display());
+    }
+    if let Some(html) = &html {
+        save_examples(year, day, html)?;
+    }
+    Ok(())
+}
+
+fn examples(args: Args) -> Result<(), String> {
+    let (year, day) = args.puzzle()?;
+    let path = args.page.as_deref().ok_or("examples needs --page")?;
+    let html = read_page(path)?;
+    if page::examples(&html).is_empty() {
+        return Err(format!("no examples with answers in {}", path));
+    }
+    save_examples(year, day, &html)
+}
+
+// Prints a made-up input, for profiling or fuzzing a solver.
+fn generate(args: Args) -> Result<(), String> {
+    let (year, day) = args.puzzle()?;
+    let generator = generate::find(year, day)
+        .ok_or_else(|| format!("no input generator for {} day {}", year, day))?;
+    let size = args.size.unwrap_or(generator.default_size);
+    println!("{}", generator.generate(args.seed.unwrap_or(0), size));
+    Ok(())
+}
+
+fn usage_error(message: &str) -> ! {
+    eprintln!("{}\n{}", message, USAGE);
+    process::exit(2);
+}
+
+// A subcommand, run with the flags it was given
+type Command = fn(Args) -> Result<(), String>;
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let (command, rest) = args
+        .split_first()
+        .unwrap_or_else(|| usage_error("expected a command"));
+    let (command, flags): (Command, &[&str]) = match command.as_str() {
+        "run" => (
+            run,
+            &[
+                "--year",
+                "--day",
+                "--part",
+                "--input",
+                "--timeout",
+                "--checkpoint",
+                "--format",
+            ],
+        ),
+        "download" => (download, &["--year", "--day"]),
+        "submit" => (
+            submit,
+            &["--year", "--day", "--part", "--answer", "--input"],
+        ),
+        "verify" => (verify, &["--year", "--day", "--format"]),
+        "check" => (check, &["--year", "--day", "--input"]),
+        "all" => (
+            all,
+            &["--year", "--day", "--workers", "--timeout", "--format"],
+        ),
+        "bench" => (
+            bench,
+            &["--year", "--day", "--runs", "--output", "--compare"],
+        ),
+        "new" => (new, &["--year", "--day", "--page"]),
+        "examples" => (examples, &["--year", "--day", "--page"]),
+        "generate" => (generate, &["--year", "--day", "--seed", "--size"]),
+        _ => usage_error(&format!("unknown command: {}", command)),
+    };
+    let args = parse_args(rest, flags).unwrap_or_else(|e| usage_error(&e));
+    log::set_level(args.log.unwrap_or(Level::Quiet));
+    if let Err(e) = command(args) {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    const ALL_FLAGS: &[&str] = &[
+        "--year",
+        "--day",
+        "--part",
+        "--input",
+        "--answer",
+        "--runs",
+        "--output",
+        "--compare",
+        "--page",
+        "--workers",
+        "--timeout",
+        "--seed",
+        "--size",
+        "--checkpoint",
+        "--format",
+    ];
+
+    #[test]
+    fn test_parse_args() {
+        let parsed = parse_args(
+            &args("--year 2022 --day 16 --part 2 --input foo.txt"),
+            ALL_FLAGS,
+        )
+        .unwrap();
+        assert_eq!(parsed.puzzle(), Ok((2022, 16)));
+        assert_eq!(parsed.part, Some(2));
+        assert_eq!(parsed.input, Some(String::from("foo.txt")));
+        assert_eq!(parsed.answer, None);
+
+        let parsed = parse_args(&args("--day 3 --year 2021"), ALL_FLAGS).unwrap();
+        assert_eq!(parsed.part, None);
+        assert_eq!(parsed.input, None);
+
+        let parsed = parse_args(&args("--year 2022 --day 6 --input download"), ALL_FLAGS).unwrap();
+        assert_eq!(parsed.source(), Ok(Source::Download { year: 2022, day: 6 }));
+        let parsed = parse_args(
+            &args("--year 2022 --day 6 --input inline:mjqjpqmgbljsphd"),
+            ALL_FLAGS,
+        )
+        .unwrap();
+        assert_eq!(
+            parsed.source(),
+            Ok(Source::Inline(String::from("mjqjpqmgbljsphd")))
+        );
+        assert!(parse_args(&args("--input -"), ALL_FLAGS)
+            .unwrap()
+            .source()
+            .is_err());
+
+        let parsed = parse_args(
+            &args("--year 2021 --day 3 --part 1 --answer 1234"),
+            ALL_FLAGS,
+        )
+        .unwrap();
+        assert_eq!(parsed.answer, Some(String::from("1234")));
+
+        assert!(parse_args(&args("--year 2022"), ALL_FLAGS)
+            .unwrap()
+            .puzzle()
+            .is_err());
+        assert!(parse_args(&args("--year 2022 --day 1 --part 3"), ALL_FLAGS).is_err());
+        assert!(parse_args(&args("--year 2022 --day"), ALL_FLAGS).is_err());
+
+        let parsed = parse_args(&args("--runs 5 --compare old.json"), ALL_FLAGS).unwrap();
+        assert_eq!(parsed.runs, Some(5));
+        assert_eq!(parsed.compare, Some(String::from("old.json")));
+        assert!(parse_args(&args("--year 2022 --answer 5"), &["--year", "--day"]).is_err());
+
+        let parsed = parse_args(&args("--year 2022 --day 24 --page 24.html"), ALL_FLAGS).unwrap();
+        assert_eq!(parsed.page, Some(String::from("24.html")));
+
+        let parsed = parse_args(&args("--workers 4 --timeout 30"), ALL_FLAGS).unwrap();
+        assert_eq!(parsed.workers, Some(4));
+        assert_eq!(parsed.timeout, Some(30));
+        assert!(parse_args(&args("--timeout soon"), ALL_FLAGS).is_err());
+
+        let parsed = parse_args(&args("--year 2022 --log debug"), &["--year"]).unwrap();
+        assert_eq!(parsed.log, Some(Level::Debug));
+        assert!(parse_args(&args("--log loud"), ALL_FLAGS).is_err());
+
+        let parsed = parse_args(&args("--seed 7 --size 1000"), ALL_FLAGS).unwrap();
+        assert_eq!(parsed.seed, Some(7));
+        assert_eq!(parsed.size, Some(1000));
+
+        let parsed = parse_args(&args("--checkpoint saved"), ALL_FLAGS).unwrap();
+        assert_eq!(parsed.checkpoint, Some(String::from("saved")));
+
+        let parsed = parse_args(&args("--format json"), ALL_FLAGS).unwrap();
+        assert!(parsed.json());
+        assert!(!parse_args(&args("--format text"), ALL_FLAGS)
+            .unwrap()
+            .json());
+        assert!(parse_args(&args("--format yaml"), ALL_FLAGS).is_err());
+    }
+}
@@ -0,0 +1,127 @@
+// Pulls pieces out of a puzzle page saved from the site. The pages are
+// simple enough that a handful of string searches does the job without a
+// real HTML parser.
+
+/// The contents of every `<pre><code>` block on the page, in order, with
+/// markup stripped and entities decoded.
+pub fn code_blocks(html: &str) -> Vec<String> {
+    let mut blocks = Vec::new();
+    let mut rest = html;
+    while let Some(start) = rest.find("<pre><code>") {
+        rest = &rest[start + "<pre><code>".len()..];
+        let Some(end) = rest.find("</code></pre>") else {
+            break;
+        };
+        blocks.push(text(&rest[..end]));
+        rest = &rest[end..];
+    }
+    blocks
+}
+
+/// An example input and the answer the puzzle gives for it.
+#[derive(Debug, PartialEq)]
+pub struct Example {
+    pub part: u8,
+    pub input: String,
+    pub answer: String,
+}
+
+/// The worked example for each part described on the page. Each part is its
+/// own `<article>`, and its answer is the last emphasized bit of code in it.
+/// Part two only gets its own input if it introduces a new example.
+pub fn examples(html: &str) -> Vec<Example> {
+    let mut articles: Vec<&str> = html.split("<article").skip(1).collect();
+    if articles.is_empty() {
+        articles.push(html);
+    }
+
+    let mut examples = Vec::new();
+    let mut input = None;
+    for (part, article) in (1..=2).zip(articles) {
+        if let Some(block) = code_blocks(article).into_iter().next() {
+            input = Some(block);
+        }
+        let answer = article.rfind("<code><em>").and_then(|start| {
+            let rest = &article[start + "<code><em>".len()..];
+            rest.find("</em></code>").map(|end| text(&rest[..end]))
+        });
+        if let (Some(input), Some(answer)) = (&input, answer) {
+            examples.push(Example {
+                part,
+                input: input.clone(),
+                answer,
+            });
+        }
+    }
+    examples
+}
+
+// Drops any tags (the site highlights parts of examples with `<em>`) and
+// decodes the entities the site uses.
+fn text(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(c),
+            _ => (),
+        }
+    }
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_code_blocks() {
+        let html = "<article><p>For example:</p>\n\
+            <pre><code>1000\n2000\n\n&lt;<em>3000</em>&gt; &amp;\n</code></pre>\n\
+            <p>Then <code>24000</code> is the answer.</p>\n\
+            <pre><code>[1,[2]]\n</code></pre></article>";
+        assert_eq!(
+            code_blocks(html),
+            vec!["1000\n2000\n\n<3000> &\n", "[1,[2]]\n"]
+        );
+        assert!(code_blocks("<p>No example today</p>").is_empty());
+    }
+
+    #[test]
+    fn test_examples() {
+        let html = "<main><article class=\"day-desc\"><h2>--- Day 1 ---</h2>\n\
+            <pre><code>1000\n2000\n</code></pre>\n\
+            <p>The first Elf has <code>1000</code>, so <code><em>2000</em></code>.</p>\n\
+            </article><p>Your puzzle answer was <code>69883</code>.</p>\n\
+            <article class=\"day-desc\"><h2>--- Part Two ---</h2>\n\
+            <p>In total, <code><em>3000</em></code>.</p></article></main>";
+        assert_eq!(
+            examples(html),
+            vec![
+                Example {
+                    part: 1,
+                    input: String::from("1000\n2000\n"),
+                    answer: String::from("2000"),
+                },
+                Example {
+                    part: 2,
+                    input: String::from("1000\n2000\n"),
+                    answer: String::from("3000"),
+                },
+            ]
+        );
+
+        // Before part one is solved, there's no second article
+        let html =
+            "<article><pre><code>a\n</code></pre><p><code><em>&lt;5</em></code></p></article>";
+        assert_eq!(examples(html).len(), 1);
+        assert_eq!(examples(html)[0].answer, "<5");
+        assert_eq!(examples("<article><p>Nothing here</p></article>"), vec![]);
+    }
+}
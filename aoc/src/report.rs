@@ -0,0 +1,60 @@
+// Helpers for the tables printed by `verify` and `bench`.
+use std::time::Duration;
+
+pub fn format_duration(duration: Duration) -> String {
+    let micros = duration.as_micros();
+    if micros == 0 {
+        format!("{}ns", duration.as_nanos())
+    } else if micros < 1000 {
+        format!("{}µs", micros)
+    } else if micros < 1_000_000 {
+        format!("{:.1}ms", micros as f64 / 1000.0)
+    } else {
+        format!("{:.2}s", duration.as_secs_f64())
+    }
+}
+
+/// Lines up `rows` (the first being the header) into columns.
+pub fn table(rows: &[Vec<String>]) -> String {
+    let mut widths = Vec::new();
+    for row in rows {
+        widths.resize(widths.len().max(row.len()), 0);
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let mut table = String::new();
+    for row in rows {
+        let line: Vec<String> = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{:width$}", cell, width = width))
+            .collect();
+        table.push_str(line.join("  ").trim_end());
+        table.push('\n');
+    }
+    table
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_format_duration() {
+        assert_eq!(format_duration(Duration::from_nanos(150)), "150ns");
+        assert_eq!(format_duration(Duration::from_micros(15)), "15µs");
+        assert_eq!(format_duration(Duration::from_micros(1500)), "1.5ms");
+        assert_eq!(format_duration(Duration::from_millis(2346)), "2.35s");
+    }
+
+    #[test]
+    fn test_table() {
+        let rows = vec![
+            vec![String::from("day"), String::from("answer")],
+            vec![String::from("1"), String::from("1184")],
+            vec![String::from("10"), String::new()],
+        ];
+        assert_eq!(table(&rows), "day  answer\n1    1184\n10\n");
+    }
+}
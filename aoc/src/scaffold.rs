@@ -0,0 +1,450 @@
+// Sets up a new day: a module with a parser, both parts and a test for the
+// example, declared in its year's crate and registered with the runner.
+// Everything is laid out the way 2022 is, since 2021 split each part into
+// its own program. A year's first day sets up its crate too.
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::page::Example;
+
+const MODULE: &str = r#"use aoc_common::parse::{self, ParseError};
+
+pub fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+    parse::lines(input, |line| Ok(line.take_while(|_| true)))
+}
+
+pub fn part1(_data: &[&str]) -> Option<{type1}> {
+    None
+}
+
+pub fn part2(_data: &[&str]) -> Option<{type2}> {
+    None
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+{data}
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&parse({input1}).unwrap()), {answer1});
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&parse({input2}).unwrap()), {answer2});
+    }
+}
+"#;
+
+const SOLUTION: &str = r#"pub struct Day{day};
+
+impl Solution for Day{day} {
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+        day{day}::parse(input)
+    }
+
+    fn part1(data: &Vec<&str>) -> Option<Answer> {
+        day{day}::part1(data).map(Answer::from)
+    }
+
+    fn part2(data: &Vec<&str>) -> Option<Answer> {
+        day{day}::part2(data).map(Answer::from)
+    }
+}
+
+"#;
+
+const MANIFEST: &str = r#"[package]
+name = "aoc{year}"
+version = "0.1.0"
+edition = "2021"
+
+[dependencies]
+aoc-common = { path = "../common" }
+"#;
+
+const LIB: &str = "//! The {year} solutions. Each day lives in `dayN/dayN.rs`.\n";
+
+const REGISTRY: &str = "use aoc{year}::*;
+use aoc_common::parse::ParseError;
+
+use crate::{Answer, Puzzle, Solution};
+
+pub static PUZZLES: &[Puzzle] = &[
+];
+";
+
+// `text` as a Rust string literal
+fn literal(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+// The type a part returns, and what its test expects, for an example answer
+fn expected(answer: &str) -> (&'static str, String) {
+    if answer.parse::<usize>().is_ok() {
+        ("usize", format!("Some({})", answer))
+    } else if answer.parse::<i64>().is_ok() {
+        ("i64", format!("Some({})", answer))
+    } else {
+        ("String", format!("Some(String::from({}))", literal(answer)))
+    }
+}
+
+/// The source for a new day's module. Each part's test runs the worked
+/// example from the puzzle page and expects the page's answer; parts without
+/// one expect `None`, which is what the stubs return.
+pub fn module(examples: &[Example]) -> String {
+    let mut inputs: Vec<&str> = Vec::new();
+    let mut data = Vec::new();
+    let mut source = String::from(MODULE);
+    for part in 1..=2 {
+        let example = examples.iter().find(|e| e.part == part);
+        // Part two mostly reuses part one's example
+        let input = match example {
+            Some(example) => example.input.trim_end(),
+            None => inputs.first().copied().unwrap_or(""),
+        };
+        let index = inputs.iter().position(|&i| i == input).unwrap_or_else(|| {
+            inputs.push(input);
+            inputs.len() - 1
+        });
+        let name = if index == 0 {
+            String::from("DATA")
+        } else {
+            format!("DATA{}", index + 1)
+        };
+        if index == data.len() {
+            data.push(format!("    const {}: &str = {};", name, literal(input)));
+        }
+        let (answer_type, answer) = match example {
+            Some(example) => expected(&example.answer),
+            None => ("usize", String::from("None")),
+        };
+        source = source
+            .replace(&format!("{{type{}}}", part), answer_type)
+            .replace(&format!("{{input{}}}", part), &name)
+            .replace(&format!("{{answer{}}}", part), &answer);
+    }
+    if examples.is_empty() {
+        data.insert(0, String::from("    // The example from the puzzle"));
+    }
+    source.replace("{data}", &data.join("\n"))
+}
+
+/// `lib` with the day's module declared at the end.
+pub fn declare(lib: &str, day: u8) -> Result<String, String> {
+    let declaration = format!("pub mod day{};\n", day);
+    if lib.lines().any(|line| line == declaration.trim_end()) {
+        return Err(format!("day{} is already declared", day));
+    }
+    Ok(format!(
+        "{}#[path = \"../day{day}/day{day}.rs\"]\n{}",
+        lib,
+        declaration,
+        day = day
+    ))
+}
+
+/// `registry` (one of the `yearNNNN.rs` files) with a `Solution` for the day
+/// and its entry at the end of `PUZZLES`.
+pub fn register(registry: &str, year: u16, day: u8) -> Result<String, String> {
+    if registry.contains(&format!("pub struct Day{};", day)) {
+        return Err(format!("{} day {} is already registered", year, day));
+    }
+    let missing = || String::from("couldn't find the `PUZZLES` list to register with");
+    let puzzles = registry.find("pub static PUZZLES").ok_or_else(missing)?;
+    let end = registry
+        .rfind("];")
+        .filter(|&end| end > puzzles)
+        .ok_or_else(missing)?;
+    Ok(format!(
+        "{}{}{}    Puzzle::new({}, {}, &Day{}),\n{}",
+        &registry[..puzzles],
+        SOLUTION.replace("{day}", &day.to_string()),
+        &registry[puzzles..end],
+        year,
+        day,
+        day,
+        &registry[end..]
+    ))
+}
+
+// `text` with `line` added after the last line containing `after`
+fn insert_after(text: &str, after: &str, line: &str) -> Option<String> {
+    let start = text.rfind(after)?;
+    let end = start + text[start..].find('\n')? + 1;
+    Some(format!("{}{}\n{}", &text[..end], line, &text[end..]))
+}
+
+/// `workspace` (the root `Cargo.toml`) with the year's crate as a member.
+pub fn add_member(workspace: &str, year: u16) -> Result<String, String> {
+    let missing = || String::from("couldn't find the workspace's members");
+    let start = workspace.find("members = [").ok_or_else(missing)? + "members = [".len();
+    let end = start + workspace[start..].find(']').ok_or_else(missing)?;
+    let member = format!("\"{}\"", year);
+    let mut members: Vec<&str> = workspace[start..end].split(", ").collect();
+    members.push(&member);
+    members.sort();
+    Ok(format!(
+        "{}{}{}",
+        &workspace[..start],
+        members.join(", "),
+        &workspace[end..]
+    ))
+}
+
+/// `manifest` (the runner's `Cargo.toml`) depending on the year's crate, and
+/// `lib` (the runner's `lib.rs`) with the year's registry in `puzzles`.
+pub fn add_year(manifest: &str, lib: &str, year: u16) -> Result<(String, String), String> {
+    let dependency = format!("aoc{} = {{ path = \"../{}\" }}", year, year);
+    let manifest = insert_after(manifest, "aoc20", &dependency)
+        .ok_or("couldn't find the runner's dependencies")?;
+    let missing = || String::from("couldn't find the runner's list of years");
+    let lib =
+        insert_after(lib, "pub mod year", &format!("pub mod year{};", year)).ok_or_else(missing)?;
+    // After the last year's registry, and whatever closes around it
+    let last = lib.rfind("::PUZZLES.iter()").ok_or_else(missing)? + "::PUZZLES.iter()".len();
+    let chain = last + lib[last..].len() - lib[last..].trim_start_matches(')').len();
+    let lib = format!(
+        "{}\n        .chain(year{}::PUZZLES.iter()){}",
+        &lib[..chain],
+        year,
+        &lib[chain..]
+    );
+    Ok((manifest, lib))
+}
+
+/// Creates the day under `root` (the repository root) and returns the files
+/// it wrote or changed. A year's first day sets up the year's crate and
+/// registry as well. Nothing is written unless every step can be done.
+pub fn scaffold(
+    root: &Path,
+    year: u16,
+    day: u8,
+    examples: &[Example],
+) -> Result<Vec<PathBuf>, String> {
+    let read = |path: &Path| {
+        fs::read_to_string(path).map_err(|e| format!("could not read {}: {}", path.display(), e))
+    };
+    let year_dir = root.join(year.to_string());
+    let module_path = year_dir
+        .join(format!("day{}", day))
+        .join(format!("day{}.rs", day));
+    let lib_path = year_dir.join("src").join("lib.rs");
+    let registry_path = root
+        .join("aoc")
+        .join("src")
+        .join(format!("year{}.rs", year));
+    if module_path.exists() {
+        return Err(format!("{} already exists", module_path.display()));
+    }
+
+    let mut new_year = Vec::new();
+    let (lib, registry) = if lib_path.exists() {
+        (read(&lib_path)?, read(&registry_path)?)
+    } else if registry_path.exists() {
+        return Err(format!("{} isn't laid out one module per day", year));
+    } else {
+        let workspace_path = root.join("Cargo.toml");
+        let manifest_path = root.join("aoc").join("Cargo.toml");
+        let runner_path = root.join("aoc").join("src").join("lib.rs");
+        let workspace = add_member(&read(&workspace_path)?, year)?;
+        let (manifest, runner) = add_year(&read(&manifest_path)?, &read(&runner_path)?, year)?;
+        let year = year.to_string();
+        new_year.push((
+            year_dir.join("Cargo.toml"),
+            MANIFEST.replace("{year}", &year),
+        ));
+        new_year.push((workspace_path, workspace));
+        new_year.push((manifest_path, manifest));
+        new_year.push((runner_path, runner));
+        (
+            LIB.replace("{year}", &year),
+            REGISTRY.replace("{year}", &year),
+        )
+    };
+    let mut writes = vec![
+        (module_path, module(examples)),
+        (lib_path, declare(&lib, day)?),
+        (registry_path, register(&registry, year, day)?),
+    ];
+    writes.append(&mut new_year);
+
+    let mut written = Vec::new();
+    for (path, contents) in writes {
+        let dir = path.parent().unwrap();
+        fs::create_dir_all(dir)
+            .map_err(|e| format!("could not create {}: {}", dir.display(), e))?;
+        fs::write(&path, contents)
+            .map_err(|e| format!("could not write {}: {}", path.display(), e))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::temp_dir;
+
+    const LIB: &str = "//! Solutions.\n#[path = \"../day1/day1.rs\"]\npub mod day1;\n";
+
+    const REGISTRY: &str = "use crate::{Answer, Puzzle, Solution};
+
+pub struct Day1;
+
+pub static PUZZLES: &[Puzzle] = &[
+    Puzzle::new(2022, 1, &Day1),
+];
+";
+
+    const WORKSPACE: &str = "[workspace]\nmembers = [\"2022\", \"aoc\", \"common\"]\n";
+
+    const MANIFEST: &str = "[dependencies]
+aoc-common = { path = \"../common\" }
+aoc2022 = { path = \"../2022\" }
+serde = \"1\"
+";
+
+    const RUNNER: &str = "pub mod verify;
+pub mod year2022;
+
+pub fn puzzles() -> impl Iterator<Item = &'static Puzzle> {
+    year2022::PUZZLES.iter()
+}
+";
+
+    fn example(part: u8, input: &str, answer: &str) -> Example {
+        Example {
+            part,
+            input: String::from(input),
+            answer: String::from(answer),
+        }
+    }
+
+    #[test]
+    fn test_module() {
+        let source = module(&[example(1, "1,\"a\"\n2,\\b\n", "-3")]);
+        assert!(source.contains("    const DATA: &str = \"1,\\\"a\\\"\n2,\\\\b\";\n"));
+        assert!(source.contains("pub fn part1(_data: &[&str]) -> Option<i64> {\n    None\n}"));
+        assert!(source.contains("assert_eq!(part1(&parse(DATA).unwrap()), Some(-3));"));
+        assert!(source.contains("pub fn part2(_data: &[&str]) -> Option<usize>"));
+        assert!(source.contains("assert_eq!(part2(&parse(DATA).unwrap()), None);"));
+
+        // Part two's example only gets its own constant when it's a new one
+        let source = module(&[example(1, "abc\n", "7"), example(2, "abc\n", "ABC")]);
+        assert!(source
+            .contains("assert_eq!(part2(&parse(DATA).unwrap()), Some(String::from(\"ABC\")));"));
+        assert!(!source.contains("DATA2"));
+        let source = module(&[example(1, "abc\n", "7"), example(2, "xyz\n", "8")]);
+        assert!(source.contains("    const DATA2: &str = \"xyz\";"));
+        assert!(source.contains("assert_eq!(part2(&parse(DATA2).unwrap()), Some(8));"));
+
+        assert!(
+            module(&[]).contains("// The example from the puzzle\n    const DATA: &str = \"\";")
+        );
+    }
+
+    #[test]
+    fn test_declare() {
+        let lib = declare(LIB, 2).unwrap();
+        assert!(lib.ends_with("pub mod day1;\n#[path = \"../day2/day2.rs\"]\npub mod day2;\n"));
+        assert!(declare(&lib, 2).is_err());
+        assert!(declare(&lib, 1).is_err());
+    }
+
+    #[test]
+    fn test_register() {
+        let registry = register(REGISTRY, 2022, 2).unwrap();
+        assert!(registry.contains("pub struct Day2;\n\nimpl Solution for Day2 {"));
+        assert!(registry.contains("        day2::part2(data).map(Answer::from)\n"));
+        assert!(registry.ends_with(
+            "pub static PUZZLES: &[Puzzle] = &[
+    Puzzle::new(2022, 1, &Day1),
+    Puzzle::new(2022, 2, &Day2),
+];
+"
+        ));
+        assert!(register(&registry, 2022, 2).is_err());
+        assert!(register("pub struct Day1;\n", 2022, 2).is_err());
+    }
+
+    #[test]
+    fn test_add_year() {
+        assert_eq!(
+            add_member(WORKSPACE, 2023).unwrap(),
+            "[workspace]\nmembers = [\"2022\", \"2023\", \"aoc\", \"common\"]\n"
+        );
+        assert!(add_member("[package]\n", 2023).is_err());
+
+        let (manifest, runner) = add_year(MANIFEST, RUNNER, 2023).unwrap();
+        assert!(manifest
+            .contains("aoc2022 = { path = \"../2022\" }\naoc2023 = { path = \"../2023\" }\n"));
+        assert!(runner.contains("pub mod year2022;\npub mod year2023;\n"));
+        assert!(runner.contains(
+            "    year2022::PUZZLES.iter()
+        .chain(year2023::PUZZLES.iter())
+}"
+        ));
+        let (_, runner) = add_year(&manifest, &runner, 2024).unwrap();
+        assert!(runner.contains(
+            "        .chain(year2023::PUZZLES.iter())
+        .chain(year2024::PUZZLES.iter())
+}"
+        ));
+    }
+
+    #[test]
+    fn test_scaffold() {
+        let root = temp_dir("scaffold");
+        fs::create_dir_all(root.join("2022/src")).unwrap();
+        fs::create_dir_all(root.join("aoc/src")).unwrap();
+        fs::write(root.join("2022/src/lib.rs"), LIB).unwrap();
+        fs::write(root.join("aoc/src/year2022.rs"), REGISTRY).unwrap();
+        fs::write(root.join("aoc/src/year2021.rs"), "").unwrap();
+
+        let written = scaffold(&root, 2022, 2, &[example(1, "abc\n", "3")]).unwrap();
+        assert_eq!(written[0], root.join("2022/day2/day2.rs"));
+        let source = fs::read_to_string(&written[0]).unwrap();
+        assert!(source.contains("const DATA: &str = \"abc\";"));
+        assert!(fs::read_to_string(root.join("2022/src/lib.rs"))
+            .unwrap()
+            .ends_with("pub mod day2;\n"));
+        assert!(fs::read_to_string(root.join("aoc/src/year2022.rs"))
+            .unwrap()
+            .contains("Puzzle::new(2022, 2, &Day2),"));
+
+        // Nothing is touched when the day already exists
+        assert!(scaffold(&root, 2022, 2, &[]).is_err());
+        assert_eq!(fs::read_to_string(&written[0]).unwrap(), source);
+        // 2021 is registered, but not one module per day
+        assert!(scaffold(&root, 2021, 19, &[]).is_err());
+        // A new year needs the workspace to add it to
+        assert!(scaffold(&root, 2023, 1, &[]).is_err());
+        assert!(!root.join("2023").exists());
+
+        fs::write(root.join("Cargo.toml"), WORKSPACE).unwrap();
+        fs::write(root.join("aoc/Cargo.toml"), MANIFEST).unwrap();
+        fs::write(root.join("aoc/src/lib.rs"), RUNNER).unwrap();
+        let written = scaffold(&root, 2023, 1, &[]).unwrap();
+        assert_eq!(written.len(), 7);
+        assert!(fs::read_to_string(root.join("2023/Cargo.toml"))
+            .unwrap()
+            .contains("name = \"aoc2023\""));
+        assert!(fs::read_to_string(root.join("2023/src/lib.rs"))
+            .unwrap()
+            .ends_with("#[path = \"../day1/day1.rs\"]\npub mod day1;\n"));
+        let registry = fs::read_to_string(root.join("aoc/src/year2023.rs")).unwrap();
+        assert!(registry.starts_with("use aoc2023::*;\n"));
+        assert!(registry.contains("    Puzzle::new(2023, 1, &Day1),\n];\n"));
+        assert!(fs::read_to_string(root.join("Cargo.toml"))
+            .unwrap()
+            .contains("\"2023\""));
+        assert!(scaffold(&root, 2023, 2, &[]).is_ok());
+    }
+}
@@ -0,0 +1,344 @@
+// Submits answers to the site and keeps a log of every verdict, so a guess
+// that's already known to be wrong (including one that's out of bounds given
+// earlier "too high"/"too low" responses) never gets sent again.
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+use crate::client::{read_response, write_atomically, Client, ClientError};
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    /// Answered too recently; the site wants us to wait this many seconds.
+    Wait(u64),
+    AlreadySolved,
+    /// A response we don't know how to interpret, as plain text.
+    Unknown(String),
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Outcome::Correct => write!(f, "correct"),
+            Outcome::TooHigh => write!(f, "too high"),
+            Outcome::TooLow => write!(f, "too low"),
+            Outcome::Incorrect => write!(f, "incorrect"),
+            Outcome::Wait(seconds) => write!(f, "wait {}s before trying again", seconds),
+            Outcome::AlreadySolved => write!(f, "already solved"),
+            Outcome::Unknown(text) => write!(f, "unrecognized response: {}", text),
+        }
+    }
+}
+
+impl Outcome {
+    // Only verdicts about the answer itself are worth remembering.
+    fn to_record(&self) -> Option<&'static str> {
+        match self {
+            Outcome::Correct => Some("correct"),
+            Outcome::TooHigh => Some("too-high"),
+            Outcome::TooLow => Some("too-low"),
+            Outcome::Incorrect => Some("incorrect"),
+            _ => None,
+        }
+    }
+
+    fn from_record(record: &str) -> Option<Outcome> {
+        match record {
+            "correct" => Some(Outcome::Correct),
+            "too-high" => Some(Outcome::TooHigh),
+            "too-low" => Some(Outcome::TooLow),
+            "incorrect" => Some(Outcome::Incorrect),
+            _ => None,
+        }
+    }
+}
+
+/// Works out what the site said from the `<article>` of its response page.
+pub fn parse_response(html: &str) -> Outcome {
+    let article = match (html.find("<article>"), html.find("</article>")) {
+        (Some(start), Some(end)) if start < end => &html[start..end],
+        _ => html,
+    };
+    let text = strip_tags(article);
+
+    if text.contains("That's the right answer") {
+        Outcome::Correct
+    } else if text.contains("That's not the right answer") {
+        if text.contains("your answer is too high") {
+            Outcome::TooHigh
+        } else if text.contains("your answer is too low") {
+            Outcome::TooLow
+        } else {
+            Outcome::Incorrect
+        }
+    } else if text.contains("You gave an answer too recently") {
+        Outcome::Wait(parse_wait(&text).unwrap_or(60))
+    } else if text.contains("You don't seem to be solving the right level") {
+        Outcome::AlreadySolved
+    } else {
+        Outcome::Unknown(text)
+    }
+}
+
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for ch in html.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => (),
+        }
+    }
+    text.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+// "You have 1m 5s left to wait." => 65
+fn parse_wait(text: &str) -> Option<u64> {
+    let start = text.find("You have ")? + "You have ".len();
+    let end = start + text[start..].find(" left to wait")?;
+    let mut seconds = 0;
+    for amount in text[start..end].split_whitespace() {
+        let (n, unit) = amount.split_at(amount.len() - 1);
+        let n: u64 = n.parse().ok()?;
+        seconds += match unit {
+            "h" => n * 3600,
+            "m" => n * 60,
+            "s" => n,
+            _ => return None,
+        };
+    }
+    Some(seconds)
+}
+
+// What the site would say about `answer` given earlier verdicts, if we can
+// tell without asking.
+fn known_outcome(guesses: &[(Outcome, String)], answer: &str) -> Option<Outcome> {
+    if let Some((outcome, _)) = guesses.iter().find(|(_, guess)| guess == answer) {
+        return Some(outcome.clone());
+    }
+    if guesses
+        .iter()
+        .any(|(outcome, _)| *outcome == Outcome::Correct)
+    {
+        return Some(Outcome::AlreadySolved);
+    }
+    let answer: i128 = answer.parse().ok()?;
+    for (outcome, guess) in guesses {
+        match (outcome, guess.parse::<i128>()) {
+            (Outcome::TooHigh, Ok(guess)) if answer >= guess => return Some(Outcome::TooHigh),
+            (Outcome::TooLow, Ok(guess)) if answer <= guess => return Some(Outcome::TooLow),
+            _ => (),
+        }
+    }
+    None
+}
+
+impl Client {
+    pub fn guesses_path(&self, year: u16, day: u8, part: u8) -> PathBuf {
+        self.cache_dir
+            .join(year.to_string())
+            .join(format!("{}-{}-guesses.txt", day, part))
+    }
+
+    /// Every recorded verdict for a part, oldest first.
+    pub fn guesses(&self, year: u16, day: u8, part: u8) -> io::Result<Vec<(Outcome, String)>> {
+        let contents = match fs::read_to_string(self.guesses_path(year, day, part)) {
+            Ok(contents) => contents,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+            Err(e) => return Err(e),
+        };
+        Ok(contents
+            .lines()
+            .filter_map(|line| {
+                let (record, answer) = line.split_once(' ')?;
+                Some((Outcome::from_record(record)?, answer.to_string()))
+            })
+            .collect())
+    }
+
+    /// The verdict for `answer` if it follows from earlier submissions.
+    pub fn previous_outcome(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> io::Result<Option<Outcome>> {
+        Ok(known_outcome(&self.guesses(year, day, part)?, answer))
+    }
+
+    /// Submits `answer`, unless earlier verdicts already tell us how it'd go,
+    /// in which case that verdict is returned without contacting the site.
+    pub fn submit(
+        &self,
+        year: u16,
+        day: u8,
+        part: u8,
+        answer: &str,
+    ) -> Result<Outcome, ClientError> {
+        let mut guesses = self.guesses(year, day, part)?;
+        if let Some(outcome) = known_outcome(&guesses, answer) {
+            return Ok(outcome);
+        }
+
+        let request = self.request("POST", &format!("/{}/day/{}/answer", year, day))?;
+        let level = part.to_string();
+        let html = read_response(request.send_form(&[("level", &level), ("answer", answer)]))?;
+        let outcome = parse_response(&html);
+
+        if outcome.to_record().is_some() {
+            guesses.push((outcome.clone(), answer.to_string()));
+            let log: String = guesses
+                .iter()
+                .map(|(outcome, guess)| format!("{} {}\n", outcome.to_record().unwrap(), guess))
+                .collect();
+            write_atomically(&self.guesses_path(year, day, part), &log)?;
+        }
+        Ok(outcome)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_server::{temp_dir, TestServer};
+
+    fn page(article: &str) -> String {
+        format!(
+            "<!DOCTYPE html><html><body><main><article><p>{}</p></article></main></body></html>",
+            article
+        )
+    }
+
+    #[test]
+    fn test_parse_response() {
+        assert_eq!(
+            parse_response(&page(
+                "That's the right answer!  You are <span class=\"day-success\">one gold star</span> closer to collecting enough star fruit."
+            )),
+            Outcome::Correct
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too high.  If you're stuck, make sure you're using the full input data. Please wait one minute before trying again. [<a href=\"/2022/day/1\">Return to Day 1</a>]"
+            )),
+            Outcome::TooHigh
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer; your answer is too low.  Please wait one minute before trying again."
+            )),
+            Outcome::TooLow
+        );
+        assert_eq!(
+            parse_response(&page(
+                "That's not the right answer.  If you're stuck, make sure you're using the full input data."
+            )),
+            Outcome::Incorrect
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 1m 5s left to wait. [<a href=\"/2022/day/1\">Return to Day 1</a>]"
+            )),
+            Outcome::Wait(65)
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 34s left to wait."
+            )),
+            Outcome::Wait(34)
+        );
+        assert_eq!(
+            parse_response(&page(
+                "You don't seem to be solving the right level.  Did you already complete it? [<a href=\"/2022/day/1\">Return to Day 1</a>]"
+            )),
+            Outcome::AlreadySolved
+        );
+        assert_eq!(
+            parse_response(&page("Something <em>new</em>.")),
+            Outcome::Unknown(String::from("Something new."))
+        );
+    }
+
+    #[test]
+    fn test_known_outcome() {
+        let guesses = vec![
+            (Outcome::TooHigh, String::from("500")),
+            (Outcome::TooLow, String::from("100")),
+            (Outcome::Incorrect, String::from("250")),
+        ];
+        assert_eq!(known_outcome(&guesses, "250"), Some(Outcome::Incorrect));
+        assert_eq!(known_outcome(&guesses, "501"), Some(Outcome::TooHigh));
+        assert_eq!(known_outcome(&guesses, "500"), Some(Outcome::TooHigh));
+        assert_eq!(known_outcome(&guesses, "-3"), Some(Outcome::TooLow));
+        assert_eq!(known_outcome(&guesses, "300"), None);
+        assert_eq!(known_outcome(&guesses, "ABCDEF"), None);
+
+        let guesses = vec![(Outcome::Correct, String::from("ABCDEF"))];
+        assert_eq!(known_outcome(&guesses, "ABCDEF"), Some(Outcome::Correct));
+        assert_eq!(
+            known_outcome(&guesses, "GHIJKL"),
+            Some(Outcome::AlreadySolved)
+        );
+    }
+
+    #[test]
+    fn test_submit() {
+        let server = TestServer::start(|request| {
+            match request.body.as_str() {
+            "level=1&answer=500" => (
+                200,
+                page("That's not the right answer; your answer is too high."),
+            ),
+            "level=1&answer=300" => (
+                200,
+                page("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have 20s left to wait."),
+            ),
+            _ => (200, page("That's the right answer!")),
+        }
+        });
+        let client = Client {
+            base_url: server.url.clone(),
+            cache_dir: temp_dir("submit"),
+            session: Some(String::from("abc123")),
+        };
+
+        assert_eq!(client.submit(2022, 4, 1, "500").unwrap(), Outcome::TooHigh);
+        // Neither of these should reach the server
+        assert_eq!(client.submit(2022, 4, 1, "500").unwrap(), Outcome::TooHigh);
+        assert_eq!(client.submit(2022, 4, 1, "600").unwrap(), Outcome::TooHigh);
+        assert_eq!(server.requests().len(), 1);
+
+        // Being told to wait says nothing about the answer, so it's not recorded
+        assert_eq!(client.submit(2022, 4, 1, "300").unwrap(), Outcome::Wait(20));
+        assert_eq!(client.submit(2022, 4, 1, "300").unwrap(), Outcome::Wait(20));
+        assert_eq!(server.requests().len(), 3);
+
+        assert_eq!(client.submit(2022, 4, 1, "400").unwrap(), Outcome::Correct);
+        assert_eq!(
+            client.submit(2022, 4, 1, "401").unwrap(),
+            Outcome::AlreadySolved
+        );
+        assert_eq!(server.requests().len(), 4);
+
+        let requests = server.requests();
+        assert_eq!(requests[0].method, "POST");
+        assert_eq!(requests[0].path, "/2022/day/4/answer");
+        assert_eq!(requests[0].header("Cookie"), Some("session=abc123"));
+        assert_eq!(
+            client.guesses(2022, 4, 1).unwrap(),
+            vec![
+                (Outcome::TooHigh, String::from("500")),
+                (Outcome::Correct, String::from("400")),
+            ]
+        );
+        assert!(client.guesses(2022, 4, 2).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,300 @@
+// Re-runs solvers and checks their answers against the ledger, so a refactor
+// that changes a result doesn't go unnoticed.
+use std::cell::Cell;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::Once;
+use std::time::{Duration, Instant};
+
+use aoc_common::cancel::{Cancel, Search};
+use aoc_common::parse::ParseError;
+
+use crate::ledger::{escape, Ledger};
+use crate::report;
+use crate::{Answer, Puzzle};
+
+#[derive(Debug, PartialEq)]
+pub enum Status {
+    Ok,
+    Mismatch(String),
+    /// Solved, but there's no accepted answer to compare against.
+    Unverified,
+    Panicked(String),
+    /// Still running when its time ran out. The answer, if there is one, is
+    /// the best it had found by then.
+    TimedOut,
+    /// The input couldn't be parsed.
+    Invalid(String),
+    /// Never started, because every worker was still tied up with a part
+    /// that had timed out.
+    Skipped,
+    Unsolved,
+    NoInput,
+}
+
+#[derive(Debug)]
+pub struct Check {
+    pub year: u16,
+    pub day: u8,
+    pub part: u8,
+    pub status: Status,
+    pub answer: Option<Answer>,
+    pub elapsed: Option<Duration>,
+}
+
+impl Check {
+    /// A check that hasn't been run, which is all there is without an input.
+    pub(crate) fn new(puzzle: &Puzzle, part: u8) -> Check {
+        Check {
+            year: puzzle.year,
+            day: puzzle.day,
+            part,
+            status: Status::NoInput,
+            answer: None,
+            elapsed: None,
+        }
+    }
+
+    /// Fills in how the run went, comparing any answer against the ledger.
+    pub(crate) fn record(&mut self, run: Run, elapsed: Duration, ledger: &Ledger) {
+        self.elapsed = Some(elapsed);
+        self.status = match run {
+            Ok(Ok(None)) => Status::Unsolved,
+            Ok(Ok(Some(Search::Cancelled(best)))) => {
+                self.answer = best;
+                Status::TimedOut
+            }
+            Ok(Ok(Some(Search::Finished(answer)))) => {
+                let status = match ledger.get(self.year, self.day, self.part) {
+                    None => Status::Unverified,
+                    Some(expected) if expected == answer.to_string() => Status::Ok,
+                    Some(expected) => Status::Mismatch(expected.to_string()),
+                };
+                self.answer = Some(answer);
+                status
+            }
+            Ok(Err(error)) => Status::Invalid(error.to_string()),
+            Err(message) => Status::Panicked(message),
+        };
+    }
+
+    pub fn failed(&self) -> bool {
+        matches!(
+            self.status,
+            Status::Mismatch(_)
+                | Status::Panicked(_)
+                | Status::Invalid(_)
+                | Status::TimedOut
+                | Status::Skipped
+        )
+    }
+}
+
+/// Runs both parts of every puzzle, using `input` to find each day's input.
+pub fn verify<'a, F>(
+    puzzles: impl Iterator<Item = &'a Puzzle>,
+    ledger: &Ledger,
+    input: F,
+) -> Vec<Check>
+where
+    F: Fn(u16, u8) -> Option<String>,
+{
+    let mut checks = Vec::new();
+    for puzzle in puzzles {
+        let input = input(puzzle.year, puzzle.day);
+        for part in 1..=2 {
+            let mut check = Check::new(puzzle, part);
+            if let Some(input) = &input {
+                let (run, elapsed) = run_part(puzzle, input, part, &Cancel::never());
+                check.record(run, elapsed, ledger);
+            }
+            checks.push(check);
+        }
+    }
+
+    checks
+}
+
+/// What came of running a part: the solver's result, or the message it
+/// panicked with.
+pub(crate) type Run = Result<Result<Option<Search<Answer>>, ParseError>, String>;
+
+pub(crate) fn run_part(puzzle: &Puzzle, input: &str, part: u8, cancel: &Cancel) -> (Run, Duration) {
+    quiet_panics();
+    let start = Instant::now();
+    QUIET.set(true);
+    let run = panic::catch_unwind(AssertUnwindSafe(|| {
+        puzzle.solver.solve_until(input, part, cancel)
+    }))
+    .map_err(|payload| panic_message(&*payload));
+    QUIET.set(false);
+    (run, start.elapsed())
+}
+
+thread_local! {
+    // Whether this thread is running a part, whose panics are reported in
+    // the table and so don't need spraying over stderr as well
+    static QUIET: Cell<bool> = const { Cell::new(false) };
+}
+
+static QUIET_HOOK: Once = Once::new();
+
+// Installs a panic hook that keeps quiet while a part is running, and
+// otherwise leaves it to the hook that was there before. It's installed once
+// and left in place, since swapping hooks in and out around every run would
+// race with any other thread panicking (or swapping hooks) meanwhile.
+fn quiet_panics() {
+    QUIET_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !QUIET.get() {
+                previous(info);
+            }
+        }));
+    });
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        String::from("unknown panic")
+    }
+}
+
+pub fn format_table(checks: &[Check]) -> String {
+    let mut rows = vec![vec![
+        String::from("year"),
+        String::from("day"),
+        String::from("part"),
+        String::from("status"),
+        String::from("time"),
+        String::from("answer"),
+    ]];
+    for check in checks {
+        let status = match &check.status {
+            Status::Ok => String::from("ok"),
+            Status::Mismatch(_) => String::from("MISMATCH"),
+            Status::Unverified => String::from("unverified"),
+            Status::Panicked(_) => String::from("PANICKED"),
+            Status::TimedOut => String::from("TIMED OUT"),
+            Status::Invalid(_) => String::from("INVALID"),
+            Status::Skipped => String::from("SKIPPED"),
+            Status::Unsolved => String::from("unsolved"),
+            Status::NoInput => String::from("no input"),
+        };
+        let details = match (&check.status, &check.answer) {
+            (Status::Mismatch(expected), Some(answer)) => {
+                format!(
+                    "{} (expected {})",
+                    escape(&answer.to_string()),
+                    escape(expected)
+                )
+            }
+            (Status::Panicked(message), _) | (Status::Invalid(message), _) => message.clone(),
+            (Status::TimedOut, Some(answer)) => {
+                format!("{} (best so far)", escape(&answer.to_string()))
+            }
+            (_, Some(answer)) => escape(&answer.to_string()),
+            (_, None) => String::new(),
+        };
+        rows.push(vec![
+            check.year.to_string(),
+            check.day.to_string(),
+            check.part.to_string(),
+            status,
+            check
+                .elapsed
+                .map(report::format_duration)
+                .unwrap_or_default(),
+            details,
+        ]);
+    }
+
+    report::table(&rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Solution;
+    use aoc_common::parse::Scanner;
+
+    struct Example;
+
+    impl Solution for Example {
+        type Input<'a> = u32;
+
+        fn parse(input: &str) -> Result<u32, ParseError> {
+            let mut line = Scanner::new(1, input.trim());
+            let number = line.number()?;
+            line.end()?;
+            Ok(number)
+        }
+
+        fn part1(input: &u32) -> Option<Answer> {
+            Some((input * 2).into())
+        }
+
+        fn part2(input: &u32) -> Option<Answer> {
+            if *input == 0 {
+                panic!("can't handle zero");
+            }
+            None
+        }
+    }
+
+    #[test]
+    fn test_verify() {
+        let puzzles = [
+            Puzzle::new(2022, 1, &Example),
+            Puzzle::new(2022, 2, &Example),
+            Puzzle::new(2022, 3, &Example),
+            Puzzle::new(2022, 4, &Example),
+            Puzzle::new(2022, 5, &Example),
+        ];
+        let ledger = Ledger::parse("2022 1 1 42\n2022 2 1 5\n").unwrap();
+        let checks = verify(puzzles.iter(), &ledger, |_, day| match day {
+            1 => Some(String::from("21")),
+            2 => Some(String::from("0")),
+            3 => Some(String::from("4")),
+            5 => Some(String::from("4 2")),
+            _ => None,
+        });
+
+        let statuses: Vec<&Status> = checks.iter().map(|c| &c.status).collect();
+        assert_eq!(
+            statuses,
+            vec![
+                &Status::Ok,
+                &Status::Unsolved,
+                &Status::Mismatch(String::from("5")),
+                &Status::Panicked(String::from("can't handle zero")),
+                &Status::Unverified,
+                &Status::Unsolved,
+                &Status::NoInput,
+                &Status::NoInput,
+                &Status::Invalid(String::from(
+                    "line 1, column 2: expected end of line, found whitespace"
+                )),
+                &Status::Invalid(String::from(
+                    "line 1, column 2: expected end of line, found whitespace"
+                )),
+            ]
+        );
+        assert_eq!(checks[2].answer, Some(Answer::Number(0)));
+        // Only quiet while the part was running
+        assert!(!QUIET.get());
+        assert_eq!(checks.iter().filter(|c| c.failed()).count(), 4);
+
+        let table = format_table(&checks);
+        let lines: Vec<&str> = table.lines().collect();
+        assert_eq!(lines.len(), 11);
+        assert!(lines[0].starts_with("year  day  part  status"));
+        assert!(lines[3].contains("MISMATCH") && lines[3].ends_with("0 (expected 5)"));
+        assert!(lines[4].contains("PANICKED") && lines[4].ends_with("can't handle zero"));
+        assert!(lines[8].ends_with("no input"));
+        assert!(lines[9].contains("INVALID") && lines[9].ends_with("found whitespace"));
+    }
+}
@@ -0,0 +1,353 @@
+use aoc2021::*;
+use aoc_common::grid::Grid;
+use aoc_common::parse::ParseError;
+
+use crate::{Answer, Puzzle, Solution};
+
+// Each 2021 part was written as its own program, so the shared parsing lives
+// in the first part's module and the second part borrows it from there.
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input<'a> = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+        day1a::parse(input)
+    }
+
+    fn part1(data: &Vec<i32>) -> Option<Answer> {
+        Some(day1a::solve(data).into())
+    }
+
+    fn part2(data: &Vec<i32>) -> Option<Answer> {
+        Some(day1b::solve(data).into())
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input<'a> = Vec<day2a::Command>;
+
+    fn parse(input: &str) -> Result<Vec<day2a::Command>, ParseError> {
+        day2a::parse(input)
+    }
+
+    fn part1(data: &Vec<day2a::Command>) -> Option<Answer> {
+        Some(day2a::solve(data).into())
+    }
+
+    fn part2(data: &Vec<day2a::Command>) -> Option<Answer> {
+        Some(day2b::solve(data).into())
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+        day3a::parse(input)
+    }
+
+    fn part1(data: &Vec<&str>) -> Option<Answer> {
+        Some(day3a::solve(data).into())
+    }
+
+    fn part2(data: &Vec<&str>) -> Option<Answer> {
+        Some(day3b::solve(data).into())
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input<'a> = day4a::Game;
+
+    fn parse(input: &str) -> Result<day4a::Game, ParseError> {
+        day4a::parse(input)
+    }
+
+    fn part1(data: &day4a::Game) -> Option<Answer> {
+        Some(day4a::solve(data).into())
+    }
+
+    fn part2(data: &day4a::Game) -> Option<Answer> {
+        Some(day4b::solve(data).into())
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input<'a> = Vec<day5a::Line>;
+
+    fn parse(input: &str) -> Result<Vec<day5a::Line>, ParseError> {
+        day5a::parse(input)
+    }
+
+    fn part1(data: &Vec<day5a::Line>) -> Option<Answer> {
+        Some(day5a::solve(data).into())
+    }
+
+    fn part2(data: &Vec<day5a::Line>) -> Option<Answer> {
+        Some(day5b::solve(data).into())
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input<'a> = Vec<i32>;
+
+    fn parse(input: &str) -> Result<Vec<i32>, ParseError> {
+        day6a::parse(input)
+    }
+
+    fn part1(data: &Vec<i32>) -> Option<Answer> {
+        Some(day6a::solve(data).into())
+    }
+
+    fn part2(data: &Vec<i32>) -> Option<Answer> {
+        Some(day6b::solve(data).into())
+    }
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input<'a> = Vec<u32>;
+
+    fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+        day7a::parse(input)
+    }
+
+    fn part1(data: &Vec<u32>) -> Option<Answer> {
+        Some(day7a::solve(data).into())
+    }
+
+    fn part2(data: &Vec<u32>) -> Option<Answer> {
+        Some(day7b::solve(data).into())
+    }
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input<'a> = Vec<day8a::Entry<'a>>;
+
+    fn parse(input: &str) -> Result<Vec<day8a::Entry<'_>>, ParseError> {
+        day8a::parse(input)
+    }
+
+    fn part1(data: &Vec<day8a::Entry>) -> Option<Answer> {
+        Some(day8a::solve(data).into())
+    }
+
+    fn part2(data: &Vec<day8a::Entry>) -> Option<Answer> {
+        Some(day8b::solve(data).into())
+    }
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input<'a> = Grid<u8>;
+
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        day9a::parse(input)
+    }
+
+    fn part1(data: &Grid<u8>) -> Option<Answer> {
+        Some(day9a::solve(data).into())
+    }
+
+    fn part2(data: &Grid<u8>) -> Option<Answer> {
+        Some(day9b::solve(data).into())
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+        day10a::parse(input)
+    }
+
+    fn part1(data: &Vec<&str>) -> Option<Answer> {
+        Some(day10a::solve(data).into())
+    }
+
+    fn part2(data: &Vec<&str>) -> Option<Answer> {
+        Some(day10b::solve(data).into())
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input<'a> = Grid<u8>;
+
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        day11a::parse(input)
+    }
+
+    fn part1(data: &Grid<u8>) -> Option<Answer> {
+        Some(day11a::solve(data).into())
+    }
+
+    fn part2(data: &Grid<u8>) -> Option<Answer> {
+        Some(day11b::solve(data).into())
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input<'a> = day12a::NodeLink;
+
+    fn parse(input: &str) -> Result<day12a::NodeLink, ParseError> {
+        day12a::parse(input)
+    }
+
+    fn part1(data: &day12a::NodeLink) -> Option<Answer> {
+        Some(day12a::solve(data).into())
+    }
+
+    fn part2(data: &day12a::NodeLink) -> Option<Answer> {
+        Some(day12b::solve(data).into())
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input<'a> = day13a::Manual;
+
+    fn parse(input: &str) -> Result<day13a::Manual, ParseError> {
+        day13a::parse(input)
+    }
+
+    fn part1(data: &day13a::Manual) -> Option<Answer> {
+        Some(day13a::solve(data).into())
+    }
+
+    fn part2(data: &day13a::Manual) -> Option<Answer> {
+        Some(Answer::screen(day13b::solve(data)))
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input<'a> = day14a::Recipe<'a>;
+
+    fn parse(input: &str) -> Result<day14a::Recipe<'_>, ParseError> {
+        day14a::parse(input)
+    }
+
+    fn part1(data: &day14a::Recipe) -> Option<Answer> {
+        Some(day14a::solve(data).into())
+    }
+
+    fn part2(data: &day14a::Recipe) -> Option<Answer> {
+        Some(day14b::solve(data).into())
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input<'a> = Grid<u8>;
+
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        day15a::parse(input)
+    }
+
+    fn part1(data: &Grid<u8>) -> Option<Answer> {
+        Some(day15a::solve(data).into())
+    }
+
+    fn part2(data: &Grid<u8>) -> Option<Answer> {
+        Some(day15b::solve(data).into())
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input<'a> = day16a::Packet;
+
+    fn parse(input: &str) -> Result<day16a::Packet, ParseError> {
+        day16a::parse(input)
+    }
+
+    fn part1(data: &day16a::Packet) -> Option<Answer> {
+        Some(day16a::solve(data).into())
+    }
+
+    fn part2(data: &day16a::Packet) -> Option<Answer> {
+        Some(day16b::solve(data).into())
+    }
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input<'a> = day17a::TargetArea;
+
+    fn parse(input: &str) -> Result<day17a::TargetArea, ParseError> {
+        day17a::parse(input)
+    }
+
+    fn part1(data: &day17a::TargetArea) -> Option<Answer> {
+        Some(day17a::solve(data).into())
+    }
+
+    fn part2(data: &day17a::TargetArea) -> Option<Answer> {
+        Some(day17b::solve(data).into())
+    }
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input<'a> = Vec<day18a::SnailfishNumber>;
+
+    fn parse(input: &str) -> Result<Vec<day18a::SnailfishNumber>, ParseError> {
+        day18a::parse(input)
+    }
+
+    fn part1(data: &Vec<day18a::SnailfishNumber>) -> Option<Answer> {
+        Some(day18a::solve(data).into())
+    }
+
+    fn part2(data: &Vec<day18a::SnailfishNumber>) -> Option<Answer> {
+        Some(day18b::solve(data).into())
+    }
+}
+
+pub static PUZZLES: &[Puzzle] = &[
+    Puzzle::new(2021, 1, &Day1),
+    Puzzle::new(2021, 2, &Day2),
+    Puzzle::new(2021, 3, &Day3),
+    Puzzle::new(2021, 4, &Day4),
+    Puzzle::new(2021, 5, &Day5),
+    Puzzle::new(2021, 6, &Day6),
+    Puzzle::new(2021, 7, &Day7),
+    Puzzle::new(2021, 8, &Day8),
+    Puzzle::new(2021, 9, &Day9),
+    Puzzle::new(2021, 10, &Day10),
+    Puzzle::new(2021, 11, &Day11),
+    Puzzle::new(2021, 12, &Day12),
+    Puzzle::new(2021, 13, &Day13),
+    Puzzle::new(2021, 14, &Day14),
+    Puzzle::new(2021, 15, &Day15),
+    Puzzle::new(2021, 16, &Day16),
+    Puzzle::new(2021, 17, &Day17),
+    Puzzle::new(2021, 18, &Day18),
+];
@@ -0,0 +1,476 @@
+use std::collections::{HashMap, HashSet};
+
+use aoc2022::*;
+use aoc_common::cancel::{Cancel, Search};
+use aoc_common::grid::Grid;
+use aoc_common::sparse::SparseGrid;
+use aoc_common::parse::ParseError;
+use aoc_common::Part;
+
+use crate::input::Trim;
+use crate::{Answer, Puzzle, Solution};
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input<'a> = Vec<Vec<u32>>;
+
+    fn parse(input: &str) -> Result<Vec<Vec<u32>>, ParseError> {
+        day1::parse(input)
+    }
+
+    fn part1(data: &Vec<Vec<u32>>) -> Option<Answer> {
+        Some(day1::process(data).into())
+    }
+
+    fn part2(data: &Vec<Vec<u32>>) -> Option<Answer> {
+        Some(day1b::process(data).into())
+    }
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input<'a> = Vec<day2::Round>;
+
+    fn parse(input: &str) -> Result<Vec<day2::Round>, ParseError> {
+        day2::parse(input)
+    }
+
+    fn part1(data: &Vec<day2::Round>) -> Option<Answer> {
+        Some(day2::process(data).into())
+    }
+
+    fn part2(data: &Vec<day2::Round>) -> Option<Answer> {
+        Some(day2b::process(data).into())
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input<'a> = Vec<&'a str>;
+
+    fn parse(input: &str) -> Result<Vec<&str>, ParseError> {
+        day3::parse(input)
+    }
+
+    fn part1(data: &Vec<&str>) -> Option<Answer> {
+        Some(day3::process(data).into())
+    }
+
+    fn part2(data: &Vec<&str>) -> Option<Answer> {
+        Some(day3b::process(data).into())
+    }
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input<'a> = Vec<day4::Pair>;
+
+    fn parse(input: &str) -> Result<Vec<day4::Pair>, ParseError> {
+        day4::parse(input)
+    }
+
+    fn part1(data: &Vec<day4::Pair>) -> Option<Answer> {
+        Some(day4::process(data).into())
+    }
+
+    fn part2(data: &Vec<day4::Pair>) -> Option<Answer> {
+        Some(day4b::process(data).into())
+    }
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input<'a> = day5::Drawing;
+
+    // The crate drawing starts with meaningful whitespace
+    const TRIM: Trim = Trim::End;
+
+    fn parse(input: &str) -> Result<day5::Drawing, ParseError> {
+        day5::parse(input)
+    }
+
+    fn part1(data: &day5::Drawing) -> Option<Answer> {
+        Some(day5::process(data).into())
+    }
+
+    fn part2(data: &day5::Drawing) -> Option<Answer> {
+        Some(day5b::process(data).into())
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input<'a> = Vec<char>;
+
+    fn parse(input: &str) -> Result<Vec<char>, ParseError> {
+        day6::parse(input)
+    }
+
+    fn part1(data: &Vec<char>) -> Option<Answer> {
+        Some(day6::process(data, 4).into())
+    }
+
+    fn part2(data: &Vec<char>) -> Option<Answer> {
+        Some(day6::process(data, 14).into())
+    }
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input<'a> = day7::Directory;
+
+    fn parse(input: &str) -> Result<day7::Directory, ParseError> {
+        day7::parse(input)
+    }
+
+    fn part1(data: &day7::Directory) -> Option<Answer> {
+        Some(day7::process(data, Part::One).into())
+    }
+
+    fn part2(data: &day7::Directory) -> Option<Answer> {
+        Some(day7::process(data, Part::Two).into())
+    }
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input<'a> = Grid<u8>;
+
+    fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+        day8::parse(input)
+    }
+
+    fn part1(data: &Grid<u8>) -> Option<Answer> {
+        Some(day8::process(data).into())
+    }
+
+    fn part2(data: &Grid<u8>) -> Option<Answer> {
+        Some(day8b::process(data).into())
+    }
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input<'a> = Vec<day9::Motion>;
+
+    fn parse(input: &str) -> Result<Vec<day9::Motion>, ParseError> {
+        day9::parse(input)
+    }
+
+    fn part1(data: &Vec<day9::Motion>) -> Option<Answer> {
+        Some(day9::process(data, 2).into())
+    }
+
+    fn part2(data: &Vec<day9::Motion>) -> Option<Answer> {
+        Some(day9::process(data, 10).into())
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input<'a> = Vec<day10::Instruction>;
+
+    fn parse(input: &str) -> Result<Vec<day10::Instruction>, ParseError> {
+        day10::parse(input)
+    }
+
+    fn part1(data: &Vec<day10::Instruction>) -> Option<Answer> {
+        Some(day10::process(data).into())
+    }
+
+    fn part2(data: &Vec<day10::Instruction>) -> Option<Answer> {
+        Some(Answer::screen(day10b::process(data)))
+    }
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input<'a> = Vec<day11::Monkey>;
+
+    fn parse(input: &str) -> Result<Vec<day11::Monkey>, ParseError> {
+        day11::parse(input)
+    }
+
+    fn part1(data: &Vec<day11::Monkey>) -> Option<Answer> {
+        Some(day11::process(&mut data.clone(), 20, Part::One).into())
+    }
+
+    fn part2(data: &Vec<day11::Monkey>) -> Option<Answer> {
+        Some(day11::process(&mut data.clone(), 10000, Part::Two).into())
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input<'a> = day12::Heightmap;
+
+    fn parse(input: &str) -> Result<day12::Heightmap, ParseError> {
+        day12::parse(input)
+    }
+
+    fn part1(data: &day12::Heightmap) -> Option<Answer> {
+        Some(day12::process(data).into())
+    }
+
+    fn part2(data: &day12::Heightmap) -> Option<Answer> {
+        Some(day12b::process(data).into())
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input<'a> = Vec<(day13::List, day13::List)>;
+
+    fn parse(input: &str) -> Result<Vec<(day13::List, day13::List)>, ParseError> {
+        day13::parse(input)
+    }
+
+    fn part1(data: &Vec<(day13::List, day13::List)>) -> Option<Answer> {
+        Some(day13::process_part1(data).into())
+    }
+
+    fn part2(data: &Vec<(day13::List, day13::List)>) -> Option<Answer> {
+        Some(day13::process_part2(data).into())
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input<'a> = Vec<Vec<(usize, usize)>>;
+
+    fn parse(input: &str) -> Result<Vec<Vec<(usize, usize)>>, ParseError> {
+        // Each part builds its own map from the rock paths
+        day14::parse_paths(input)
+    }
+
+    fn part1(data: &Vec<Vec<(usize, usize)>>) -> Option<Answer> {
+        let mut map = day14::build_map(data);
+        Some(day14::process(&mut map, (500, 0)).into())
+    }
+
+    fn part2(data: &Vec<Vec<(usize, usize)>>) -> Option<Answer> {
+        let mut map = day14b::build_map(data);
+        Some(day14b::process(&mut map, (500, 0)).into())
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input<'a> = Vec<day15::Reading>;
+
+    fn parse(input: &str) -> Result<Vec<day15::Reading>, ParseError> {
+        day15::parse(input)
+    }
+
+    fn part1(data: &Vec<day15::Reading>) -> Option<Answer> {
+        Some(day15::process(data, 2_000_000).into())
+    }
+
+    fn part2(data: &Vec<day15::Reading>) -> Option<Answer> {
+        Some(day15b::process(data).into())
+    }
+
+    fn part2_until(data: &Vec<day15::Reading>, cancel: &Cancel) -> Option<Search<Answer>> {
+        Some(day15b::process_until(data, cancel).map(Answer::from))
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input<'a> = HashMap<&'a str, day16::Valve>;
+
+    fn parse(input: &str) -> Result<HashMap<&'_ str, day16::Valve>, ParseError> {
+        day16::parse(input)
+    }
+
+    fn part1(data: &HashMap<&str, day16::Valve>) -> Option<Answer> {
+        Some(day16::process(data).into())
+    }
+
+    fn part2(data: &HashMap<&str, day16::Valve>) -> Option<Answer> {
+        Some(day16b::process(data).into())
+    }
+
+    fn part2_until(data: &HashMap<&str, day16::Valve>, cancel: &Cancel) -> Option<Search<Answer>> {
+        Some(day16b::process_until(data, cancel).map(Answer::from))
+    }
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input<'a> = Vec<day17::JetDirection>;
+
+    fn parse(input: &str) -> Result<Vec<day17::JetDirection>, ParseError> {
+        day17::parse_jets(input)
+    }
+
+    fn part1(data: &Vec<day17::JetDirection>) -> Option<Answer> {
+        Some(day17::process(data, 2022).into())
+    }
+
+    fn part2(data: &Vec<day17::JetDirection>) -> Option<Answer> {
+        Some(day17::process(data, 1_000_000_000_000).into())
+    }
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input<'a> = HashSet<(i32, i32, i32)>;
+
+    fn parse(input: &str) -> Result<HashSet<(i32, i32, i32)>, ParseError> {
+        day18::parse(input)
+    }
+
+    fn part1(data: &HashSet<(i32, i32, i32)>) -> Option<Answer> {
+        Some(day18::process(data, true).into())
+    }
+
+    fn part2(data: &HashSet<(i32, i32, i32)>) -> Option<Answer> {
+        Some(day18::process(data, false).into())
+    }
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input<'a> = Vec<day19::Blueprint>;
+
+    fn parse(input: &str) -> Result<Vec<day19::Blueprint>, ParseError> {
+        day19::parse(input)
+    }
+
+    fn part1(data: &Vec<day19::Blueprint>) -> Option<Answer> {
+        Some(day19::process(data).into())
+    }
+
+    fn part2(data: &Vec<day19::Blueprint>) -> Option<Answer> {
+        Some(day19::process2(data).into())
+    }
+
+    fn part1_until(data: &Vec<day19::Blueprint>, cancel: &Cancel) -> Option<Search<Answer>> {
+        Some(day19::process_until(data, cancel).map(Answer::from))
+    }
+
+    fn part2_until(data: &Vec<day19::Blueprint>, cancel: &Cancel) -> Option<Search<Answer>> {
+        Some(day19::process2_until(data, cancel).map(Answer::from))
+    }
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input<'a> = Vec<i64>;
+
+    fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+        day20::parse(input)
+    }
+
+    fn part1(data: &Vec<i64>) -> Option<Answer> {
+        Some(day20::process(data, 1, 1).into())
+    }
+
+    fn part2(data: &Vec<i64>) -> Option<Answer> {
+        Some(day20::process(data, 811589153, 10).into())
+    }
+}
+
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input<'a> = day21::Troop<'a>;
+
+    fn parse(input: &str) -> Result<day21::Troop<'_>, ParseError> {
+        day21::parse(input)
+    }
+
+    fn part1(data: &day21::Troop) -> Option<Answer> {
+        Some(day21::process(data, Part::One).into())
+    }
+
+    fn part2(data: &day21::Troop) -> Option<Answer> {
+        Some(day21::process(data, Part::Two).into())
+    }
+}
+
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input<'a> = day22::Notes;
+
+    // There's some intentional whitespace at the beginning
+    const TRIM: Trim = Trim::End;
+
+    fn parse(input: &str) -> Result<day22::Notes, ParseError> {
+        day22::parse(input)
+    }
+
+    fn part1(data: &day22::Notes) -> Option<Answer> {
+        Some(day22::process(data, Part::One).into())
+    }
+
+    fn part2(data: &day22::Notes) -> Option<Answer> {
+        Some(day22::process(data, Part::Two).into())
+    }
+}
+
+pub struct Day23;
+
+impl Solution for Day23 {
+    type Input<'a> = SparseGrid;
+
+    fn parse(input: &str) -> Result<SparseGrid, ParseError> {
+        day23::parse_elf_map(input)
+    }
+
+    fn part1(data: &SparseGrid) -> Option<Answer> {
+        Some(day23::process(data).into())
+    }
+
+    fn part2(data: &SparseGrid) -> Option<Answer> {
+        Some(day23::process2(data).into())
+    }
+}
+
+pub static PUZZLES: &[Puzzle] = &[
+    Puzzle::new(2022, 1, &Day1),
+    Puzzle::new(2022, 2, &Day2),
+    Puzzle::new(2022, 3, &Day3),
+    Puzzle::new(2022, 4, &Day4),
+    Puzzle::new(2022, 5, &Day5),
+    Puzzle::new(2022, 6, &Day6),
+    Puzzle::new(2022, 7, &Day7),
+    Puzzle::new(2022, 8, &Day8),
+    Puzzle::new(2022, 9, &Day9),
+    Puzzle::new(2022, 10, &Day10),
+    Puzzle::new(2022, 11, &Day11),
+    Puzzle::new(2022, 12, &Day12),
+    Puzzle::new(2022, 13, &Day13),
+    Puzzle::new(2022, 14, &Day14),
+    Puzzle::new(2022, 15, &Day15),
+    Puzzle::new(2022, 16, &Day16),
+    Puzzle::new(2022, 17, &Day17),
+    Puzzle::new(2022, 18, &Day18),
+    Puzzle::new(2022, 19, &Day19),
+    Puzzle::new(2022, 20, &Day20),
+    Puzzle::new(2022, 21, &Day21),
+    Puzzle::new(2022, 22, &Day22),
+    Puzzle::new(2022, 23, &Day23),
+];
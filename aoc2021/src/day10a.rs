@@ -1,7 +1,7 @@
-use std::env;
-use std::fs;
 use std::collections::VecDeque;
 
+use aoc_common::parse::{lines, ParseError};
+
 fn get_corruption_points(input: &str) -> u32 {
     let mut stack = VecDeque::new();
     for c in input.trim().chars() {
@@ -33,21 +33,25 @@ fn get_corruption_points(input: &str) -> u32 {
     return 0;
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
+/// The navigation subsystem's lines, which are nothing but brackets.
+pub fn parse(raw_inputs: &str) -> Result<Vec<&str>, ParseError> {
+    lines(raw_inputs, |line| {
+        line.take_while(|c| c == ' ');
+        let chunks = line.take_while(|c| "()[]{}<>".contains(c));
+        line.take_while(|c| c == ' ');
+        if !line.is_empty() {
+            return Err(line.error("a bracket"));
+        }
+        Ok(chunks)
+    })
+}
 
-    let filename = &args[1];
-    let raw_inputs = fs::read_to_string(filename).expect("Something went wrong reading the file");
+pub fn solve(inputs: &[&str]) -> u32 {
     let mut corruption_total = 0;
-    for input in raw_inputs.trim().split("\n").collect::<Vec<&str>>() {
+    for input in inputs {
         corruption_total += get_corruption_points(input);
     }
-    println!("{}", corruption_total);
+    corruption_total
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
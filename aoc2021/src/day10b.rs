@@ -1,5 +1,3 @@
-use std::env;
-use std::fs;
 use std::collections::VecDeque;
 
 fn get_correction_points(input: &str) -> u64 {
@@ -44,25 +42,16 @@ fn get_correction_points(input: &str) -> u64 {
     return points;
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
-
-    let filename = &args[1];
-    let raw_inputs = fs::read_to_string(filename).expect("Something went wrong reading the file");
+pub fn solve(inputs: &[&str]) -> u64 {
     let mut correction_pointses = Vec::new();
-    for input in raw_inputs.trim().split("\n").collect::<Vec<&str>>() {
+    for input in inputs {
         let points = get_correction_points(input);
         if points > 0 {
             correction_pointses.push(points);
         }
     }
     correction_pointses.sort();
-    println!("{}", correction_pointses[correction_pointses.len()/2]);
+    correction_pointses[correction_pointses.len()/2]
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
@@ -1,38 +1,21 @@
-use std::env;
-use std::fs;
+use aoc_common::grid::Grid;
+use aoc_common::parse::ParseError;
 
-fn parse_input(input: &str) -> Vec<Vec<u8>> {
-    let mut octopodes = Vec::new();
-    for row in input.trim().split("\n") {
-        let mut new_row = Vec::new();
-        for char in row.trim().chars() {
-            new_row.push(char.to_digit(10).unwrap() as u8);
-        }
-        octopodes.push(new_row);
-    }
-    return octopodes;
+pub fn parse(input: &str) -> Result<Grid<u8>, ParseError> {
+    // Rows can be indented, like the examples below
+    let rows: Vec<&str> = input.trim().split("\n").map(|row| row.trim()).collect();
+    Grid::digits(&rows.join("\n"))
 }
 
-fn simulate_rounds(mut octopodes: Vec<Vec<u8>>, rounds: u32) -> u32 {
-    let height = octopodes.len();
-    let width = octopodes[0].len();
+fn simulate_rounds(mut octopodes: Grid<u8>, rounds: u32) -> u32 {
     let mut number_fired = 0;
     for _round in 0..rounds {
-        let mut fired_octopodes: Vec<Vec<bool>> = Vec::new();
-        for row in &octopodes {
-            let mut fired_row = Vec::new();
-            for _octopus in row {
-                fired_row.push(false);
-            }
-            fired_octopodes.push(fired_row);
-        }
-    
+        let mut fired_octopodes = Grid::new(octopodes.width(), octopodes.height(), false);
+
         // During a single step, the following occurs:
         // First, the energy level of each octopus increases by 1.
-        for i in 0..height {
-            for j in 0..width {
-                octopodes[i][j] += 1;
-            }
+        for (_, energy) in octopodes.iter_mut() {
+            *energy += 1;
         }
 
         // Then, any octopus with an energy level greater than 9 flashes. This
@@ -44,77 +27,31 @@ fn simulate_rounds(mut octopodes: Vec<Vec<u8>>, rounds: u32) -> u32 {
         let mut changed = true;
         while changed {
             changed = false;
-            for i in 0..height {
-                for j in 0..width {
-                    if octopodes[i][j] > 9 && !fired_octopodes[i][j] {
-                        number_fired += 1;
-                        changed = true;
-                        fired_octopodes[i][j] = true;
-                        // octopus above
-                        if i != 0 {
-                            octopodes[i-1][j] += 1;
-                        }
-                        // above right
-                        if i != 0 && j != width - 1 {
-                            octopodes[i-1][j+1] += 1;
-                        }
-                        // octopus to right
-                        if j != width - 1 {
-                            octopodes[i][j+1] += 1;
-                        }
-                        // below right
-                        if j != width - 1  && i != height - 1 {
-                            octopodes[i+1][j+1] += 1;
-                        }
-                        // octopus below
-                        if i != height - 1 {
-                            octopodes[i+1][j] += 1;
-                        }
-                        // below left
-                        if i != height - 1 && j != 0 {
-                            octopodes[i+1][j-1] += 1;
-                        }
-                        // octopus to left
-                        if j != 0 {
-                            octopodes[i][j-1] += 1;
-                        }
-                        // above left
-                        if j != 0 && i != 0 {
-                            octopodes[i-1][j-1] += 1;
-                        }
+            for octopus in octopodes.points() {
+                if octopodes[octopus] > 9 && !fired_octopodes[octopus] {
+                    number_fired += 1;
+                    changed = true;
+                    fired_octopodes[octopus] = true;
+                    for neighbor in octopodes.neighbors8(octopus) {
+                        octopodes[neighbor] += 1;
                     }
                 }
             }
         }
 
         // Finally, any octopus that flashed during this step has its energy level set to 0, as it used all of its energy to flash.
-        for (i, row) in fired_octopodes.iter().enumerate() {
-            for (j, fired) in row.iter().enumerate() {
-                if *fired {
-                    octopodes[i][j] = 0;
-                }
+        for (octopus, fired) in fired_octopodes.iter() {
+            if *fired {
+                octopodes[octopus] = 0;
             }
         }
-        // for row in &octopodes {
-        //     println!("{:?}", row);
-        // }
-        // println!();
+        // println!("{}\n", octopodes);
     }
     return number_fired;
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
-
-    let filename = &args[1];
-    let raw_inputs = fs::read_to_string(filename).expect("Something went wrong reading the file");
-
-    println!("{}", simulate_rounds(parse_input(raw_inputs.as_str()), 100));
+pub fn solve(octopodes: &Grid<u8>) -> u32 {
+    simulate_rounds(octopodes.clone(), 100)
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
@@ -137,11 +74,11 @@ mod tests {
 
     #[test]
     fn test_ten_rounds() {
-        assert_eq!(simulate_rounds(parse_input(INPUT), 10), 204);
+        assert_eq!(simulate_rounds(parse(INPUT).unwrap(), 10), 204);
     }
 
     #[test]
     fn test_100_rounds() {
-        assert_eq!(simulate_rounds(parse_input(INPUT), 100), 1656);
+        assert_eq!(simulate_rounds(parse(INPUT).unwrap(), 100), 1656);
     }
 }
@@ -1,20 +1,20 @@
-use std::env;
-use std::fs;
 use std::cell::RefCell;
 use std::rc::Rc;
 
+use aoc_common::parse::{lines, ParseError};
+
 // With gratitude to the timely https://eli.thegreenplace.net/2021/rust-data-structures-with-circular-references/
 
 
-struct Node {
-    name: String,
-    connected: NodeList,
-    visited: bool,
+pub struct Node {
+    pub(crate) name: String,
+    pub(crate) connected: NodeList,
+    pub(crate) visited: bool,
 }
 
-type NodeLink = Rc<RefCell<Node>>;
+pub type NodeLink = Rc<RefCell<Node>>;
 
-struct NodeList(Vec<NodeLink>);
+pub(crate) struct NodeList(pub(crate) Vec<NodeLink>);
 
 impl NodeList {
     fn find_by_name(&mut self, name: &str) -> Option<NodeLink> {
@@ -44,16 +44,26 @@ impl NodeList {
 }
 
 // Returns the starting node
-fn parse_edge_list(input: &str) -> NodeLink {
+pub fn parse(input: &str) -> Result<NodeLink, ParseError> {
     let mut nodes = NodeList(Vec::new());
-    for line in input.trim().split("\n") {
-        let mut caves = line.trim().splitn(2, "-");
-        let first = nodes.find_by_name_or_create(caves.next().unwrap());
-        let second = nodes.find_by_name_or_create(caves.next().unwrap());
+    let edges = lines(input.trim(), |line| {
+        line.take_while(char::is_whitespace);
+        let first = line.word()?;
+        line.expect("-")?;
+        let second = line.word()?;
+        line.take_while(char::is_whitespace);
+        Ok((first, second))
+    })?;
+    for (first, second) in edges {
+        let first = nodes.find_by_name_or_create(first);
+        let second = nodes.find_by_name_or_create(second);
         first.borrow_mut().connected.0.push(Rc::clone(&second));
         second.borrow_mut().connected.0.push(first);
     }
-    return nodes.find_by_name("start").unwrap();
+    let end = input.trim().split("\n").count() + 1;
+    return nodes
+        .find_by_name("start")
+        .ok_or_else(|| ParseError::new(end, 1, "a cave called `start`", "end of input"));
 }
 
 fn find_path_count_to_end(start: NodeLink) -> u32 {
@@ -77,20 +87,8 @@ fn find_path_count_to_end(start: NodeLink) -> u32 {
     return total_paths;
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
-
-    let filename = &args[1];
-    let raw_inputs = fs::read_to_string(filename).expect("Something went wrong reading the file");
-
-    let edges = parse_edge_list(raw_inputs.as_str());
-    // println!("{}", format_edges(edges));
-    println!("{}", find_path_count_to_end(edges));
+pub fn solve(start: &NodeLink) -> u32 {
+    find_path_count_to_end(Rc::clone(start))
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
@@ -100,18 +98,30 @@ mod tests {
 
     #[test]
     fn test_tiny_input() {
-        assert_eq!(find_path_count_to_end(parse_edge_list("start-A
+        assert_eq!(find_path_count_to_end(parse("start-A
             start-b
             A-c
             A-b
             b-d
             A-end
-            b-end")), 10);
+            b-end").unwrap()), 10);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("start-A\nA+end").err(),
+            Some(ParseError::new(2, 2, "`-`", "`+`"))
+        );
+        assert_eq!(
+            parse("b-A\nA-end").err(),
+            Some(ParseError::new(3, 1, "a cave called `start`", "end of input"))
+        );
     }
 
     #[test]
     fn test_medium_input() {
-        assert_eq!(find_path_count_to_end(parse_edge_list("dc-end
+        assert_eq!(find_path_count_to_end(parse("dc-end
             HN-start
             start-kj
             dc-start
@@ -120,12 +130,12 @@ mod tests {
             HN-end
             kj-sa
             kj-HN
-            kj-dc")), 19);
+            kj-dc").unwrap()), 19);
     }
 
     #[test]
     fn test_large_input() {
-        assert_eq!(find_path_count_to_end(parse_edge_list("fs-end
+        assert_eq!(find_path_count_to_end(parse("fs-end
             he-DX
             fs-he
             start-DX
@@ -142,6 +152,6 @@ mod tests {
             he-WI
             zg-he
             pj-fs
-            start-RW")), 226);
+            start-RW").unwrap()), 226);
     }
 }
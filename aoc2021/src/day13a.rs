@@ -1,28 +1,51 @@
-use std::env;
-use std::fs;
-
-fn print_grid(grid: &Vec<Vec<bool>>) {
-    for row in grid {
-        for cell in row {
-            if *cell {
-                print!("#");
-            } else {
-                print!(".");
-            }
+use aoc_common::grid::Grid;
+use aoc_common::parse::{Lines, ParseError, Scanner};
+
+#[allow(dead_code)]
+fn print_grid(grid: &Grid<bool>) {
+    println!("{}\n", grid.map(|&cell| if cell { '#' } else { '.' }));
+}
+
+/// Where the dots are, as `(x, y)`, and the folds to make: along which
+/// axis, and where along it.
+pub type Manual = (Vec<(usize, usize)>, Vec<(char, usize)>);
+
+fn parse_fold(line: &mut Scanner) -> Result<(char, usize), ParseError> {
+    line.take_while(|c| c == ' ');
+    line.expect("fold along ")?;
+    let axis = line.one_of("xy")?;
+    line.expect("=")?;
+    let fold = (axis, line.number()?);
+    line.end()?;
+    Ok(fold)
+}
+
+pub fn parse(raw_input: &str) -> Result<Manual, ParseError> {
+    let mut lines = Lines::new(raw_input);
+    let mut dots = Vec::new();
+    loop {
+        let mut line = lines.next_line()?;
+        line.take_while(|c| c == ' ');
+        if line.is_empty() {
+            break;
         }
-        println!();
+        let x = line.number()?;
+        line.expect(",")?;
+        dots.push((x, line.number()?));
+        line.end()?;
+    }
+    let mut folds = vec![parse_fold(&mut lines.next_line()?)?];
+    for mut line in lines {
+        folds.push(parse_fold(&mut line)?);
     }
-    println!();
+    Ok((dots, folds))
 }
 
-fn generate_grid(instructions: &str) -> Vec<Vec<bool>> {
+pub(crate) fn generate_grid(dots: &[(usize, usize)]) -> Grid<bool> {
     // how big do we make this dang thing?
     let mut max_x = 0;
     let mut max_y = 0;
-    for instruction in instructions.trim().split("\n") {
-        let mut coords = instruction.trim().split(",");
-        let x = coords.next().unwrap().parse().unwrap();
-        let y = coords.next().unwrap().parse().unwrap();
+    for &(x, y) in dots {
         if x > max_x {
             max_x = x;
         }
@@ -30,22 +53,16 @@ fn generate_grid(instructions: &str) -> Vec<Vec<bool>> {
             max_y = y;
         }
     }
-    let mut ret = vec![vec![false; max_x+1]; max_y+1];
+    let mut ret = Grid::new(max_x + 1, max_y + 1, false);
 
     // now fill it up
-    for instruction in instructions.trim().split("\n") {
-        let mut coords = instruction.trim().split(",");
-        let x: usize = coords.next().unwrap().parse().unwrap();
-        let y: usize = coords.next().unwrap().parse().unwrap();
-        ret[y][x] = true;
+    for &(x, y) in dots {
+        ret[(y, x)] = true;
     }
     return ret;
 }
 
-fn process_fold(dots: Vec<Vec<bool>>, fold: &str) -> Vec<Vec<bool>> {
-    let fold_target: usize = fold.trim().split("=").nth(1).unwrap().parse().unwrap();
-    let fold_direction = fold.trim().chars().nth(11).unwrap();
-
+fn process_fold(dots: Grid<bool>, (fold_direction, fold_target): (char, usize)) -> Grid<bool> {
     // Create new half-sized grid
     let width;
     let height;
@@ -56,18 +73,18 @@ fn process_fold(dots: Vec<Vec<bool>>, fold: &str) -> Vec<Vec<bool>> {
         // #...#|.....    #...#
         // .#.#.|#.###    #####
         width = fold_target;
-        height = dots.len();
+        height = dots.height();
     } else {
         assert_eq!(fold_direction, 'y');
         height = fold_target;
-        width = dots[0].len();
+        width = dots.width();
     }
-    let mut ret = vec![vec![false; width]; height];
+    let mut ret = Grid::new(width, height, false);
     
     // copy the base
     for i in 0..height {
         for j in 0..width {
-            ret[i][j] = dots[i][j];
+            ret[(i, j)] = dots[(i, j)];
         }
     }
 
@@ -75,12 +92,12 @@ fn process_fold(dots: Vec<Vec<bool>>, fold: &str) -> Vec<Vec<bool>> {
     for i in 0..height {
         for j in 0..width {
             if fold_direction == 'x' {
-                if dots[i][2*width - j] {
-                    ret[i][j] = true;
+                if dots[(i, 2*width - j)] {
+                    ret[(i, j)] = true;
                 }
             } else {
-                if dots[2*height - i][j] {
-                    ret[i][j] = true;
+                if dots[(2*height - i, j)] {
+                    ret[(i, j)] = true;
                 }
             }
         }
@@ -88,36 +105,13 @@ fn process_fold(dots: Vec<Vec<bool>>, fold: &str) -> Vec<Vec<bool>> {
     return ret;
 }
 
-fn count_visible(grid: &Vec<Vec<bool>>) -> u32{
-    let mut visible = 0;
-    for row in grid {
-        for cell in row {
-            if *cell {
-                visible += 1;
-            }
-        }
-    }
-    return visible;
+fn count_visible(grid: &Grid<bool>) -> u32{
+    grid.iter().filter(|(_, &cell)| cell).count() as u32
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
-
-    let filename = &args[1];
-    let raw_input = fs::read_to_string(filename).expect("Something went wrong reading the file");
-
-    let mut inputs = raw_input.trim().split("\n\n");
-    let points = inputs.next().unwrap();
-    let mut folds = inputs.next().unwrap().trim().split("\n");
-    let mut grid = generate_grid(points);
-
-    grid = process_fold(grid, folds.next().unwrap());
-    println!("{}", count_visible(&grid));
+pub fn solve((dots, folds): &Manual) -> u32 {
+    let grid = process_fold(generate_grid(dots), folds[0]);
+    count_visible(&grid)
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
@@ -149,17 +143,15 @@ mod tests {
 
     #[test]
     fn test_inputs() {
-        let mut inputs = INPUT.trim().split("\n\n");
-        let points = inputs.next().unwrap();
-        let mut folds = inputs.next().unwrap().trim().split("\n");
-        let mut grid = generate_grid(points);
+        let (dots, folds) = parse(INPUT).unwrap();
+        let mut grid = generate_grid(&dots);
         print_grid(&grid);
         assert_eq!(count_visible(&grid), 18);
 
-        grid = process_fold(grid, folds.next().unwrap());
+        grid = process_fold(grid, folds[0]);
         print_grid(&grid);
         assert_eq!(count_visible(&grid), 17);
-        grid = process_fold(grid, folds.next().unwrap());
+        grid = process_fold(grid, folds[1]);
         print_grid(&grid);
         assert_eq!(count_visible(&grid), 16);
     }
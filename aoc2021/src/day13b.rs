@@ -1,51 +1,13 @@
-use std::env;
-use std::fs;
+use aoc_common::grid::Grid;
 
-fn print_grid(grid: &Vec<Vec<bool>>) {
-    for row in grid {
-        for cell in row {
-            if *cell {
-                print!("#");
-            } else {
-                print!(".");
-            }
-        }
-        println!();
-    }
-    println!();
-}
-
-fn generate_grid(instructions: &str) -> Vec<Vec<bool>> {
-    // how big do we make this dang thing?
-    let mut max_x = 0;
-    let mut max_y = 0;
-    for instruction in instructions.trim().split("\n") {
-        let mut coords = instruction.trim().split(",");
-        let x = coords.next().unwrap().parse().unwrap();
-        let y = coords.next().unwrap().parse().unwrap();
-        if x > max_x {
-            max_x = x;
-        }
-        if y > max_y {
-            max_y = y;
-        }
-    }
-    let mut ret = vec![vec![false; max_x+1]; max_y+1];
+use crate::day13a::{generate_grid, Manual};
 
-    // now fill it up
-    for instruction in instructions.trim().split("\n") {
-        let mut coords = instruction.trim().split(",");
-        let x: usize = coords.next().unwrap().parse().unwrap();
-        let y: usize = coords.next().unwrap().parse().unwrap();
-        ret[y][x] = true;
-    }
-    return ret;
+fn format_grid(grid: &Grid<bool>) -> String {
+    grid.map(|&cell| if cell { '#' } else { '.' }).to_string()
 }
 
-fn process_fold(dots: Vec<Vec<bool>>, fold: &str) -> Vec<Vec<bool>> {
-    let fold_target: usize = fold.trim().split("=").nth(1).unwrap().parse().unwrap();
-    let fold_direction = fold.trim().chars().nth(11).unwrap();
 
+fn process_fold(dots: Grid<bool>, (fold_direction, fold_target): (char, usize)) -> Grid<bool> {
     // Create new half-sized grid
     let width;
     let height;
@@ -56,18 +18,18 @@ fn process_fold(dots: Vec<Vec<bool>>, fold: &str) -> Vec<Vec<bool>> {
         // #...#|.....    #...#
         // .#.#.|#.###    #####
         width = fold_target;
-        height = dots.len();
+        height = dots.height();
     } else {
         assert_eq!(fold_direction, 'y');
         height = fold_target;
-        width = dots[0].len();
+        width = dots.width();
     }
-    let mut ret = vec![vec![false; width + 1]; height + 1];
+    let mut ret = Grid::new(width + 1, height + 1, false);
     
     // copy the base
     for i in 0..height {
         for j in 0..width {
-            ret[i][j] = dots[i][j];
+            ret[(i, j)] = dots[(i, j)];
         }
     }
 
@@ -75,12 +37,12 @@ fn process_fold(dots: Vec<Vec<bool>>, fold: &str) -> Vec<Vec<bool>> {
     for i in 0..height {
         for j in 0..width {
             if fold_direction == 'x' {
-                if dots[i][2*width - j] {
-                    ret[i][j] = true;
+                if dots[(i, 2*width - j)] {
+                    ret[(i, j)] = true;
                 }
             } else {
-                if dots[2*height - i][j] {
-                    ret[i][j] = true;
+                if dots[(2*height - i, j)] {
+                    ret[(i, j)] = true;
                 }
             }
         }
@@ -88,45 +50,26 @@ fn process_fold(dots: Vec<Vec<bool>>, fold: &str) -> Vec<Vec<bool>> {
     return ret;
 }
 
-fn count_visible(grid: &Vec<Vec<bool>>) -> u32{
-    let mut visible = 0;
-    for row in grid {
-        for cell in row {
-            if *cell {
-                visible += 1;
-            }
-        }
-    }
-    return visible;
+#[cfg(test)]
+fn count_visible(grid: &Grid<bool>) -> u32{
+    grid.iter().filter(|(_, &cell)| cell).count() as u32
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
-
-    let filename = &args[1];
-    let raw_input = fs::read_to_string(filename).expect("Something went wrong reading the file");
-
-    let mut inputs = raw_input.trim().split("\n\n");
-    let points = inputs.next().unwrap();
-    let folds = inputs.next().unwrap().trim().split("\n");
-    let mut grid = generate_grid(points);
+pub fn solve((dots, folds): &Manual) -> String {
+    let mut grid = generate_grid(dots);
 
-    for fold in folds {
+    for &fold in folds {
         grid = process_fold(grid, fold);
     }
 
-    print_grid(&grid);
+    format_grid(&grid)
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::day13a::parse;
 
     const INPUT: &str = "6,10
     0,14
@@ -152,18 +95,16 @@ mod tests {
 
     #[test]
     fn test_inputs() {
-        let mut inputs = INPUT.trim().split("\n\n");
-        let points = inputs.next().unwrap();
-        let mut folds = inputs.next().unwrap().trim().split("\n");
-        let mut grid = generate_grid(points);
-        print_grid(&grid);
+        let (dots, folds) = parse(INPUT).unwrap();
+        let mut grid = generate_grid(&dots);
+        println!("{}", format_grid(&grid));
         assert_eq!(count_visible(&grid), 18);
 
-        grid = process_fold(grid, folds.next().unwrap());
-        print_grid(&grid);
+        grid = process_fold(grid, folds[0]);
+        println!("{}", format_grid(&grid));
         assert_eq!(count_visible(&grid), 17);
-        grid = process_fold(grid, folds.next().unwrap());
-        print_grid(&grid);
+        grid = process_fold(grid, folds[1]);
+        println!("{}", format_grid(&grid));
         assert_eq!(count_visible(&grid), 16);
     }
 
@@ -1,15 +1,34 @@
-use std::env;
-use std::fs;
-use std::time::Instant;
 use std::collections::HashMap;
 
-fn parse_rules(raw_rules: Vec<&str>) -> Vec<((char, char), char)> {
+use aoc_common::parse::{ParseError, Scanner};
+
+// A rule like "CH -> B", which may be indented
+fn parse_rule(line: &mut Scanner) -> Result<((char, char), char), ParseError> {
+    line.take_while(char::is_whitespace);
+    let start = line.clone();
+    let pair = line.word()?.chars().collect::<Vec<char>>();
+    if pair.len() != 2 {
+        return Err(start.error("a pair of elements"));
+    }
+    line.expect(" -> ")?;
+    let start = line.clone();
+    let element = line.word()?.chars().collect::<Vec<char>>();
+    if element.len() != 1 {
+        return Err(start.error("a single element"));
+    }
+    line.take_while(char::is_whitespace);
+    line.end()?;
+    Ok(((pair[0], pair[1]), element[0]))
+}
+
+// `first_line` is where the rules start in the whole input, for errors
+fn parse_rules(raw_rules: Vec<&str>, first_line: usize) -> Result<Vec<((char, char), char)>, ParseError> {
     let mut rules = Vec::new();
-    for rule in raw_rules {
-        let cs = rule.trim().chars().collect::<Vec<char>>();
-        rules.push(((cs[0], cs[1]), cs[6]));
+    for (i, rule) in raw_rules.into_iter().enumerate() {
+        let rule = parse_rule(&mut Scanner::new(first_line + i, rule))?;
+        rules.push(rule);
     }
-    rules
+    Ok(rules)
 }
 
 fn polymerize(polymer_base: String, rules: &Vec<((char, char), char)>) -> String {
@@ -40,25 +59,27 @@ fn count_unique_chars(polymer: String) -> HashMap<char, u32> {
     char_count
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
+/// The polymer template, and the pair insertion rules.
+pub type Recipe<'a> = (&'a str, Vec<((char, char), char)>);
 
-    let filename = &args[1];
-    let raw_input = fs::read_to_string(filename).expect("Something went wrong reading the file");
+pub fn parse(raw_input: &str) -> Result<Recipe<'_>, ParseError> {
     let mut inputs = raw_input.trim().split("\n\n");
-    let mut polymer = String::from(inputs.next().unwrap());
-    let rules = parse_rules(inputs.next().unwrap().trim().split("\n").collect());
+    let template = inputs.next().unwrap();
+    let raw_rules = inputs.next().ok_or_else(|| {
+        ParseError::new(2, 1, "a blank line, then the rules", "end of input")
+    })?;
+    let rules = parse_rules(raw_rules.trim().split("\n").collect(), 3)?;
+    Ok((template, rules))
+}
 
-    let mut now = Instant::now();
-    for round in 0..10 {
-        polymer = polymerize(polymer, &rules);
-        println!("Completed round {} in {} seconds", round, now.elapsed().as_secs_f32());
-        now = Instant::now();
+pub fn solve(recipe: &Recipe) -> u32 {
+    solve_steps(recipe, 10)
+}
+
+pub(crate) fn solve_steps((template, rules): &Recipe, steps: usize) -> u32 {
+    let mut polymer = String::from(*template);
+    for _round in 0..steps {
+        polymer = polymerize(polymer, rules);
     }
 
     let char_counts = count_unique_chars(polymer);
@@ -72,7 +93,7 @@ fn main() {
             least = count;
         }
     }
-    println!("{}", most - least);
+    most - least
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
@@ -99,11 +120,23 @@ mod tests {
     CC -> N
     CN -> C";
 
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("NNCB\n\nCH -> B\nHHH -> N").unwrap_err(),
+            ParseError::new(4, 1, "a pair of elements", "`HHH`")
+        );
+        assert_eq!(
+            parse("NNCB\n\nCH => B").unwrap_err(),
+            ParseError::new(3, 3, "` -> `", "` => `")
+        );
+    }
+
     #[test]
     fn test_polymerize() {
         let mut inputs = INPUT.trim().split("\n\n");
         let mut polymer = String::from(inputs.next().unwrap());
-        let rules = parse_rules(inputs.next().unwrap().trim().split("\n").collect());
+        let rules = parse_rules(inputs.next().unwrap().trim().split("\n").collect(), 3).unwrap();
 
         polymer = polymerize(polymer, &rules);
         assert_eq!(polymer, "NCNBCHB");
@@ -1,17 +1,6 @@
-use std::env;
-use std::fs;
-use std::time::Instant;
 use std::collections::HashMap;
 
-
-fn parse_rules(raw_rules: Vec<&str>) -> HashMap<(char, char), char> {
-    let mut rules = HashMap::new();
-    for rule in raw_rules {
-        let cs = rule.trim().chars().collect::<Vec<char>>();
-        rules.insert((cs[0], cs[1]), cs[6]);
-    }
-    rules
-}
+use crate::day14a::Recipe;
 
 fn parse_polymer(polymer: String) -> HashMap<(char, char), u64> {
     let mut pairs = HashMap::new();
@@ -49,54 +38,34 @@ fn polymerize(polymer: HashMap<(char, char), u64>, rules: &HashMap<(char, char),
     new_polymer
 }
 
-fn count_unique_chars(polymer: HashMap<(char, char), u64>) -> HashMap<char, u64> {
+// Every element starts exactly one pair, apart from the last one in the
+// polymer, which never changes
+fn count_unique_chars(polymer: HashMap<(char, char), u64>, last: char) -> HashMap<char, u64> {
     let mut char_count = HashMap::new();
+    char_count.insert(last, 1);
     for (pair, count) in polymer {
-        // first
         if let Some(x) = char_count.get_mut(&pair.0) {
             *x += count;
         } else {
             char_count.insert(pair.0, count);
         }
-        // first
-        if let Some(x) = char_count.get_mut(&pair.1) {
-            *x += count;
-        } else {
-            char_count.insert(pair.1, count);
-        }
-    }
-    for (_, val) in char_count.iter_mut() {
-        if *val % 2 == 0 {
-            *val = *val / 2;
-        } else {
-            *val = (*val + 1) / 2;
-        }
     }
     char_count
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
+pub fn solve(recipe: &Recipe) -> u64 {
+    solve_steps(recipe, 40)
+}
 
-    let filename = &args[1];
-    let raw_input = fs::read_to_string(filename).expect("Something went wrong reading the file");
-    let mut inputs = raw_input.trim().split("\n\n");
-    let mut polymer = parse_polymer(String::from(inputs.next().unwrap()));
-    let rules = parse_rules(inputs.next().unwrap().trim().split("\n").collect());
+pub(crate) fn solve_steps((template, rules): &Recipe, steps: usize) -> u64 {
+    let mut polymer = parse_polymer(String::from(*template));
+    let rules: HashMap<(char, char), char> = rules.iter().copied().collect();
 
-    let mut now = Instant::now();
-    for round in 0..40 {
+    for _round in 0..steps {
         polymer = polymerize(polymer, &rules);
-        println!("Completed round {} in {} seconds", round, now.elapsed().as_secs_f32());
-        now = Instant::now();
     }
 
-    let char_counts = count_unique_chars(polymer);
+    let char_counts = count_unique_chars(polymer, template.chars().last().unwrap());
     let mut most: u64 = 0;
     let mut least: u64 = u64::MAX;
     for (_char, count) in char_counts {
@@ -107,5 +76,83 @@ fn main() {
             least = count;
         }
     }
-    println!("{}", most - least);
+    most - least
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::day14a::parse;
+    use aoc_common::differential::{compare, shrink_number, shrink_vec};
+    use aoc_common::rng::Rng;
+
+    // A template and a rule for every pair of elements it could grow
+    #[derive(Clone, Debug)]
+    struct Instructions {
+        template: Vec<char>,
+        elements: Vec<char>,
+        insertions: Vec<char>,
+        steps: usize,
+    }
+
+    impl Instructions {
+        fn generate(rng: &mut Rng) -> Instructions {
+            let elements: Vec<char> = "BCHN".chars().take(2 + rng.below(3)).collect();
+            let template = (0..2 + rng.below(7)).map(|_| *rng.choose(&elements)).collect();
+            let insertions = (0..elements.len() * elements.len())
+                .map(|_| *rng.choose(&elements))
+                .collect();
+            Instructions {
+                template,
+                elements,
+                insertions,
+                steps: rng.below(9),
+            }
+        }
+
+        fn shrink(&self) -> Vec<Instructions> {
+            let mut smaller = Vec::new();
+            for template in shrink_vec(&self.template) {
+                if template.len() >= 2 {
+                    smaller.push(Instructions {
+                        template,
+                        ..self.clone()
+                    });
+                }
+            }
+            for steps in shrink_number(self.steps as u64) {
+                smaller.push(Instructions {
+                    steps: steps as usize,
+                    ..self.clone()
+                });
+            }
+            smaller
+        }
+
+        fn to_input(&self) -> String {
+            let mut input: String = self.template.iter().collect();
+            input.push('\n');
+            let pairs = self
+                .elements
+                .iter()
+                .flat_map(|a| self.elements.iter().map(move |b| (a, b)));
+            for ((a, b), insertion) in pairs.zip(&self.insertions) {
+                input.push_str(&format!("\n{}{} -> {}", a, b, insertion));
+            }
+            input
+        }
+    }
+
+    #[test]
+    fn test_agrees_with_day14a() {
+        compare(
+            0,
+            500,
+            Instructions::generate,
+            Instructions::shrink,
+            |i| crate::day14a::solve_steps(&parse(&i.to_input()).unwrap(), i.steps) as u64,
+            |i| solve_steps(&parse(&i.to_input()).unwrap(), i.steps),
+        )
+        .unwrap();
+    }
 }
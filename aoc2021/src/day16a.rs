@@ -1,12 +1,12 @@
-use std::env;
-use std::fs;
 use std::fmt;
 
-struct Packet {
-    version: u8, // 3 bits
-    type_id: u8, // 3 bits
-    subpackets: Vec<Packet>,
-    contents: u64,
+use aoc_common::parse::ParseError;
+
+pub struct Packet {
+    pub(crate) version: u8, // 3 bits
+    pub(crate) type_id: u8, // 3 bits
+    pub(crate) subpackets: Vec<Packet>,
+    pub(crate) contents: u64,
 }
 
 impl Packet {
@@ -36,9 +36,9 @@ impl fmt::Debug for Packet {
     }
 }
 
-fn parse_to_binary(s: &str) -> Vec<u8> {
+pub(crate) fn parse_to_binary(s: &str) -> Result<Vec<u8>, ParseError> {
     let mut bin = Vec::new();
-    for c in s.chars() {
+    for (i, c) in s.chars().enumerate() {
         bin.append(&mut match c {
             '0' => vec![0, 0, 0, 0],
             '1' => vec![0, 0, 0, 1],
@@ -56,13 +56,16 @@ fn parse_to_binary(s: &str) -> Vec<u8> {
             'D' => vec![1, 1, 0, 1],
             'E' => vec![1, 1, 1, 0],
             'F' => vec![1, 1, 1, 1],
-            _ => panic!("Unknown character"),
+            _ => {
+                let found = format!("`{}`", c);
+                return Err(ParseError::new(1, i + 1, "a hexadecimal digit", found));
+            }
         });
     }
-    bin
+    Ok(bin)
 }
 
-fn binary_to_number(bin: &[u8]) -> u64 {
+pub(crate) fn binary_to_number(bin: &[u8]) -> u64 {
     let mut total = 0;
     for (shift, digit) in bin.iter().rev().enumerate() {
         total += (*digit as u64) << shift;
@@ -70,7 +73,7 @@ fn binary_to_number(bin: &[u8]) -> u64 {
     total
 }
 
-fn parse_packet(packet_binary: Vec<u8>) -> (Packet, u64, Vec<u8>) {
+pub(crate) fn parse_packet(packet_binary: Vec<u8>) -> (Packet, u64, Vec<u8>) {
     let (version, rest) = packet_binary.split_at(3);
     let (type_id, mut rest) = rest.split_at(3);
     if binary_to_number(type_id) == 4 {
@@ -132,19 +135,14 @@ fn parse_packet(packet_binary: Vec<u8>) -> (Packet, u64, Vec<u8>) {
     }
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
+/// The outermost packet of the transmission, with all the others inside it.
+pub fn parse(raw_input: &str) -> Result<Packet, ParseError> {
+    let (packet, _packet_len, _rest) = parse_packet(parse_to_binary(raw_input.trim())?);
+    Ok(packet)
+}
 
-    let filename = &args[1];
-    let raw_input = fs::read_to_string(filename).expect("Something went wrong reading the file");
-    let (packet, _packet_len, _rest) = parse_packet(parse_to_binary(raw_input.as_str().trim()));
-    
-    println!("{}", packet.get_version_sum());
+pub fn solve(packet: &Packet) -> u64 {
+    packet.get_version_sum()
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
@@ -161,7 +159,7 @@ mod tests {
     #[test]
     fn test_decode_single_packet() {
         let raw = "D2FE28";
-        let (packet, packet_len, rest) = parse_packet(parse_to_binary(raw));
+        let (packet, packet_len, rest) = parse_packet(parse_to_binary(raw).unwrap());
         assert_eq!(packet.version, 6);
         assert_eq!(packet.type_id, 4);
         assert_eq!(packet.contents, 2021);
@@ -173,7 +171,7 @@ mod tests {
     #[test]
     fn test_decode_type_0_with_subpackets() {
         let raw = "38006F45291200";
-        let (packet, _packet_len, _rest) = parse_packet(parse_to_binary(raw));
+        let (packet, _packet_len, _rest) = parse_packet(parse_to_binary(raw).unwrap());
         assert_eq!(packet.version, 1);
         assert_eq!(packet.type_id, 6);
         assert_eq!(packet.contents, 0);
@@ -184,7 +182,7 @@ mod tests {
     #[test]
     fn test_decode_type_1_with_subpackets() {
         let raw = "EE00D40C823060";
-        let (packet, _packet_len, _rest) = parse_packet(parse_to_binary(raw));
+        let (packet, _packet_len, _rest) = parse_packet(parse_to_binary(raw).unwrap());
         assert_eq!(packet.version, 7);
         assert_eq!(packet.type_id, 3);
         assert_eq!(packet.contents, 0);
@@ -196,7 +194,7 @@ mod tests {
     #[test]
     fn test_example_one() {
         let raw = "8A004A801A8002F478";
-        let (packet, _packet_len, _rest) = parse_packet(parse_to_binary(raw));
+        let (packet, _packet_len, _rest) = parse_packet(parse_to_binary(raw).unwrap());
         assert_eq!(packet.version, 4);
         assert_eq!(packet.subpackets[0].version, 1);
         assert_eq!(packet.subpackets[0].subpackets[0].version, 5);
@@ -207,7 +205,7 @@ mod tests {
     #[test]
     fn test_example_two() {
         let raw = "620080001611562C8802118E34";
-        let (packet, _packet_len, _rest) = parse_packet(parse_to_binary(raw));
+        let (packet, _packet_len, _rest) = parse_packet(parse_to_binary(raw).unwrap());
         assert_eq!(packet.version, 3);
         assert_eq!(packet.get_version_sum(), 12);
     }
@@ -215,14 +213,14 @@ mod tests {
     #[test]
     fn test_example_three() {
         let raw = "C0015000016115A2E0802F182340";
-        let (packet, _packet_len, _rest) = parse_packet(parse_to_binary(raw));
+        let (packet, _packet_len, _rest) = parse_packet(parse_to_binary(raw).unwrap());
         assert_eq!(packet.get_version_sum(), 23);
     }
 
     #[test]
     fn test_example_four() {
         let raw = "A0016C880162017C3686B18A3D4780";
-        let (packet, _packet_len, _rest) = parse_packet(parse_to_binary(raw));
+        let (packet, _packet_len, _rest) = parse_packet(parse_to_binary(raw).unwrap());
         assert_eq!(packet.get_version_sum(), 31);
     }
 }
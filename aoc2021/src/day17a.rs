@@ -1,5 +1,7 @@
 // https://www.desmos.com/calculator/lms03mpiz8
 
+use aoc_common::parse::{ParseError, Scanner};
+
 fn y(t: i32, initial_velocity: i32) -> i32 {
     return -(i32::pow(t, 2) - t)/2 + initial_velocity * t;
 }
@@ -44,12 +46,25 @@ fn lands_in_target(x_min: i32, x_max: i32, y_min: i32, y_max: i32, velocity: (i3
 
 
 
-fn main() {
-    // target area: x=277..318, y=-92..-53
-    let x_min = 277;
-    let x_max = 318;
-    let y_min = -92;
-    let y_max = -53;
+/// The target area, as `(x_min, x_max, y_min, y_max)`.
+pub type TargetArea = (i32, i32, i32, i32);
+
+// Parses a line like "target area: x=20..30, y=-10..-5"
+pub fn parse(input: &str) -> Result<TargetArea, ParseError> {
+    let mut line = Scanner::new(1, input.trim());
+    line.expect("target area: x=")?;
+    let x_min = line.number()?;
+    line.expect("..")?;
+    let x_max = line.number()?;
+    line.expect(", y=")?;
+    let y_min = line.number()?;
+    line.expect("..")?;
+    let y_max = line.number()?;
+    line.end()?;
+    Ok((x_min, x_max, y_min, y_max))
+}
+
+pub fn solve(&(x_min, x_max, y_min, y_max): &TargetArea) -> i32 {
     let mut highest_y_position = 0;
     for x_velocity in 0..x_max {
         for y_velocity in 0..=-y_min {
@@ -60,8 +75,7 @@ fn main() {
             }
         }
     }
-    println!("{}", highest_y_position);
-
+    highest_y_position
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
@@ -88,6 +102,15 @@ mod tests {
         assert_eq!(lands_in_target(20, 30, -10, -5, (17, -4)), false);
     }
 
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("target area: x=20..30, y=-10..-5\n"), Ok((20, 30, -10, -5)));
+        assert_eq!(
+            parse("target area: x=20..30, y=-10.-5"),
+            Err(ParseError::new(1, 29, "`..`", "`.-`"))
+        );
+    }
+
     #[test]
     fn find_highest() {
         // target area: x=20..30, y=-10..-5
@@ -1,5 +1,7 @@
 // https://www.desmos.com/calculator/lms03mpiz8
 
+use crate::day17a::TargetArea;
+
 fn y(t: i32, initial_velocity: i32) -> i32 {
     return -(i32::pow(t, 2) - t)/2 + initial_velocity * t;
 }
@@ -43,14 +45,7 @@ fn lands_in_target(x_min: i32, x_max: i32, y_min: i32, y_max: i32, velocity: (i3
     false
 }
 
-
-
-fn main() {
-    // target area: x=277..318, y=-92..-53
-    let x_min = 277;
-    let x_max = 318;
-    let y_min = -92;
-    let y_max = -53;
+pub fn solve(&(x_min, x_max, y_min, y_max): &TargetArea) -> i32 {
     let mut lands_in_target_count = 0;
     for x_velocity in 0..=x_max {
         for y_velocity in y_min..=-y_min {
@@ -59,14 +54,14 @@ fn main() {
             }
         }
     }
-    println!("{}", lands_in_target_count);
-
+    lands_in_target_count
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::day17a::parse;
 
     #[test]
     fn find_max_y() {
@@ -87,6 +82,11 @@ mod tests {
         assert_eq!(lands_in_target(20, 30, -10, -5, (17, -4)), false);
     }
 
+    #[test]
+    fn test_parse() {
+        assert_eq!(parse("target area: x=20..30, y=-10..-5\n"), Ok((20, 30, -10, -5)));
+    }
+
     #[test]
     fn find_highest() {
         // target area: x=20..30, y=-10..-5
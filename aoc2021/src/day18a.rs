@@ -1,11 +1,11 @@
-use std::env;
-use std::fs;
 use std::fmt;
 use std::ops::Add;
 use std::iter::Sum;
 
+use aoc_common::parse::{lines, ParseError, Scanner};
+
 #[derive(Eq, Debug, Clone)]
-struct SnailfishNumber {
+pub struct SnailfishNumber {
     regular_number: Option<u32>,
     pair_left: Option<Box<SnailfishNumber>>,
     pair_right: Option<Box<SnailfishNumber>>,
@@ -133,6 +133,11 @@ impl SnailfishNumber {
         let mut reduced = true;
         while reduced {
             reduced = false;
+            // Originally, I'd been iterating over chars here, which is _nearly_
+            // the same as iterating over tokens. However, iterating over chars
+            // causes problems when a number is longer than a single digit.
+            // Because of this, it's easier to tokenize the string and process
+            // it as tokens (which, mostly, are individual characters).
             let tokens = self.tokenize();
             let mut new_tokens = Vec::new();
             let mut depth = 0;
@@ -223,7 +228,7 @@ impl SnailfishNumber {
         }
     }
 
-    fn magnitude(&self) -> u32 {
+    pub(crate) fn magnitude(&self) -> u32 {
         if self.regular_number.is_some() {
             self.regular_number.unwrap()
         } else {
@@ -292,23 +297,39 @@ impl<'a> Sum<&'a Self> for SnailfishNumber {
     }
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
+// A regular number, or a pair like "[1,[2,3]]"
+fn parse_number(line: &mut Scanner) -> Result<SnailfishNumber, ParseError> {
+    if !line.eat("[") {
+        return Ok(SnailfishNumber{
+            regular_number: Some(line.number()?),
+            pair_left: None,
+            pair_right: None,
+        });
     }
+    let left = parse_number(line)?;
+    line.expect(",")?;
+    let right = parse_number(line)?;
+    line.expect("]")?;
+    Ok(SnailfishNumber{
+        regular_number: None,
+        pair_left: Some(Box::new(left)),
+        pair_right: Some(Box::new(right)),
+    })
+}
 
-    let filename = &args[1];
-    let raw_input = fs::read_to_string(filename).expect("Something went wrong reading the file");
-    let mut numbers = Vec::new();
-    for line in raw_input.trim().split("\n") {
-        numbers.push(SnailfishNumber::from_string(&String::from(line.trim())));
-    }
+/// One snailfish number per line, which may be indented.
+pub fn parse(raw_input: &str) -> Result<Vec<SnailfishNumber>, ParseError> {
+    lines(raw_input.trim(), |line| {
+        line.take_while(char::is_whitespace);
+        let number = parse_number(line)?;
+        line.take_while(char::is_whitespace);
+        Ok(number)
+    })
+}
 
+pub fn solve(numbers: &[SnailfishNumber]) -> u32 {
     let sum: SnailfishNumber = numbers.iter().sum();
-    println!("{}", sum.magnitude());
+    sum.magnitude()
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
@@ -591,4 +612,20 @@ mod tests {
         assert_eq!(sum.to_string(), "[[[[6,6],[7,6]],[[7,7],[7,0]]],[[[7,7],[7,7]],[[7,8],[9,9]]]]");
         assert_eq!(sum.magnitude(), 4140);
     }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse("[1,2]\n  [[1,2],3]  ").unwrap(),
+            vec![SnailfishNumber::from_string(&String::from("[1,2]")), SnailfishNumber::from_string(&String::from("[[1,2],3]"))]
+        );
+        assert_eq!(
+            parse("[1,2]\n[[1,2];3]").unwrap_err(),
+            ParseError::new(2, 7, "`,`", "`;`")
+        );
+        assert_eq!(
+            parse("[1,x]").unwrap_err(),
+            ParseError::new(1, 4, "a number", "`x]`")
+        );
+    }
 }
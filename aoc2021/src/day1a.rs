@@ -1,31 +1,17 @@
-use std::env;
-use std::fs;
+use aoc_common::parse::{lines, ParseError};
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
-
-    let filename = &args[1];
-
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
+pub fn parse(contents: &str) -> Result<Vec<i32>, ParseError> {
+    lines(contents, |line| line.number())
+}
 
-    let depths = contents.split_whitespace();
+pub fn solve(depths: &[i32]) -> u32 {
     let mut prev = 0;
     let mut increasing_depth_count = 0;
-    for depth_str in depths {
-        let depth = depth_str.parse::<i32>().expect(concat!(
-            "Couldn't parse ",
-            stringify!(depth_str),
-            " as integer"
-        ));
+    for &depth in depths {
         if depth > prev && prev > 0 {
             increasing_depth_count += 1;
         }
         prev = depth;
     }
-    println!("{}", increasing_depth_count);
+    increasing_depth_count
 }
@@ -1,40 +1,36 @@
-use std::env;
-use std::fs;
+use aoc_common::parse::{lines, ParseError};
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
-
-    let filename = &args[1];
-
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
+#[derive(Clone, Copy, Debug)]
+pub enum Command {
+    Forward(i32),
+    Up(i32),
+    Down(i32),
+}
 
-    let directions = contents.split('\n');
+pub fn parse(contents: &str) -> Result<Vec<Command>, ParseError> {
+    lines(contents, |line| {
+        if line.eat("forward ") {
+            Ok(Command::Forward(line.number()?))
+        } else if line.eat("up ") {
+            Ok(Command::Up(line.number()?))
+        } else if line.eat("down ") {
+            Ok(Command::Down(line.number()?))
+        } else {
+            Err(line.error("`forward`, `up` or `down`"))
+        }
+    })
+}
 
+pub fn solve(commands: &[Command]) -> i32 {
     let mut x = 0;
     let mut z = 0; // depth
 
-    for direction in directions {
-        let words = direction.split_whitespace().collect::<Vec<&str>>();
-        if words.len() == 0 {
-            // empty last line, perhaps
-            continue;
-        }
-        let distance = words[1].parse::<i32>().expect(concat!(
-            "Couldn't parse ",
-            stringify!(words[1]),
-            " as integer"
-        ));
-        match words[0] {
-            "forward" => x += distance,
-            "up" => z -= distance,
-            "down" => z += distance,
-            _ => panic!(),
+    for &command in commands {
+        match command {
+            Command::Forward(distance) => x += distance,
+            Command::Up(distance) => z -= distance,
+            Command::Down(distance) => z += distance,
         }
     }
-    println!("x{}z{} = {}", x, z, x * z);
+    x * z
 }
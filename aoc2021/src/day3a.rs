@@ -1,20 +1,28 @@
-use std::env;
-use std::fs;
+use aoc_common::parse::{lines, ParseError};
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
-
-    let filename = &args[1];
-
-    let raw_contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
-    let contents = raw_contents.trim();
+/// The diagnostic report, which has to be binary numbers all of the same
+/// width.
+pub fn parse(contents: &str) -> Result<Vec<&str>, ParseError> {
+    let mut width = None;
+    lines(contents, |line| {
+        let bits = line.take_while(|c| c == '0' || c == '1');
+        if bits.is_empty() {
+            return Err(line.error("a binary number"));
+        }
+        let width = *width.get_or_insert(bits.len());
+        if bits.len() != width && line.is_empty() {
+            return Err(ParseError::new(
+                line.line(),
+                1,
+                format!("{} digits, like the first line", width),
+                format!("{} digits", bits.len()),
+            ));
+        }
+        Ok(bits)
+    })
+}
 
-    let inputs = contents.split('\n').collect::<Vec<&str>>();
+pub fn solve(inputs: &[&str]) -> u32 {
     let inputs_count = inputs.len();
     let digit_count = inputs[0].len();
     let mut digits = vec![0; digit_count];
@@ -29,7 +37,6 @@ fn main() {
 
     let mut gamma = 0;
     let mut epsilon = 0;
-    println!("{:?}", digits);
     for (i, digit) in digits.iter().enumerate() {
         if digit > &(inputs_count/2) {
             gamma += (2 << (digit_count - i - 1))/2;
@@ -41,5 +48,5 @@ fn main() {
         }
     }
 
-    println!("Gamma: {}\nEpsilon: {}\nPower Consumption: {}", gamma, epsilon, gamma * epsilon)
+    gamma * epsilon
 }
@@ -1,48 +1,16 @@
-use std::env;
-use std::fs;
-
 #[derive(Debug, PartialEq, Eq)]
 enum Rating {
     Oxygen,
     CarbonDioxide,
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
-
-    let filename = &args[1];
-
-    // ??? If I don't add the intermediate `let` binding, this errors:
-    //
-    // temporary value dropped while borrowed
-    // creates a temporary which is freed while still in use
-    // note: consider using a `let` binding to create a longer lived valuerustc(E0716)
-    // day3b.rs(21, 18): creates a temporary which is freed while still in use
-    // day3b.rs(21, 136): temporary value is freed at the end of this statement
-    // day3b.rs(23, 47): borrow later used here
-    //
-    // let inputs = fs::read_to_string(filename).expect("Something went wrong reading the file").trim().split('\n').collect::<Vec<&str>>();
-    let raw_inputs = fs::read_to_string(filename).expect("Something went wrong reading the file");
-    let inputs = raw_inputs.trim().split('\n').collect::<Vec<&str>>();
-
-    let oxygen_generator_binary = find_rating(inputs.clone(), Rating::Oxygen);
+pub fn solve(inputs: &[&str]) -> isize {
+    let oxygen_generator_binary = find_rating(inputs.to_vec(), Rating::Oxygen);
     let oxygen_generator_rating = isize::from_str_radix(oxygen_generator_binary, 2).unwrap();
-    let co2_scrubber_binary = find_rating(inputs, Rating::CarbonDioxide);
+    let co2_scrubber_binary = find_rating(inputs.to_vec(), Rating::CarbonDioxide);
     let co2_scrubber_rating = isize::from_str_radix(co2_scrubber_binary, 2).unwrap();
 
-    println!(
-        "Oxygen: {} ({})\nCO₂: {} ({})\nLife Support: {}",
-        oxygen_generator_rating,
-        oxygen_generator_binary,
-        co2_scrubber_rating,
-        co2_scrubber_binary,
-        oxygen_generator_rating * co2_scrubber_rating
-    )
+    oxygen_generator_rating * co2_scrubber_rating
 }
 
 fn find_rating(mut inputs: Vec<&str>, mode: Rating) -> &str {
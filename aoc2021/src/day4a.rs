@@ -1,15 +1,15 @@
-use std::env;
 use std::fmt;
-use std::fs;
+
+use aoc_common::parse::{ParseError, Scanner};
 
 #[derive(Debug, Copy, Clone)]
-struct Space {
-    value: u8,
-    called: bool,
+pub(crate) struct Space {
+    pub(crate) value: u8,
+    pub(crate) called: bool,
 }
 
 #[derive(Debug, Copy, Clone)]
-struct Board([[Space; 5]; 5]); // board[row][column]
+pub struct Board(pub(crate) [[Space; 5]; 5]); // board[row][column]
 
 impl fmt::Display for Board {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -32,7 +32,7 @@ impl fmt::Display for Board {
 }
 
 impl Board {
-    fn apply_move(&mut self, number: u8) -> bool {
+    pub(crate) fn apply_move(&mut self, number: u8) -> bool {
         let mut changed = false;
         // This seems to not work. Perhaps one of the for loops makes a copy??
         // for row in self.0 {
@@ -55,7 +55,7 @@ impl Board {
         return changed;
     }
 
-    fn score(&self, last_number: u8) -> u32 {
+    pub(crate) fn score(&self, last_number: u8) -> u32 {
         let mut score = 0;
         for row in self.0 {
             for cell in row {
@@ -69,29 +69,31 @@ impl Board {
     }
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
+/// The numbers called, in order, and the boards.
+pub type Game = (Vec<u8>, Vec<Board>);
 
-    let filename = &args[1];
-    let raw_inputs = fs::read_to_string(filename).expect("Something went wrong reading the file");
+pub fn parse(raw_inputs: &str) -> Result<Game, ParseError> {
     let mut inputs = raw_inputs.trim().split("\n\n");
-    let number_strings = inputs.next().unwrap();
+    let mut number_line = Scanner::new(1, inputs.next().unwrap());
+    let numbers: Vec<u8> = number_line.list(",", |n| n.number())?;
+    number_line.end()?;
     let board_strings = inputs.collect::<Vec<&str>>();
 
     let mut boards: Vec<Board> = Vec::new();
+    let mut first_line = 3; // after the numbers and a blank line
     for board_string in board_strings {
-        boards.push(parse_board(board_string));
+        boards.push(parse_board(board_string, first_line)?);
+        first_line += 6;
     }
+    Ok((numbers, boards))
+}
 
+pub fn solve((numbers, boards): &Game) -> u32 {
+    let mut boards = boards.clone();
     let mut winning_board = None;
     let mut number = 0; // whatever, just so it's always initialized. It'll get overwritten in the next step
-    for number_string in number_strings.split(',') {
-        number = number_string.parse().unwrap();
+    for &called in numbers {
+        number = called;
         for i in 0..boards.len() {
             boards[i].apply_move(number);
             if check_win(boards[i]) {
@@ -105,25 +107,36 @@ fn main() {
     }
 
     let winning_board = winning_board.expect("No board has won?");
-    println!("Won:\n{}\nScore: {}", winning_board, winning_board.score(number));
+    winning_board.score(number)
 }
 
-fn parse_board(input: &str) -> Board {
+// `first_line` is where the board starts in the whole input, for errors
+pub(crate) fn parse_board(input: &str, first_line: usize) -> Result<Board, ParseError> {
     let mut board = Board(
         [[Space {
             value: 0,
             called: false,
         }; 5]; 5],
     );
-    for (i, row) in input.split("\n").enumerate() {
-        for (j, value) in row.split_whitespace().enumerate() {
-            board.0[i][j].value = value.parse().unwrap();
+    let mut rows = input.split("\n");
+    for i in 0..5 {
+        let row = rows.next().ok_or_else(|| {
+            ParseError::new(first_line + i, 1, "a row of 5 numbers", "end of board")
+        })?;
+        let mut row = Scanner::new(first_line + i, row);
+        for j in 0..5 {
+            row.take_while(|c| c == ' ');
+            board.0[i][j].value = row.number()?;
         }
+        row.end()?;
+    }
+    if let Some(row) = rows.next() {
+        return Err(Scanner::new(first_line + 5, row).error("a blank line between boards"));
     }
-    return board;
+    Ok(board)
 }
 
-fn check_win(board: Board) -> bool {
+pub(crate) fn check_win(board: Board) -> bool {
     for row in board.0 {
         if row[0].called && row[1].called && row[2].called && row[3].called && row[4].called {
             return true;
@@ -170,7 +183,7 @@ mod tests {
     #[test]
     fn test_parse_board() {
         let input = "22 13 17 11  0\n8  2 23  4 24\n21  9 14 16  7\n6 10  3 18  5\n1 12 20 15 19";
-        let board = parse_board(input);
+        let board = parse_board(input, 1).unwrap();
         assert_eq!(board.0[0][0].value, 22);
         assert_eq!(board.0[0][1].value, 13);
         assert_eq!(board.0[0][2].value, 17);
@@ -182,10 +195,23 @@ mod tests {
         assert_eq!(board.0[4][4].value, 19);
     }
 
+    #[test]
+    fn test_parse_errors() {
+        let input = "22 13 17 11  0\n8  2 23  4 24\n21  9 14 16\n6 10  3 18  5\n1 12 20 15 19";
+        assert_eq!(
+            parse_board(input, 3).unwrap_err(),
+            ParseError::new(5, 12, "a number", "end of line")
+        );
+        assert_eq!(
+            parse("7,4;9\n\n").unwrap_err(),
+            ParseError::new(1, 4, "end of line", "`;9`")
+        );
+    }
+
     #[test]
     fn test_check_not_won() {
         let input = "22 13 17 11  0\n8  2 23  4 24\n21  9 14 16  7\n6 10  3 18  5\n1 12 20 15 19";
-        let mut board = parse_board(input);
+        let mut board = parse_board(input, 1).unwrap();
         board.0[1][0].called = true;
         board.0[1][1].called = true;
         board.0[1][2].called = true;
@@ -196,7 +222,7 @@ mod tests {
     #[test]
     fn test_check_win_row() {
         let input = "22 13 17 11  0\n8  2 23  4 24\n21  9 14 16  7\n6 10  3 18  5\n1 12 20 15 19";
-        let mut board = parse_board(input);
+        let mut board = parse_board(input, 1).unwrap();
         board.0[1][0].called = true;
         board.0[1][1].called = true;
         board.0[1][2].called = true;
@@ -208,7 +234,7 @@ mod tests {
     #[test]
     fn test_check_win_column() {
         let input = "22 13 17 11  0\n8  2 23  4 24\n21  9 14 16  7\n6 10  3 18  5\n1 12 20 15 19";
-        let mut board = parse_board(input);
+        let mut board = parse_board(input, 1).unwrap();
         board.0[0][1].called = true;
         board.0[1][1].called = true;
         board.0[2][1].called = true;
@@ -221,7 +247,7 @@ mod tests {
     fn test_run_game() {
         let mut boards: Vec<Board> = Vec::new();
         for board_string in RAW_BOARDS.split("\n\n") {
-            boards.push(parse_board(board_string));
+            boards.push(parse_board(board_string, 1).unwrap());
         }
     
         let mut winning_board = None;
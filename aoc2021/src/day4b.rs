@@ -1,100 +1,12 @@
-use std::env;
-use std::fmt;
-use std::fs;
-
-#[derive(Debug, Copy, Clone)]
-struct Space {
-    value: u8,
-    called: bool,
-}
-
-#[derive(Debug, Copy, Clone)]
-struct Board([[Space; 5]; 5]); // board[row][column]
-
-impl fmt::Display for Board {
-    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
-        for row in self.0 {
-            for cell in row {
-                let mut taken = ' ';
-                if cell.called {
-                    taken = '*';
-                }
-                if cell.value < 10 {
-                    write!(f, " {}{}{} ", taken, cell.value, taken)?;
-                } else {
-                    write!(f, "{}{}{} ", taken, cell.value, taken)?;
-                }
-            }
-            write!(f, "\n")?;
-        }
-        write!(f, "")
-    }
-}
-
-impl Board {
-    fn apply_move(&mut self, number: u8) -> bool {
-        let mut changed = false;
-        // This seems to not work. Perhaps one of the for loops makes a copy??
-        // for row in self.0 {
-        //     for mut cell in row {
-        //         if cell.value == number && !cell.called {
-        //             changed = true;
-        //             cell.called = true;
-        //             println!("updated {} {} {}", cell.called, cell.value, self);
-        //         }
-        //     }
-        // }
-        for row in 0..5 {
-            for column in 0..5 {
-                if self.0[row][column].value == number {
-                    self.0[row][column].called = true;
-                    changed = true;
-                }
-            }
-        }
-        return changed;
-    }
-
-    fn score(&self, last_number: u8) -> u32 {
-        let mut score = 0;
-        for row in self.0 {
-            for cell in row {
-                if !cell.called {
-                    score += cell.value as u32; // https://stackoverflow.com/a/44552464
-                    println!("{}", score);
-                }
-            }
-        }
-        score *= last_number as u32;
-        return score;
-    }
-}
-
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
-
-    let filename = &args[1];
-    let raw_inputs = fs::read_to_string(filename).expect("Something went wrong reading the file");
-    let mut inputs = raw_inputs.trim().split("\n\n");
-    let number_strings = inputs.next().unwrap();
-    let board_strings = inputs.collect::<Vec<&str>>();
-
-    let mut boards: Vec<Board> = Vec::new();
-    for board_string in board_strings {
-        boards.push(parse_board(board_string));
-        println!("{}", boards[boards.len() - 1]);
-    }
+use crate::day4a::{check_win, Game};
 
+pub fn solve((numbers, boards): &Game) -> u32 {
+    let mut boards = boards.clone();
     let mut last_board_won = None;
     let mut number = 0; // whatever, just so it's always initialized. It'll get overwritten in the next step
-    for number_string in number_strings.split(',') {
+    for &called in numbers {
         let mut remaining_boards = Vec::new();
-        number = number_string.parse().unwrap();
+        number = called;
         for i in 0..boards.len() {
             boards[i].apply_move(number);
             if check_win(boards[i]) {
@@ -110,49 +22,14 @@ fn main() {
     }
 
     let last_board_won = last_board_won.expect("No board has won?");
-    println!("Won:\n{}\nScore: {}", last_board_won, last_board_won.score(number));
-}
-
-// TODO: this could be a static method of Board (e.g. Board::parse_board(...))
-fn parse_board(input: &str) -> Board {
-    let mut board = Board(
-        [[Space {
-            value: 0,
-            called: false,
-        }; 5]; 5],
-    );
-    for (i, row) in input.split("\n").enumerate() {
-        for (j, value) in row.split_whitespace().enumerate() {
-            board.0[i][j].value = value.parse().unwrap();
-        }
-    }
-    return board;
-}
-
-// TODO: this could be a method of Board
-fn check_win(board: Board) -> bool {
-    for row in board.0 {
-        if row[0].called && row[1].called && row[2].called && row[3].called && row[4].called {
-            return true;
-        }
-    }
-    for column in 0..5 {
-        if board.0[0][column].called
-            && board.0[1][column].called
-            && board.0[2][column].called
-            && board.0[3][column].called
-            && board.0[4][column].called
-        {
-            return true;
-        }
-    }
-    return false;
+    last_board_won.score(number)
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::day4a::{parse_board, Board};
 
     const RAW_MOVES: &str =
         "7,4,9,5,11,17,23,2,0,14,21,24,10,16,13,6,15,25,12,22,18,20,8,19,3,26,1";
@@ -177,7 +54,7 @@ mod tests {
     #[test]
     fn test_parse_board() {
         let input = "22 13 17 11  0\n8  2 23  4 24\n21  9 14 16  7\n6 10  3 18  5\n1 12 20 15 19";
-        let board = parse_board(input);
+        let board = parse_board(input, 1).unwrap();
         assert_eq!(board.0[0][0].value, 22);
         assert_eq!(board.0[0][1].value, 13);
         assert_eq!(board.0[0][2].value, 17);
@@ -192,7 +69,7 @@ mod tests {
     #[test]
     fn test_check_not_won() {
         let input = "22 13 17 11  0\n8  2 23  4 24\n21  9 14 16  7\n6 10  3 18  5\n1 12 20 15 19";
-        let mut board = parse_board(input);
+        let mut board = parse_board(input, 1).unwrap();
         board.0[1][0].called = true;
         board.0[1][1].called = true;
         board.0[1][2].called = true;
@@ -203,7 +80,7 @@ mod tests {
     #[test]
     fn test_check_win_row() {
         let input = "22 13 17 11  0\n8  2 23  4 24\n21  9 14 16  7\n6 10  3 18  5\n1 12 20 15 19";
-        let mut board = parse_board(input);
+        let mut board = parse_board(input, 1).unwrap();
         board.0[1][0].called = true;
         board.0[1][1].called = true;
         board.0[1][2].called = true;
@@ -215,7 +92,7 @@ mod tests {
     #[test]
     fn test_check_win_column() {
         let input = "22 13 17 11  0\n8  2 23  4 24\n21  9 14 16  7\n6 10  3 18  5\n1 12 20 15 19";
-        let mut board = parse_board(input);
+        let mut board = parse_board(input, 1).unwrap();
         board.0[0][1].called = true;
         board.0[1][1].called = true;
         board.0[2][1].called = true;
@@ -228,7 +105,7 @@ mod tests {
     fn test_run_game() {
         let mut boards: Vec<Board> = Vec::new();
         for board_string in RAW_BOARDS.split("\n\n") {
-            boards.push(parse_board(board_string));
+            boards.push(parse_board(board_string, 1).unwrap());
             println!("{}", boards[boards.len() - 1]);
         }
     
@@ -257,7 +134,7 @@ mod tests {
     fn find_last_win() {
         let mut boards: Vec<Board> = Vec::new();
         for board_string in RAW_BOARDS.split("\n\n") {
-            boards.push(parse_board(board_string));
+            boards.push(parse_board(board_string, 1).unwrap());
             println!("{}", boards[boards.len() - 1]);
         }
     
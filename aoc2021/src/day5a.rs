@@ -1,85 +1,56 @@
-use std::env;
-use std::fs;
 use std::cmp;
 
+use aoc_common::parse::{lines, ParseError, Scanner};
+use aoc_common::sparse::SparseGrid;
+
 #[allow(dead_code)]
-fn format_grid(grid: Vec<Vec<i32>>) -> String {
-    let mut ret = String::new();
-    for row in grid {
-        for cell in row {
-            ret += &cell.to_string();
-            ret += " ";
-        }
-        ret += "\n";
-    }
-    return ret;
+fn format_grid(grid: &SparseGrid<i32>) -> String {
+    grid.render(|cell| match cell {
+        Some(count) => char::from_digit(*count as u32, 10).unwrap_or('+'),
+        None => '.',
+    })
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
+/// Where a line of vents starts and ends.
+pub type Line = ((i64, i64), (i64, i64));
+
+fn parse_point(line: &mut Scanner) -> Result<(i64, i64), ParseError> {
+    let x = line.number()?;
+    line.expect(",")?;
+    Ok((x, line.number()?))
+}
+
+pub fn parse(raw_inputs: &str) -> Result<Vec<Line>, ParseError> {
+    lines(raw_inputs, |line| {
+        line.take_while(|c| c == ' ');
+        let start = parse_point(line)?;
+        line.expect(" -> ")?;
+        let end = parse_point(line)?;
+        line.take_while(|c| c == ' ');
+        Ok((start, end))
+    })
+}
 
-    let filename = &args[1];
-    let raw_inputs = fs::read_to_string(filename).expect("Something went wrong reading the file");
-    let inputs = raw_inputs.trim().split("\n");
-    //DEBUG assert_eq!(inputs.len(), 500);
-    //DEBUG println!("{}", inputs[0]);
-    //DEBUG println!("{}", inputs[499]);
-    let mut field: Vec<Vec<i32>> = vec![vec![0; 1000]; 1000];
-    for input in inputs {
-        let coords = input.split(" -> ").collect::<Vec<&str>>();
-        let start = coords[0].split(',').collect::<Vec<&str>>();
-        let end = coords[1].split(',').collect::<Vec<&str>>();
-        let start_x: usize = cmp::min(start[0].parse().unwrap(), end[0].parse().unwrap());
-        let start_y: usize = cmp::min(start[1].parse().unwrap(), end[1].parse().unwrap());
-        let end_x: usize = cmp::max(start[0].parse().unwrap(), end[0].parse().unwrap());
-        let end_y: usize = cmp::max(start[1].parse().unwrap(), end[1].parse().unwrap());
-        if start_x != end_x && start_y != end_y {
-            //DEBUG println!("Skipping {:?} -> {:?}", start, end);
+fn draw_lines(lines: &[Line]) -> SparseGrid<i32> {
+    let mut field = SparseGrid::new();
+    for &(start, end) in lines {
+        if start.0 != end.0 && start.1 != end.1 {
             continue;
         }
-        //DEBUG println!("Drawing line from {},{} to {},{} (inputs {} and {})", start_x, start_y, end_x, end_y, coords[0], coords[1]);
-        //DEBUG if start_x == end_x && start_y == end_y {
-        //DEBUG     println!("Found matching start/end: {:?} -> {:?}", start, end);
-        //DEBUG }
-        for x in start_x..=end_x {
-            for y in start_y..=end_y {
-                field[y][x] += 1;
-            }
+        // Lines are all either straight or at 45 degrees, so each step is at
+        // most one in each direction
+        let step = ((end.0 - start.0).signum(), (end.1 - start.1).signum());
+        let length = cmp::max((end.0 - start.0).abs(), (end.1 - start.1).abs());
+        for i in 0..=length {
+            *field.entry((start.0 + i * step.0, start.1 + i * step.1)) += 1;
         }
     }
-    /* (0,2)
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [x, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0],
-    [0, 0, 0, 0, 0]
-    */
+    return field;
+}
 
-    //DEBUG let mut total = 0;
-    //DEBUG for (y, row) in field.iter().enumerate() {
-    //DEBUG     for (x, cell) in row.iter().enumerate() {
-    //DEBUG         if cell > &1 {
-    //DEBUG             if cell > &2 {
-    //DEBUG                 println!("Found crossing of value {} at {},{}", cell, x, y);
-    //DEBUG             }
-    //DEBUG             total += 1;
-    //DEBUG         }
-    //DEBUG     }
-    //DEBUG }
-    let mut total = 0;
-    for row in field {
-        for cell in row {
-            if cell > 1 {
-                total += 1;
-            }
-        }
-    }
-    println!("{}", total);
+pub fn solve(lines: &[Line]) -> u32 {
+    let field = draw_lines(lines);
+    field.iter().filter(|(_, &cell)| cell > 1).count() as u32
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
@@ -101,36 +72,14 @@ mod tests {
 
     #[test]
     fn test_apply_puzzle() {
-        let inputs = RAW_MOVES.trim().split("\n");
-        let mut field: Vec<Vec<i32>> = vec![vec![0; 10]; 10];
-        for mut input in inputs {
-            input = input.trim();
-            let coords = input.split(" -> ").collect::<Vec<&str>>();
-            let start = coords[0].split(',').collect::<Vec<&str>>();
-            let end = coords[1].split(',').collect::<Vec<&str>>();
-            let start_x: usize = cmp::min(start[0].parse().unwrap(), end[0].parse().unwrap());
-            let start_y: usize = cmp::min(start[1].parse().unwrap(), end[1].parse().unwrap());
-            let end_x: usize = cmp::max(start[0].parse().unwrap(), end[0].parse().unwrap());
-            let end_y: usize = cmp::max(start[1].parse().unwrap(), end[1].parse().unwrap());
-            if start_x != end_x && start_y != end_y {
-                continue;
-            }
-            for x in start_x..=end_x {
-                for y in start_y..=end_y {
-                    field[y][x] += 1;
-                }
-            }
-        }
+        println!("{}", format_grid(&draw_lines(&parse(RAW_MOVES).unwrap())));
+        assert_eq!(solve(&parse(RAW_MOVES).unwrap()), 5);
+    }
 
-        let mut total = 0;
-        for row in &field {
-            for cell in row {
-                if *cell > 1 {
-                    total += 1;
-                }
-            }
-        }
-        println!("{}", format_grid(field));
-        assert_eq!(total, 5);
+    #[test]
+    fn test_far_out_coordinates() {
+        // Crossing at 1002,5, while the diagonal through -4,1 is left out
+        let lines = "1000,5 -> 1003,5\n1002,3 -> 1002,7\n-4,0 -> -4,2\n-4,1 -> -2,-1";
+        assert_eq!(solve(&parse(lines).unwrap()), 1);
     }
 }
@@ -1,82 +1,41 @@
-use std::env;
-use std::fs;
+use std::cmp;
+
+use aoc_common::sparse::SparseGrid;
+
+use crate::day5a::Line;
 
 #[allow(dead_code)]
-fn format_grid(grid: Vec<Vec<i32>>) -> String {
-    let mut ret = String::new();
-    for row in grid {
-        for cell in row {
-            ret += &cell.to_string();
-            ret += " ";
-        }
-        ret += "\n";
-    }
-    return ret;
+fn format_grid(grid: &SparseGrid<i32>) -> String {
+    grid.render(|cell| match cell {
+        Some(count) => char::from_digit(*count as u32, 10).unwrap_or('+'),
+        None => '.',
+    })
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
-
-    let filename = &args[1];
-    let raw_inputs = fs::read_to_string(filename).expect("Something went wrong reading the file");
-    let inputs = raw_inputs.trim().split("\n");
-    let mut field: Vec<Vec<i32>> = vec![vec![0; 1000]; 1000];
-    for mut input in inputs {
-        input = input.trim();
-        let coords = input.split(" -> ").collect::<Vec<&str>>();
-        let start = coords[0].split(',').collect::<Vec<&str>>();
-        let end = coords[1].split(',').collect::<Vec<&str>>();
-        let mut start: Vec<usize> = vec![start[0].parse().unwrap(), start[1].parse().unwrap()];
-        let mut end: Vec<usize> = vec![end[0].parse().unwrap(), end[1].parse().unwrap()];
-        
-        let diagonal = start[0] != end[0] && start[1] != end[1];
-        if diagonal {
-            if start[0] > end[0] { // always drawing towards positive x
-                std::mem::swap(&mut start, &mut end);
-            }
-            let y_dir: i32;
-            if start[1] > end[1] {
-                y_dir = -1;
-            } else {
-                y_dir = 1;
-            }
-            for i in 0..=end[0]-start[0] {
-                let y = (start[1] as i32 + i as i32 * y_dir) as usize;
-                let x = start[0]+i;
-                field[y][x] += 1;
-            }
-        } else {
-            if start[0] > end[0] || start[1] > end[1]{
-                std::mem::swap(&mut start, &mut end);
-            }
-            for x in start[0]..=end[0] {
-                for y in start[1]..=end[1] {
-                    field[y][x] += 1;
-                }
-            }
+fn draw_lines(lines: &[Line]) -> SparseGrid<i32> {
+    let mut field = SparseGrid::new();
+    for &(start, end) in lines {
+        // Lines are all either straight or at 45 degrees, so each step is at
+        // most one in each direction
+        let step = ((end.0 - start.0).signum(), (end.1 - start.1).signum());
+        let length = cmp::max((end.0 - start.0).abs(), (end.1 - start.1).abs());
+        for i in 0..=length {
+            *field.entry((start.0 + i * step.0, start.1 + i * step.1)) += 1;
         }
     }
+    return field;
+}
 
-    let mut total = 0;
-    for row in field {
-        for cell in row {
-            if cell > 1 {
-                total += 1;
-            }
-        }
-    }
-    println!("{}", total);
+pub fn solve(lines: &[Line]) -> u32 {
+    let field = draw_lines(lines);
+    field.iter().filter(|(_, &cell)| cell > 1).count() as u32
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::day5a::parse;
 
     const RAW_MOVES: &str =
         "0,9 -> 5,9
@@ -92,53 +51,14 @@ mod tests {
 
     #[test]
     fn test_apply_puzzle() {
-        let inputs = RAW_MOVES.trim().split("\n");
-        let mut field: Vec<Vec<i32>> = vec![vec![0; 10]; 10];
-        for mut input in inputs {
-            input = input.trim();
-            let coords = input.split(" -> ").collect::<Vec<&str>>();
-            let start = coords[0].split(',').collect::<Vec<&str>>();
-            let end = coords[1].split(',').collect::<Vec<&str>>();
-            let mut start: Vec<usize> = vec![start[0].parse().unwrap(), start[1].parse().unwrap()];
-            let mut end: Vec<usize> = vec![end[0].parse().unwrap(), end[1].parse().unwrap()];
-            
-            let diagonal = start[0] != end[0] && start[1] != end[1];
-            if diagonal {
-                if start[0] > end[0] { // always drawing towards positive x
-                    std::mem::swap(&mut start, &mut end);
-                }
-                let y_dir: i32;
-                if start[1] > end[1] {
-                    y_dir = -1;
-                } else {
-                    y_dir = 1;
-                }
-                for i in 0..=end[0]-start[0] {
-                    let y = (start[1] as i32 + i as i32 * y_dir) as usize;
-                    let x = start[0]+i;
-                    field[y][x] += 1;
-                }
-            } else {
-                if start[0] > end[0] || start[1] > end[1]{
-                    std::mem::swap(&mut start, &mut end);
-                }
-                for x in start[0]..=end[0] {
-                    for y in start[1]..=end[1] {
-                        field[y][x] += 1;
-                    }
-                }
-            }
-        }
+        println!("{}", format_grid(&draw_lines(&parse(RAW_MOVES).unwrap())));
+        assert_eq!(solve(&parse(RAW_MOVES).unwrap()), 12);
+    }
 
-        let mut total = 0;
-        for row in &field {
-            for cell in row {
-                if *cell > 1 {
-                    total += 1;
-                }
-            }
-        }
-        println!("{}", format_grid(field));
-        assert_eq!(total, 12);
+    #[test]
+    fn test_far_out_coordinates() {
+        // Crossing at 1002,5, and the diagonal crosses the line at -4,1
+        let lines = "1000,5 -> 1003,5\n1002,3 -> 1002,7\n-4,0 -> -4,2\n-4,1 -> -2,-1";
+        assert_eq!(solve(&parse(lines).unwrap()), 2);
     }
 }
@@ -1,7 +1,6 @@
-use std::env;
-use std::fs;
+use aoc_common::parse::{ParseError, Scanner};
 
-fn calculate_fish_count(mut fishes: Vec<i32>, days: u32) -> u32 {
+pub(crate) fn calculate_fish_count(mut fishes: Vec<i32>, days: u32) -> u32 {
     for _ in 0..days {
         for i in 0..fishes.len() {
             if fishes[i] == 0 {
@@ -15,21 +14,22 @@ fn calculate_fish_count(mut fishes: Vec<i32>, days: u32) -> u32 {
     return fishes.len() as u32;
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
+/// Each lanternfish's timer, which counts down from 8 at the most.
+pub fn parse(raw_inputs: &str) -> Result<Vec<i32>, ParseError> {
+    let mut line = Scanner::new(1, raw_inputs);
+    let fishes = line.list(",", |timer| {
+        let column = timer.column();
+        match timer.number()? {
+            fish @ 0..=8 => Ok(fish),
+            fish => Err(ParseError::new(1, column, "a timer from 0 to 8", format!("`{}`", fish))),
+        }
+    })?;
+    line.end()?;
+    Ok(fishes)
+}
 
-    let filename = &args[1];
-    let raw_inputs = fs::read_to_string(filename).expect("Something went wrong reading the file");
-    let mut inputs = Vec::new();
-    for input in raw_inputs.trim().split(",") {
-        inputs.push(input.parse().unwrap());
-    }
-    println!("{}", calculate_fish_count(inputs, 80));
+pub fn solve(fishes: &[i32]) -> u32 {
+    calculate_fish_count(fishes.to_vec(), 80)
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
@@ -1,6 +1,3 @@
-use std::env;
-use std::fs;
-
 fn calculate_fish_count(fishes: Vec<i32>, days: u32) -> u64 {
     let mut fish_by_day = vec![0 as u64; 9];
     for fish in fishes {
@@ -22,27 +19,16 @@ fn calculate_fish_count(fishes: Vec<i32>, days: u32) -> u64 {
     return fish_by_day.iter().sum();
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
-
-    let filename = &args[1];
-    let raw_inputs = fs::read_to_string(filename).expect("Something went wrong reading the file");
-    let mut inputs = Vec::new();
-    for input in raw_inputs.trim().split(",") {
-        inputs.push(input.parse().unwrap());
-    }
-    println!("{}", calculate_fish_count(inputs, 256));
+pub fn solve(fishes: &[i32]) -> u64 {
+    calculate_fish_count(fishes.to_vec(), 256)
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
 #[cfg(test)]
 mod tests {
     use super::*;
+    use aoc_common::differential::{compare, shrink_number, shrink_vec};
+    use aoc_common::rng::Rng;
 
     const INPUT: &str ="3,4,3,1,2";
 
@@ -56,4 +42,31 @@ mod tests {
         assert_eq!(calculate_fish_count(inputs.clone(), 80), 5934);
         assert_eq!(calculate_fish_count(inputs.clone(), 256), 26984457539);
     }
+
+    #[test]
+    fn test_agrees_with_day6a() {
+        let generate = |rng: &mut Rng| {
+            let fishes = (0..1 + rng.below(6)).map(|_| rng.between(0, 8) as i32).collect();
+            (fishes, rng.below(81) as u32)
+        };
+        let shrink = |(fishes, days): &(Vec<i32>, u32)| {
+            let mut smaller: Vec<(Vec<i32>, u32)> = shrink_vec(fishes)
+                .into_iter()
+                .map(|fishes| (fishes, *days))
+                .collect();
+            for days in shrink_number(*days as u64) {
+                smaller.push((fishes.clone(), days as u32));
+            }
+            smaller
+        };
+        compare(
+            0,
+            200,
+            generate,
+            shrink,
+            |(fishes, days)| crate::day6a::calculate_fish_count(fishes.clone(), *days) as u64,
+            |(fishes, days)| calculate_fish_count(fishes.clone(), *days),
+        )
+        .unwrap();
+    }
 }
@@ -1,5 +1,4 @@
-use std::env;
-use std::fs;
+use aoc_common::parse::{ParseError, Scanner};
 
 fn calculate_optimal_position(crabs: Vec<u32>) -> (u32, u32) {
     // Efficiency? What?
@@ -19,21 +18,15 @@ fn calculate_optimal_position(crabs: Vec<u32>) -> (u32, u32) {
     return (best_position, min_fuel_used);
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
+pub fn parse(raw_inputs: &str) -> Result<Vec<u32>, ParseError> {
+    let mut line = Scanner::new(1, raw_inputs);
+    let crabs = line.list(",", |crab| crab.number())?;
+    line.end()?;
+    Ok(crabs)
+}
 
-    let filename = &args[1];
-    let raw_inputs = fs::read_to_string(filename).expect("Something went wrong reading the file");
-    let mut inputs = Vec::new();
-    for input in raw_inputs.trim().split(",") {
-        inputs.push(input.parse().unwrap());
-    }
-    println!("{}", calculate_optimal_position(inputs).1);
+pub fn solve(crabs: &[u32]) -> u32 {
+    calculate_optimal_position(crabs.to_vec()).1
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
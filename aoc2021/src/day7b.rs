@@ -1,6 +1,3 @@
-use std::env;
-use std::fs;
-
 fn calculate_optimal_position(crabs: Vec<u32>) -> (u32, u32) {
     // Efficiency? What?
     let max_position = crabs.iter().max().unwrap();
@@ -21,21 +18,8 @@ fn calculate_optimal_position(crabs: Vec<u32>) -> (u32, u32) {
     return (best_position, min_fuel_used);
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
-
-    let filename = &args[1];
-    let raw_inputs = fs::read_to_string(filename).expect("Something went wrong reading the file");
-    let mut inputs = Vec::new();
-    for input in raw_inputs.trim().split(",") {
-        inputs.push(input.parse().unwrap());
-    }
-    println!("{}", calculate_optimal_position(inputs).1);
+pub fn solve(crabs: &[u32]) -> u32 {
+    calculate_optimal_position(crabs.to_vec()).1
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
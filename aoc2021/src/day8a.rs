@@ -1,5 +1,4 @@
-use std::env;
-use std::fs;
+use aoc_common::parse::{lines, ParseError, Scanner};
 
 fn identify_digits(_combinations: Vec<&str>, currently_displayed: Vec<&str>) -> String {
     let mut ret = String::new();
@@ -16,31 +15,44 @@ fn identify_digits(_combinations: Vec<&str>, currently_displayed: Vec<&str>) ->
     return ret;
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
+/// The ten unique signal patterns seen on a display, and the four digits
+/// it's showing.
+pub type Entry<'a> = (Vec<&'a str>, Vec<&'a str>);
+
+fn segments<'a>(line: &mut Scanner<'a>) -> Result<&'a str, ParseError> {
+    let segments = line.take_while(|c| ('a'..='g').contains(&c));
+    if segments.is_empty() {
+        return Err(line.error("segments `a` to `g`"));
     }
+    Ok(segments)
+}
 
-    let filename = &args[1];
-    let raw_inputs = fs::read_to_string(filename).expect("Something went wrong reading the file");
+pub fn parse(raw_inputs: &str) -> Result<Vec<Entry<'_>>, ParseError> {
+    lines(raw_inputs, |line| {
+        line.take_while(|c| c == ' ');
+        let mut patterns = vec![segments(line)?];
+        for _ in 1..10 {
+            line.expect(" ")?;
+            patterns.push(segments(line)?);
+        }
+        line.expect(" | ")?;
+        let output_digits = line.list(" ", segments)?;
+        line.take_while(|c| c == ' ');
+        Ok((patterns, output_digits))
+    })
+}
 
+pub fn solve(entries: &[Entry]) -> u32 {
     let mut ones_fours_sevens_eights_count = 0;
-    for input in raw_inputs.trim().split("\n") {
-        let input_sections = input.split(" | ").collect::<Vec<&str>>();
-        let patterns = input_sections[0].trim().split(" ").collect::<Vec<&str>>();
-        assert_eq!(patterns.len(), 10);
-        let output_digits = input_sections[1].split(" ").collect::<Vec<&str>>();
-        let correct_digits = identify_digits(patterns, output_digits);
+    for (patterns, output_digits) in entries {
+        let correct_digits = identify_digits(patterns.clone(), output_digits.clone());
         for digit in correct_digits.chars() {
             if digit == '1' || digit == '4' || digit == '7' || digit == '8' { // TODO: is there a better way to do this?
                 ones_fours_sevens_eights_count += 1;
             }
         }
     }
-    println!("{}", ones_fours_sevens_eights_count);
+    ones_fours_sevens_eights_count
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
@@ -1,7 +1,7 @@
-use std::env;
-use std::fs;
 use std::collections::HashMap;
 
+use crate::day8a::Entry;
+
 fn identify_digits(combinations: Vec<&str>, currently_displayed: Vec<&str>) -> String {
     // Segments on the display occur in some fraction of the ten decimal digits
     // that are displayed. The bottom left segment, represented by "e" below:
@@ -72,28 +72,14 @@ fn identify_digits(combinations: Vec<&str>, currently_displayed: Vec<&str>) -> S
     return ret;
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
-
-    let filename = &args[1];
-    let raw_inputs = fs::read_to_string(filename).expect("Something went wrong reading the file");
-
+pub fn solve(entries: &[Entry]) -> u32 {
     let mut total = 0;
-    for input in raw_inputs.trim().split("\n") {
-        let input_sections = input.split(" | ").collect::<Vec<&str>>();
-        let patterns = input_sections[0].trim().split(" ").collect::<Vec<&str>>();
-        assert_eq!(patterns.len(), 10);
-        let output_digits = input_sections[1].split(" ").collect::<Vec<&str>>();
-        let correct_digits = identify_digits(patterns, output_digits);
+    for (patterns, output_digits) in entries {
+        let correct_digits = identify_digits(patterns.clone(), output_digits.clone());
         let displayed_value: u32 = correct_digits.parse().unwrap();
         total += displayed_value;
     }
-    println!("{}", total);
+    total
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
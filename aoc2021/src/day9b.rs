@@ -1,10 +1,8 @@
-use std::env;
-use std::fs;
 use std::collections::VecDeque;
 
-fn find_basin_sizes(inputs: Vec<&str>) -> Vec<u32> {
-    let mut field: Vec<Vec<bool>> = Vec::new();
+use aoc_common::grid::Grid;
 
+fn find_basin_sizes(field: &Grid<u8>) -> Vec<u32> {
     // Create a map of possible basins
     //
     // for a grid like
@@ -13,7 +11,7 @@ fn find_basin_sizes(inputs: Vec<&str>) -> Vec<u32> {
     //   9856789892
     //   8767896789
     //   9899965678
-    // this will produce a Vec<Vec<bool>> like
+    // this will produce a Grid<bool> like
     //   ..***.....
     //   .*...*.*..
     //   *.....*.*.
@@ -21,91 +19,30 @@ fn find_basin_sizes(inputs: Vec<&str>) -> Vec<u32> {
     //   *.***.....
     //
     // Printing this grid can be done with something like the following:
-    /*
-    for row in &field {
-        for cell in row {
-            if *cell {
-                print!("*");
-            } else {
-                print!(".");
-            }
-        }
-        println!();
-    }
-    */
-    let height = inputs.len();
-    let width = inputs[0].len();
-    let mut remaining_cells = 0;
-    for input in inputs {
-        let mut row = Vec::new();
-        for c in input.chars() {
-            let val = c.to_digit(10);
-            match val {
-                Some(x) => {
-                    if x == 9 {
-                        row.push(true);
-                    } else {
-                        remaining_cells += 1;
-                        row.push(false);
-                    }
-                }
-                None => {
-                    eprintln!("Invalid val '{}'", c);
-                    panic!();
-                }
-            }
-        }
-        field.push(row);
-    }
+    //   println!("{}", field.map(|&cell| if cell { '*' } else { '.' }));
+    let mut field = field.map(|&height| height == 9);
+    let mut remaining_cells = field.iter().filter(|(_, &visited)| !visited).count();
 
     // Then walk each basin to find its size
     let mut basin_sizes = Vec::new();
     while remaining_cells > 0 {
-        // Find a starting cell for a new basin
-        let mut to_visit = VecDeque::new();
-        for (x, row) in field.iter().enumerate() {
-            for (y, visited) in row.iter().enumerate() {
-                if !visited {
-                    to_visit.push_back((x, y));
-                    break;
-                }
-            }
-            if to_visit.len() > 0 {
-                break;
-            }
-        }
-
-        // Mark our starting cell as visited -- Can't do this in the loop above
-        // because it makes the borrow checker unhappy.
-        let first_up = to_visit.front().unwrap();
-        field[first_up.0][first_up.1] = true;
+        // Find a starting cell for a new basin, and mark it as visited
+        let start = field.position(|&visited| !visited).unwrap();
+        field[start] = true;
+        let mut to_visit = VecDeque::from([start]);
 
         let mut new_basin_size = 0;
         // Walk around from that starting cell, incrementing the size for each
         // new cell visited
         while to_visit.len() > 0 {
-            let (i,j) = to_visit.pop_front().unwrap();
+            let cell = to_visit.pop_front().unwrap();
             remaining_cells -= 1;
             new_basin_size += 1;
-            // cell above
-            if i != 0 && !field[i-1][j] {
-                to_visit.push_back((i-1, j));
-                field[i-1][j] = true; // We've been here
-            }
-            // cell below
-            if i != height - 1 && !field[i+1][j] {
-                to_visit.push_back((i+1, j));
-                field[i+1][j] = true; // We've been here
-            }
-            // cell to left
-            if j != 0 && !field[i][j-1] {
-                to_visit.push_back((i, j-1));
-                field[i][j-1] = true; // We've been here
-            }
-            // cell to right
-            if j != width - 1 && !field[i][j+1] {
-                to_visit.push_back((i, j+1));
-                field[i][j+1] = true; // We've been here
+            for neighbor in field.neighbors4(cell) {
+                if !field[neighbor] {
+                    to_visit.push_back(neighbor);
+                    field[neighbor] = true; // We've been here
+                }
             }
         }
         basin_sizes.push(new_basin_size);
@@ -115,26 +52,18 @@ fn find_basin_sizes(inputs: Vec<&str>) -> Vec<u32> {
 
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() != 2 {
-        // no additional args; print help text
-        eprintln!("Usage: {} infile.txt", args[0]);
-        return;
-    }
-
-    let filename = &args[1];
-    let raw_inputs = fs::read_to_string(filename).expect("Something went wrong reading the file");
-    let mut basin_sizes = find_basin_sizes(raw_inputs.trim().split("\n").collect());
+pub fn solve(field: &Grid<u8>) -> u32 {
+    let mut basin_sizes = find_basin_sizes(field);
     basin_sizes.sort();
     basin_sizes.reverse();
-    println!("{}", basin_sizes[0..3].iter().product::<u32>());
+    basin_sizes[0..3].iter().product::<u32>()
 }
 
 // https://doc.rust-lang.org/rust-by-example/testing/unit_testing.html
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::day9a::parse;
 
     const INPUT: &str ="
 2199943210
@@ -146,7 +75,7 @@ mod tests {
 
     #[test]
     fn test_find_total_risk_level() {
-        let mut basin_sizes = find_basin_sizes(INPUT.trim().split("\n").collect());
+        let mut basin_sizes = find_basin_sizes(&parse(INPUT.trim()).unwrap());
         basin_sizes.sort();
         basin_sizes.reverse();
         println!("{:?}", basin_sizes);
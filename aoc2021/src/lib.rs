@@ -0,0 +1,57 @@
+// The original solutions predate running clippy over them; keep the lints
+// they trip quiet rather than rewriting every day.
+#![allow(
+    clippy::bool_assert_comparison,
+    clippy::cast_abs_to_unsigned,
+    clippy::chars_next_cmp,
+    clippy::collapsible_if,
+    clippy::len_zero,
+    clippy::manual_div_ceil,
+    clippy::needless_borrow,
+    clippy::needless_lifetimes,
+    clippy::needless_range_loop,
+    clippy::needless_return,
+    clippy::ptr_arg,
+    clippy::type_complexity,
+    clippy::unnecessary_cast,
+    clippy::unnecessary_unwrap,
+    clippy::useless_vec,
+    clippy::while_let_on_iterator,
+    clippy::write_with_newline,
+)]
+pub mod day1a;
+pub mod day1b;
+pub mod day2a;
+pub mod day2b;
+pub mod day3a;
+pub mod day3b;
+pub mod day4a;
+pub mod day4b;
+pub mod day5a;
+pub mod day5b;
+pub mod day6a;
+pub mod day6b;
+pub mod day7a;
+pub mod day7b;
+pub mod day8a;
+pub mod day8b;
+pub mod day9a;
+pub mod day9b;
+pub mod day10a;
+pub mod day10b;
+pub mod day11a;
+pub mod day11b;
+pub mod day12a;
+pub mod day12b;
+pub mod day13a;
+pub mod day13b;
+pub mod day14a;
+pub mod day14b;
+pub mod day15a;
+pub mod day15b;
+pub mod day16a;
+pub mod day16b;
+pub mod day17a;
+pub mod day17b;
+pub mod day18a;
+pub mod day18b;
@@ -0,0 +1,115 @@
+//! Stopping the slow searches early. A search is handed a `Cancel` and checks
+//! it every so often; once it's been cancelled (or its deadline has passed)
+//! the search stops with the best answer it had found by then.
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Clones share the same flag, so one can be handed to a search and another
+/// kept to cancel it with.
+#[derive(Clone, Debug, Default)]
+pub struct Cancel {
+    cancelled: Arc<AtomicBool>,
+    deadline: Option<Instant>,
+    checks_left: Option<Arc<AtomicU64>>,
+}
+
+impl Cancel {
+    /// A token that's only cancelled by calling `cancel`.
+    pub fn never() -> Cancel {
+        Cancel::default()
+    }
+
+    /// A token that cancels itself once `budget` has passed.
+    pub fn after(budget: Duration) -> Cancel {
+        Cancel {
+            deadline: Some(Instant::now() + budget),
+            ..Cancel::default()
+        }
+    }
+
+    /// A token that cancels itself once it's been checked `checks` times, so
+    /// that a search stops at the same point on every run.
+    pub fn after_checks(checks: u64) -> Cancel {
+        Cancel {
+            checks_left: Some(Arc::new(AtomicU64::new(checks))),
+            ..Cancel::default()
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        let out_of_checks = |checks_left: &Arc<AtomicU64>| {
+            let update = checks_left
+                .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1));
+            update.is_err()
+        };
+        self.cancelled.load(Ordering::Relaxed)
+            || self
+                .deadline
+                .is_some_and(|deadline| Instant::now() >= deadline)
+            || self.checks_left.as_ref().is_some_and(out_of_checks)
+    }
+}
+
+/// How a search that can be cancelled came out.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Search<T> {
+    Finished(T),
+    /// Stopped early. For optimizations this is the best answer found so
+    /// far, which the real answer is at least as good as.
+    Cancelled(Option<T>),
+}
+
+impl<T> Search<T> {
+    pub fn map<U>(self, f: impl FnOnce(T) -> U) -> Search<U> {
+        match self {
+            Search::Finished(answer) => Search::Finished(f(answer)),
+            Search::Cancelled(best) => Search::Cancelled(best.map(f)),
+        }
+    }
+
+    /// The answer, if the search got to the end.
+    pub fn finished(self) -> Option<T> {
+        match self {
+            Search::Finished(answer) => Some(answer),
+            Search::Cancelled(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cancel() {
+        let cancel = Cancel::never();
+        let search = cancel.clone();
+        assert!(!search.is_cancelled());
+        cancel.cancel();
+        assert!(search.is_cancelled());
+
+        assert!(Cancel::after(Duration::ZERO).is_cancelled());
+        assert!(!Cancel::after(Duration::from_secs(60)).is_cancelled());
+
+        let cancel = Cancel::after_checks(2);
+        assert!(!cancel.is_cancelled());
+        assert!(!cancel.clone().is_cancelled());
+        assert!(cancel.is_cancelled());
+    }
+
+    #[test]
+    fn test_search() {
+        assert_eq!(Search::Finished(2).map(|n| n * 10), Search::Finished(20));
+        assert_eq!(
+            Search::Cancelled(Some(2)).map(|n| n * 10),
+            Search::Cancelled(Some(20))
+        );
+        assert_eq!(Search::Finished(2).finished(), Some(2));
+        assert_eq!(Search::Cancelled(Some(2)).finished(), None);
+    }
+}
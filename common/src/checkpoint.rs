@@ -0,0 +1,144 @@
+//! Saving a long search's progress every so often, so that a run that's
+//! killed partway can be picked up again where it left off. Each search
+//! writes its own state as text; this only deals with where that goes and
+//! when. Checkpoints are off unless a directory has been set with `set_dir`.
+use std::fs;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use crate::info;
+
+static DIR: Mutex<Option<PathBuf>> = Mutex::new(None);
+
+/// Where `Checkpoint::open` keeps checkpoints, or `None` to not keep any.
+pub fn set_dir(dir: Option<PathBuf>) {
+    *DIR.lock().unwrap() = dir;
+}
+
+const SAVE_EVERY: Duration = Duration::from_secs(30);
+
+pub struct Checkpoint {
+    path: Option<PathBuf>,
+    /// Identifies the input being searched, so that a checkpoint left over
+    /// from some other input isn't resumed from.
+    key: String,
+    every: Duration,
+    last_saved: Instant,
+}
+
+impl Checkpoint {
+    /// The checkpoint for the search called `name`, in the directory given
+    /// to `set_dir`. `input` is whatever's being searched, written out as
+    /// text.
+    pub fn open(name: &str, input: &str) -> Checkpoint {
+        let path = DIR.lock().unwrap().as_ref().map(|dir| dir.join(name));
+        Checkpoint::new(path, input)
+    }
+
+    /// A checkpoint kept at `path`, whatever `set_dir` was given.
+    pub fn at(path: PathBuf, input: &str) -> Checkpoint {
+        Checkpoint::new(Some(path), input)
+    }
+
+    fn new(path: Option<PathBuf>, input: &str) -> Checkpoint {
+        Checkpoint {
+            path,
+            key: format!("{:016x}", fnv(input.as_bytes())),
+            every: SAVE_EVERY,
+            last_saved: Instant::now(),
+        }
+    }
+
+    /// How often `due` says to save, rather than every 30 seconds.
+    pub fn every(mut self, every: Duration) -> Checkpoint {
+        self.every = every;
+        self
+    }
+
+    /// The state last saved for this input, if there is one.
+    pub fn load(&self) -> Option<String> {
+        let contents = fs::read_to_string(self.path.as_ref()?).ok()?;
+        let (key, state) = contents.split_once('\n')?;
+        if key != self.key {
+            info!("ignoring checkpoint for a different input");
+            return None;
+        }
+        info!("resuming from checkpoint");
+        Some(state.to_string())
+    }
+
+    /// Whether it's been long enough since the last save to save again.
+    pub fn due(&self) -> bool {
+        self.path.is_some() && self.last_saved.elapsed() >= self.every
+    }
+
+    /// Saves `state`, replacing the last one. A checkpoint that can't be
+    /// written isn't worth stopping the search over, so failures are only
+    /// logged.
+    pub fn save(&mut self, state: &str) {
+        let Some(path) = &self.path else {
+            return;
+        };
+        // Written alongside and then moved into place, so that being killed
+        // mid-write doesn't leave half a checkpoint
+        let partial = path.with_extension("partial");
+        let result = fs::write(&partial, format!("{}\n{}", self.key, state))
+            .and_then(|()| fs::rename(&partial, path));
+        if let Err(e) = result {
+            info!("could not save checkpoint to {}: {}", path.display(), e);
+        }
+        self.last_saved = Instant::now();
+    }
+
+    /// Throws the checkpoint away, once the search has finished.
+    pub fn clear(&self) {
+        if let Some(path) = &self.path {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+// FNV-1a. Unlike `Hash`, which leaves the bytes fed to the hasher up to each
+// type and to the version of Rust, this only ever sees the input's text, so
+// keys stay the same from one build to the next.
+fn fnv(bytes: &[u8]) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325;
+    for &byte in bytes {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_checkpoint() {
+        let path = std::env::temp_dir().join(format!("aoc-checkpoint-{}", std::process::id()));
+        let mut checkpoint = Checkpoint::at(path.clone(), "input");
+        assert_eq!(checkpoint.load(), None);
+        checkpoint.save("state\nmore state");
+        assert_eq!(
+            Checkpoint::at(path.clone(), "input").load(),
+            Some(String::from("state\nmore state"))
+        );
+        assert_eq!(Checkpoint::at(path.clone(), "other input").load(), None);
+        // Saved keys mustn't change from one build to the next
+        assert_eq!(
+            Checkpoint::at(path.clone(), "input").key,
+            "1ebbae8f5810b65b"
+        );
+        checkpoint.clear();
+        assert_eq!(checkpoint.load(), None);
+
+        assert!(!Checkpoint::at(path.clone(), "input").due());
+        assert!(Checkpoint::at(path, "input").every(Duration::ZERO).due());
+        // Without a directory set, there's nowhere to save to
+        assert!(!Checkpoint::open("search", "input")
+            .every(Duration::ZERO)
+            .due());
+    }
+}
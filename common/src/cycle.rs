@@ -0,0 +1,153 @@
+//! Skipping ahead in simulations that settle into a loop. The simulation is
+//! stepped until one of its states comes round again, and from then on
+//! whatever's being measured (a tower's height, say) is assumed to grow by the
+//! same amount every time round.
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// A loop found in a simulation, along with what was measured on the way.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Cycle {
+    /// How many steps it took to first reach the state that repeats.
+    pub offset: usize,
+    /// How many steps it takes to get back to that state.
+    pub period: usize,
+    /// The metric after each step, up to the state coming round again.
+    metrics: Vec<i64>,
+}
+
+impl Cycle {
+    /// What the metric would be after `steps` steps, carrying on the loop
+    /// for as long as it takes.
+    pub fn metric_at(&self, steps: u64) -> i64 {
+        if let Some(&metric) = self.metrics.get(steps as usize) {
+            return metric;
+        }
+        let (offset, period) = (self.offset as u64, self.period as u64);
+        let laps = (steps - offset) / period;
+        let into_lap = (steps - offset) % period;
+        let per_lap = self.metrics[self.offset + self.period] - self.metrics[self.offset];
+        self.metrics[(offset + into_lap) as usize] + laps as i64 * per_lap
+    }
+}
+
+/// Steps `state` along until `key` gives a key it's given before, for at most
+/// `max_steps` steps. `key` can give `None` for states that shouldn't be
+/// compared (ones too close to the start to have settled down, say).
+/// `metric` is measured on the starting state and after every step.
+pub fn find_cycle<S, K>(
+    state: S,
+    step: impl FnMut(&mut S),
+    key: impl FnMut(&S) -> Option<K>,
+    metric: impl FnMut(&S) -> i64,
+    max_steps: u64,
+) -> Option<Cycle>
+where
+    K: Eq + Hash,
+{
+    run(state, step, key, metric, max_steps).ok()
+}
+
+/// The metric after `steps` steps of the simulation, as with `find_cycle`,
+/// but skipping ahead once the states start repeating instead of stepping
+/// through every one.
+pub fn metric_after<S, K>(
+    state: S,
+    step: impl FnMut(&mut S),
+    key: impl FnMut(&S) -> Option<K>,
+    metric: impl FnMut(&S) -> i64,
+    steps: u64,
+) -> i64
+where
+    K: Eq + Hash,
+{
+    match run(state, step, key, metric, steps) {
+        Ok(cycle) => cycle.metric_at(steps),
+        // It got there without going round at all
+        Err(metrics) => *metrics.last().unwrap(),
+    }
+}
+
+// The cycle, or every metric measured if there wasn't one within `max_steps`.
+fn run<S, K>(
+    mut state: S,
+    mut step: impl FnMut(&mut S),
+    mut key: impl FnMut(&S) -> Option<K>,
+    mut metric: impl FnMut(&S) -> i64,
+    max_steps: u64,
+) -> Result<Cycle, Vec<i64>>
+where
+    K: Eq + Hash,
+{
+    let mut seen = HashMap::new();
+    let mut metrics = vec![metric(&state)];
+    for steps in 0..=max_steps as usize {
+        if let Some(key) = key(&state) {
+            if let Some(&offset) = seen.get(&key) {
+                return Ok(Cycle {
+                    offset,
+                    period: steps - offset,
+                    metrics,
+                });
+            }
+            seen.insert(key, steps);
+        }
+        if steps as u64 == max_steps {
+            break;
+        }
+        step(&mut state);
+        metrics.push(metric(&state));
+    }
+    Err(metrics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // Counts up, but the key only sees the count mod 4, starting from 2 so
+    // that it's gone round after 4 steps. The metric is the count itself.
+    fn count_up(max_steps: u64) -> Option<Cycle> {
+        find_cycle(2, |n| *n += 1, |&n| Some(n % 4), |&n| n, max_steps)
+    }
+
+    #[test]
+    fn test_find_cycle() {
+        let cycle = count_up(100).unwrap();
+        assert_eq!((cycle.offset, cycle.period), (0, 4));
+        assert_eq!(cycle.metric_at(3), 5);
+        assert_eq!(cycle.metric_at(1_000_000_000_000), 1_000_000_000_002);
+        assert_eq!(count_up(4), Some(cycle));
+        assert_eq!(count_up(3), None);
+    }
+
+    // 0, 1 and 2 lead into a loop of 3, 4, 5 and 6, with the metric adding
+    // up every number passed
+    fn spiral((n, total): &mut (i64, i64)) {
+        *n = if *n == 6 { 3 } else { *n + 1 };
+        *total += *n;
+    }
+
+    #[test]
+    fn test_offset() {
+        let cycle = find_cycle((0, 0), spiral, |&(n, _)| Some(n), |&(_, t)| t, 100).unwrap();
+        assert_eq!((cycle.offset, cycle.period), (3, 4));
+        for steps in [0, 2, 3, 7, 8, 9, 10, 11, 100] {
+            let mut state = (0, 0);
+            for _ in 0..steps {
+                spiral(&mut state);
+            }
+            assert_eq!(cycle.metric_at(steps), state.1);
+            assert_eq!(
+                metric_after((0, 0), spiral, |&(n, _)| Some(n), |&(_, t)| t, steps),
+                state.1
+            );
+        }
+        // Without any keys it has to step the whole way: 1 to 6, then round
+        // to 3 again
+        assert_eq!(
+            metric_after((0, 0), spiral, |_| None::<i64>, |&(_, t)| t, 7),
+            21 + 3
+        );
+    }
+}
@@ -0,0 +1,158 @@
+//! Differential testing, for puzzles solved twice over (usually a simple
+//! version for part one and a cleverer one for part two). Both are run on
+//! random inputs, and when they disagree the input is shrunk to the smallest
+//! one that still shows the difference.
+use std::panic::{self, AssertUnwindSafe};
+
+use crate::rng::Rng;
+
+/// An input the two implementations gave different answers for. A panic
+/// counts as an answer of its own, as the message it panicked with.
+#[derive(Debug)]
+pub struct Disagreement<T, A> {
+    /// Which seed the unshrunk input came from.
+    pub seed: u64,
+    pub input: T,
+    pub left: Result<A, String>,
+    pub right: Result<A, String>,
+}
+
+/// Runs `left` and `right` on `cases` inputs from `generate`, seeded from
+/// `seed` upwards. `shrink` gives smaller variations of an input to try when
+/// looking for a minimal counterexample.
+pub fn compare<T, A>(
+    seed: u64,
+    cases: u64,
+    generate: impl Fn(&mut Rng) -> T,
+    shrink: impl Fn(&T) -> Vec<T>,
+    left: impl Fn(&T) -> A,
+    right: impl Fn(&T) -> A,
+) -> Result<(), Disagreement<T, A>>
+where
+    A: PartialEq,
+{
+    let run = |input: &T| (catch(|| left(input)), catch(|| right(input)));
+
+    for seed in seed..seed + cases {
+        let mut input = generate(&mut Rng::new(seed));
+        let mut answers = run(&input);
+        if agree(&answers) {
+            continue;
+        }
+        'shrinking: loop {
+            for smaller in shrink(&input) {
+                let smaller_answers = run(&smaller);
+                if !agree(&smaller_answers) {
+                    input = smaller;
+                    answers = smaller_answers;
+                    continue 'shrinking;
+                }
+            }
+            break;
+        }
+        let (left, right) = answers;
+        return Err(Disagreement {
+            seed,
+            input,
+            left,
+            right,
+        });
+    }
+    Ok(())
+}
+
+type Answers<A> = (Result<A, String>, Result<A, String>);
+
+fn agree<A: PartialEq>(answers: &Answers<A>) -> bool {
+    matches!(answers, (Ok(left), Ok(right)) if left == right)
+}
+
+fn catch<A>(f: impl FnOnce() -> A) -> Result<A, String> {
+    panic::catch_unwind(AssertUnwindSafe(f)).map_err(|payload| {
+        if let Some(message) = payload.downcast_ref::<&str>() {
+            message.to_string()
+        } else if let Some(message) = payload.downcast_ref::<String>() {
+            message.clone()
+        } else {
+            String::from("unknown panic")
+        }
+    })
+}
+
+/// `items` with chunks of it taken out: each half, then each single item.
+pub fn shrink_vec<T: Clone>(items: &[T]) -> Vec<Vec<T>> {
+    let mut smaller = Vec::new();
+    if items.len() > 1 {
+        let half = items.len() / 2;
+        smaller.push(items[half..].to_vec());
+        smaller.push(items[..half].to_vec());
+    }
+    for i in 0..items.len() {
+        let mut without = items.to_vec();
+        without.remove(i);
+        smaller.push(without);
+    }
+    smaller
+}
+
+/// Smaller numbers to try in place of `n`, smallest first.
+pub fn shrink_number(n: u64) -> Vec<u64> {
+    let mut smaller = vec![0, n / 2, n.saturating_sub(1)];
+    smaller.dedup();
+    smaller.retain(|&m| m < n);
+    smaller
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn numbers(rng: &mut Rng) -> Vec<u64> {
+        (0..rng.below(20)).map(|_| rng.below(10) as u64).collect()
+    }
+
+    #[test]
+    fn test_agreement() {
+        let sum = |numbers: &Vec<u64>| numbers.iter().sum::<u64>();
+        let looped = |numbers: &Vec<u64>| {
+            let mut total = 0;
+            for n in numbers {
+                total += n;
+            }
+            total
+        };
+        assert!(compare(1, 100, numbers, |n| shrink_vec(n), sum, looped).is_ok());
+    }
+
+    #[test]
+    fn test_shrinking() {
+        // Forgets about any 7s
+        let sum = |numbers: &Vec<u64>| numbers.iter().sum::<u64>();
+        let buggy = |numbers: &Vec<u64>| numbers.iter().filter(|&&n| n != 7).sum::<u64>();
+        let disagreement = compare(1, 100, numbers, |n| shrink_vec(n), sum, buggy).unwrap_err();
+        assert_eq!(disagreement.input, vec![7]);
+        assert_eq!(disagreement.left, Ok(7));
+        assert_eq!(disagreement.right, Ok(0));
+
+        // Panics are answers too
+        let panicky = |numbers: &Vec<u64>| {
+            assert!(numbers.len() < 3, "too many numbers");
+            sum(numbers)
+        };
+        let disagreement = compare(1, 100, numbers, |n| shrink_vec(n), sum, panicky).unwrap_err();
+        assert_eq!(disagreement.input.len(), 3);
+        assert_eq!(disagreement.right, Err(String::from("too many numbers")));
+    }
+
+    #[test]
+    fn test_shrink_helpers() {
+        assert_eq!(
+            shrink_vec(&[1, 2, 3]),
+            vec![vec![2, 3], vec![1], vec![2, 3], vec![1, 3], vec![1, 2]]
+        );
+        assert!(shrink_vec::<u8>(&[]).is_empty());
+        assert_eq!(shrink_number(10), vec![0, 5, 9]);
+        assert_eq!(shrink_number(1), vec![0]);
+        assert!(shrink_number(0).is_empty());
+    }
+}
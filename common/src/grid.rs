@@ -0,0 +1,286 @@
+//! A rectangular grid of cells, for the puzzles whose input is a map drawn
+//! in characters. Points are `(row, column)`, counting from the top left.
+use std::fmt;
+use std::ops::{Index, IndexMut};
+
+use crate::parse::ParseError;
+
+pub type Point = (usize, usize);
+
+#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    /// Row by row.
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Panics if the rows aren't all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Grid<T> {
+        let height = rows.len();
+        let width = rows.first().map_or(0, |row| row.len());
+        assert!(
+            rows.iter().all(|row| row.len() == width),
+            "rows of a grid must all be the same length"
+        );
+        Grid {
+            width,
+            height,
+            cells: rows.into_iter().flatten().collect(),
+        }
+    }
+
+    /// Reads a grid drawn one row to a line, with `cell` turning each
+    /// character into a cell (or `None` if it isn't one). `expected` says
+    /// what a cell should look like, for the error when one doesn't.
+    pub fn parse(
+        data: &str,
+        expected: &str,
+        mut cell: impl FnMut(Point, char) -> Option<T>,
+    ) -> Result<Grid<T>, ParseError> {
+        let mut rows = Vec::new();
+        for (i, line) in data.split('\n').enumerate() {
+            let mut row = Vec::new();
+            for (j, c) in line.chars().enumerate() {
+                let parsed = cell((i, j), c)
+                    .ok_or_else(|| ParseError::new(i + 1, j + 1, expected, format!("`{}`", c)))?;
+                row.push(parsed);
+            }
+            if let Some(first) = rows.first().map(Vec::len) {
+                if row.len() != first {
+                    let width = format!("a row {} wide, like the first", first);
+                    let found = format!("one {} wide", row.len());
+                    return Err(ParseError::new(i + 1, 1, width, found));
+                }
+            }
+            rows.push(row);
+        }
+        Ok(Grid::from_rows(rows))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, (row, column): Point) -> bool {
+        row < self.height && column < self.width
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.contains(point).then(|| &self[point])
+    }
+
+    pub fn get_mut(&mut self, point: Point) -> Option<&mut T> {
+        if self.contains(point) {
+            Some(&mut self[point])
+        } else {
+            None
+        }
+    }
+
+    /// Every point in the grid, row by row.
+    pub fn points(&self) -> impl Iterator<Item = Point> {
+        let width = self.width;
+        (0..self.height).flat_map(move |row| (0..width).map(move |column| (row, column)))
+    }
+
+    /// Every cell along with its point, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.points().zip(self.cells.iter())
+    }
+
+    pub fn iter_mut(&mut self) -> impl Iterator<Item = (Point, &mut T)> {
+        self.points().zip(self.cells.iter_mut())
+    }
+
+    /// The first point whose cell matches, row by row.
+    pub fn position(&self, mut matches: impl FnMut(&T) -> bool) -> Option<Point> {
+        self.iter()
+            .find(|(_, cell)| matches(cell))
+            .map(|(point, _)| point)
+    }
+
+    /// The points above, below, left and right of `point` that are in the
+    /// grid.
+    pub fn neighbors4(&self, point: Point) -> impl Iterator<Item = Point> {
+        self.neighbors(point, &[(-1, 0), (1, 0), (0, -1), (0, 1)])
+    }
+
+    /// Like `neighbors4`, but with the diagonals too.
+    pub fn neighbors8(&self, point: Point) -> impl Iterator<Item = Point> {
+        let offsets = &[
+            (-1, -1),
+            (-1, 0),
+            (-1, 1),
+            (0, -1),
+            (0, 1),
+            (1, -1),
+            (1, 0),
+            (1, 1),
+        ];
+        self.neighbors(point, offsets)
+    }
+
+    fn neighbors(
+        &self,
+        (row, column): Point,
+        offsets: &'static [(isize, isize)],
+    ) -> impl Iterator<Item = Point> {
+        let (width, height) = (self.width, self.height);
+        offsets.iter().filter_map(move |&(down, right)| {
+            let row = row.checked_add_signed(down).filter(|&row| row < height)?;
+            let column = column
+                .checked_add_signed(right)
+                .filter(|&column| column < width)?;
+            Some((row, column))
+        })
+    }
+
+    pub fn row(&self, row: usize) -> &[T] {
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // Not `chunks`, which panics on the width of 0 an empty grid has
+        (0..self.height).map(|row| self.row(row))
+    }
+
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> {
+        assert!(column < self.width, "column {} is outside the grid", column);
+        self.cells.iter().skip(column).step_by(self.width)
+    }
+
+    /// The grid with its rows and columns swapped.
+    pub fn transpose(&self) -> Grid<T>
+    where
+        T: Clone,
+    {
+        Grid {
+            width: self.height,
+            height: self.width,
+            cells: (0..self.width)
+                .flat_map(|column| self.column(column).cloned())
+                .collect(),
+        }
+    }
+
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl Grid<u8> {
+    /// A grid of single digits, like a height map.
+    pub fn digits(data: &str) -> Result<Grid<u8>, ParseError> {
+        Grid::parse(data, "a digit", |_, c| c.to_digit(10).map(|d| d as u8))
+    }
+}
+
+impl<T> Index<Point> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (row, column): Point) -> &T {
+        assert!(column < self.width, "column {} is outside the grid", column);
+        &self.cells[row * self.width + column]
+    }
+}
+
+impl<T> IndexMut<Point> for Grid<T> {
+    fn index_mut(&mut self, (row, column): Point) -> &mut T {
+        assert!(column < self.width, "column {} is outside the grid", column);
+        &mut self.cells[row * self.width + column]
+    }
+}
+
+/// Draws the grid a row to a line, without a newline after the last one.
+impl<T: fmt::Display> fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, row) in self.rows().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            for cell in row {
+                write!(f, "{}", cell)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::digits("123\n456").unwrap();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 4);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.to_string(), "123\n456");
+        assert_eq!(
+            Grid::digits("123\n4x6"),
+            Err(ParseError::new(2, 2, "a digit", "`x`"))
+        );
+        assert_eq!(
+            Grid::digits("123\n45"),
+            Err(ParseError::new(
+                2,
+                1,
+                "a row 3 wide, like the first",
+                "one 2 wide"
+            ))
+        );
+    }
+
+    #[test]
+    fn test_neighbors() {
+        let grid = Grid::new(3, 3, ());
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+        assert_eq!(
+            grid.neighbors8((2, 1)).collect::<Vec<_>>(),
+            vec![(1, 0), (1, 1), (1, 2), (2, 0), (2, 2)]
+        );
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+    }
+
+    #[test]
+    fn test_views() {
+        let grid = Grid::digits("123\n456").unwrap();
+        assert_eq!(grid.row(1), &[4, 5, 6]);
+        assert_eq!(grid.column(2).copied().collect::<Vec<_>>(), vec![3, 6]);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.transpose().to_string(), "14\n25\n36");
+        assert_eq!(grid.position(|&d| d > 4), Some((1, 1)));
+        assert!(grid.map(|&d| d % 2 == 0)[(0, 1)]);
+        assert_eq!(
+            grid.iter().map(|(point, _)| point).collect::<Vec<_>>(),
+            grid.points().collect::<Vec<_>>()
+        );
+    }
+}
@@ -0,0 +1,206 @@
+//! Sets of integers kept as runs of consecutive values, for when there are
+//! far too many values to store one at a time. Ranges are inclusive at both
+//! ends, the way the puzzles write them.
+use std::ops::RangeInclusive;
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct IntervalSet {
+    /// Sorted, with no two runs overlapping or even touching, so that each
+    /// set has only the one way of being written down.
+    runs: Vec<(i64, i64)>,
+}
+
+impl IntervalSet {
+    pub fn new() -> IntervalSet {
+        IntervalSet { runs: Vec::new() }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.runs.is_empty()
+    }
+
+    /// How many values are in the set, across every run.
+    pub fn total_len(&self) -> u64 {
+        self.runs
+            .iter()
+            .map(|&(start, end)| end.abs_diff(start) + 1)
+            .sum()
+    }
+
+    /// The runs of consecutive values, from lowest to highest.
+    pub fn ranges(&self) -> impl Iterator<Item = RangeInclusive<i64>> + '_ {
+        self.runs.iter().map(|&(start, end)| start..=end)
+    }
+
+    /// Adds every value in `range`, merging it with any runs it overlaps or
+    /// touches. An empty range adds nothing.
+    pub fn insert(&mut self, range: RangeInclusive<i64>) {
+        let (mut start, mut end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        // The runs from `first` up to `last` (exclusive) are all swallowed
+        let first = self
+            .runs
+            .partition_point(|&(_, e)| e < start.saturating_sub(1));
+        let last = self
+            .runs
+            .partition_point(|&(s, _)| s <= end.saturating_add(1));
+        if first < last {
+            start = start.min(self.runs[first].0);
+            end = end.max(self.runs[last - 1].1);
+        }
+        self.runs.splice(first..last, [(start, end)]);
+    }
+
+    /// Takes every value in `range` out of the set.
+    pub fn remove(&mut self, range: RangeInclusive<i64>) {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return;
+        }
+        let first = self.runs.partition_point(|&(_, e)| e < start);
+        let last = self.runs.partition_point(|&(s, _)| s <= end);
+        if first == last {
+            return;
+        }
+        // Whatever's left of the outermost runs either side of `range`
+        let mut kept = Vec::new();
+        if self.runs[first].0 < start {
+            kept.push((self.runs[first].0, start - 1));
+        }
+        if self.runs[last - 1].1 > end {
+            kept.push((end + 1, self.runs[last - 1].1));
+        }
+        self.runs.splice(first..last, kept);
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        self.contains_range(value..=value)
+    }
+
+    /// Whether every value in `range` is in the set. An empty range is
+    /// always contained.
+    pub fn contains_range(&self, range: RangeInclusive<i64>) -> bool {
+        let (start, end) = range.into_inner();
+        if start > end {
+            return true;
+        }
+        let i = self.runs.partition_point(|&(_, e)| e < start);
+        self.runs
+            .get(i)
+            .is_some_and(|&(s, e)| s <= start && end <= e)
+    }
+
+    /// Whether every value in `other` is also in this set.
+    pub fn is_superset(&self, other: &IntervalSet) -> bool {
+        other.ranges().all(|range| self.contains_range(range))
+    }
+
+    pub fn union(&self, other: &IntervalSet) -> IntervalSet {
+        let mut union = self.clone();
+        for range in other.ranges() {
+            union.insert(range);
+        }
+        union
+    }
+
+    pub fn intersection(&self, other: &IntervalSet) -> IntervalSet {
+        let mut intersection = self.clone();
+        for gap in other.gaps(i64::MIN..=i64::MAX).ranges() {
+            intersection.remove(gap);
+        }
+        intersection
+    }
+
+    /// The values in this set that aren't in `other`.
+    pub fn difference(&self, other: &IntervalSet) -> IntervalSet {
+        let mut difference = self.clone();
+        for range in other.ranges() {
+            difference.remove(range);
+        }
+        difference
+    }
+
+    /// The values within `within` that aren't in the set.
+    pub fn gaps(&self, within: RangeInclusive<i64>) -> IntervalSet {
+        let mut gaps = IntervalSet::from(within);
+        for range in self.ranges() {
+            gaps.remove(range);
+        }
+        gaps
+    }
+}
+
+impl From<RangeInclusive<i64>> for IntervalSet {
+    fn from(range: RangeInclusive<i64>) -> IntervalSet {
+        let mut set = IntervalSet::new();
+        set.insert(range);
+        set
+    }
+}
+
+impl FromIterator<RangeInclusive<i64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = RangeInclusive<i64>>>(ranges: I) -> IntervalSet {
+        let mut set = IntervalSet::new();
+        for range in ranges {
+            set.insert(range);
+        }
+        set
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ranges(set: &IntervalSet) -> Vec<RangeInclusive<i64>> {
+        set.ranges().collect()
+    }
+
+    #[test]
+    fn test_insert_merges() {
+        let mut set: IntervalSet = [10..=12, 1..=3, 20..=25].into_iter().collect();
+        assert_eq!(ranges(&set), vec![1..=3, 10..=12, 20..=25]);
+        // Touching counts, since there's no value between 3 and 4
+        set.insert(4..=5);
+        assert_eq!(ranges(&set), vec![1..=5, 10..=12, 20..=25]);
+        set.insert(11..=21);
+        assert_eq!(ranges(&set), vec![1..=5, 10..=25]);
+        // Backwards, so empty
+        let (start, end) = (7, 6);
+        set.insert(start..=end);
+        assert_eq!(set.total_len(), 5 + 16);
+        set.insert(-100..=100);
+        assert_eq!(ranges(&set), vec![-100..=100]);
+    }
+
+    #[test]
+    fn test_remove_and_gaps() {
+        let mut set = IntervalSet::from(0..=20);
+        set.remove(5..=7);
+        set.remove(15..=30);
+        assert_eq!(ranges(&set), vec![0..=4, 8..=14]);
+        set.remove(-3..=0);
+        assert_eq!(ranges(&set), vec![1..=4, 8..=14]);
+        assert_eq!(ranges(&set.gaps(0..=20)), vec![0..=0, 5..=7, 15..=20]);
+        assert_eq!(ranges(&set.gaps(2..=3)), vec![]);
+
+        let other: IntervalSet = [3..=9].into_iter().collect();
+        assert_eq!(ranges(&set.difference(&other)), vec![1..=2, 10..=14]);
+        assert_eq!(ranges(&set.intersection(&other)), vec![3..=4, 8..=9]);
+        assert_eq!(ranges(&set.union(&other)), vec![1..=14]);
+    }
+
+    #[test]
+    fn test_containment() {
+        let set: IntervalSet = [1..=4, 8..=14].into_iter().collect();
+        assert!(set.contains(1));
+        assert!(!set.contains(5));
+        assert!(set.contains_range(9..=14));
+        assert!(!set.contains_range(3..=9));
+        assert!(set.is_superset(&IntervalSet::from(2..=3)));
+        assert!(!IntervalSet::from(2..=3).is_superset(&set));
+        assert!(set.is_superset(&IntervalSet::new()));
+    }
+}
@@ -0,0 +1,21 @@
+//! Pieces shared between the solutions for every year.
+pub mod cancel;
+pub mod checkpoint;
+pub mod cycle;
+pub mod differential;
+pub mod grid;
+pub mod interval;
+pub mod log;
+pub mod parse;
+pub mod pathfinding;
+pub mod rng;
+pub mod screen;
+pub mod sparse;
+
+/// Which half of a day's puzzle to solve, for days where both halves share
+/// most of their code.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Part {
+    One,
+    Two,
+}
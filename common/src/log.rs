@@ -0,0 +1,183 @@
+//! Diagnostics for the slower solutions. Everything goes to stderr, so the
+//! answers on stdout stay easy to pick out, and nothing is written at all
+//! unless the level has been turned up from `Quiet`.
+use std::fmt;
+use std::io::{self, Write};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Level {
+    Quiet,
+    /// Progress bars and the odd milestone.
+    Info,
+    /// Intermediate results.
+    Debug,
+    /// Anything else that might help, however often it comes up.
+    Trace,
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Level, String> {
+        match s {
+            "quiet" => Ok(Level::Quiet),
+            "info" => Ok(Level::Info),
+            "debug" => Ok(Level::Debug),
+            "trace" => Ok(Level::Trace),
+            _ => Err(format!("invalid log level: {}", s)),
+        }
+    }
+}
+
+static LEVEL: AtomicU8 = AtomicU8::new(Level::Quiet as u8);
+
+pub fn set_level(level: Level) {
+    LEVEL.store(level as u8, Ordering::Relaxed);
+}
+
+pub fn enabled(level: Level) -> bool {
+    level != Level::Quiet && level as u8 <= LEVEL.load(Ordering::Relaxed)
+}
+
+#[doc(hidden)]
+pub fn write(level: Level, args: fmt::Arguments) {
+    if enabled(level) {
+        let _ = writeln!(io::stderr(), "{}", args);
+    }
+}
+
+#[macro_export]
+macro_rules! info {
+    ($($arg:tt)*) => {
+        $crate::log::write($crate::log::Level::Info, format_args!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! debug {
+    ($($arg:tt)*) => {
+        $crate::log::write($crate::log::Level::Debug, format_args!($($arg)*))
+    };
+}
+
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        $crate::log::write($crate::log::Level::Trace, format_args!($($arg)*))
+    };
+}
+
+// Redrawing on every step would spend more time on the terminal than on the
+// puzzle
+const REDRAW_EVERY: Duration = Duration::from_millis(100);
+
+/// A progress bar on stderr, shown at `Info` and up. Without a known total
+/// it's just a running count. The line is finished off when it's dropped.
+pub struct Progress {
+    label: String,
+    done: u64,
+    total: Option<u64>,
+    last_drawn: Option<Instant>,
+}
+
+impl Progress {
+    pub fn new(label: &str, total: u64) -> Progress {
+        Progress {
+            label: label.to_string(),
+            done: 0,
+            total: Some(total),
+            last_drawn: None,
+        }
+    }
+
+    pub fn counter(label: &str) -> Progress {
+        Progress {
+            label: label.to_string(),
+            done: 0,
+            total: None,
+            last_drawn: None,
+        }
+    }
+
+    pub fn inc(&mut self) {
+        self.done += 1;
+        if enabled(Level::Info)
+            && self
+                .last_drawn
+                .is_none_or(|drawn| drawn.elapsed() >= REDRAW_EVERY)
+        {
+            self.draw();
+        }
+    }
+
+    fn draw(&mut self) {
+        let _ = write!(io::stderr(), "\r{}", self);
+        self.last_drawn = Some(Instant::now());
+    }
+}
+
+impl fmt::Display for Progress {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.total {
+            Some(total) => {
+                const WIDTH: u64 = 30;
+                let filled = (self.done.min(total) * WIDTH / total.max(1)) as usize;
+                write!(
+                    f,
+                    "{} [{}{}] {}/{}",
+                    self.label,
+                    "#".repeat(filled),
+                    " ".repeat(WIDTH as usize - filled),
+                    self.done,
+                    total
+                )
+            }
+            None => write!(f, "{}: {}", self.label, self.done),
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        if self.last_drawn.is_some() {
+            self.draw();
+            let _ = writeln!(io::stderr());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_level() {
+        assert_eq!("debug".parse(), Ok(Level::Debug));
+        assert!("loud".parse::<Level>().is_err());
+        assert!(Level::Quiet < Level::Info && Level::Debug < Level::Trace);
+        // Quiet is only ever a setting, never something to log at
+        assert!(!enabled(Level::Quiet));
+    }
+
+    #[test]
+    fn test_progress() {
+        let mut progress = Progress::new("scanning", 4);
+        assert_eq!(
+            progress.to_string(),
+            format!("scanning [{}] 0/4", " ".repeat(30))
+        );
+        progress.inc();
+        assert_eq!(
+            progress.to_string(),
+            format!("scanning [{}{}] 1/4", "#".repeat(7), " ".repeat(23))
+        );
+
+        let mut counter = Progress::counter("states");
+        counter.inc();
+        counter.inc();
+        assert_eq!(counter.to_string(), "states: 2");
+    }
+}
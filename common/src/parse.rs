@@ -0,0 +1,293 @@
+//! Input parsing that reports where and why an input didn't match, rather
+//! than panicking on an `unwrap` or an out-of-range slice.
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// Where parsing went wrong, and what was expected there. Lines and columns
+/// count from 1.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub expected: String,
+    pub found: String,
+}
+
+impl ParseError {
+    pub fn new(
+        line: usize,
+        column: usize,
+        expected: impl Into<String>,
+        found: impl Into<String>,
+    ) -> ParseError {
+        ParseError {
+            line,
+            column,
+            expected: expected.into(),
+            found: found.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: expected {}, found {}",
+            self.line, self.column, self.expected, self.found
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+/// Reads one line of input from left to right.
+#[derive(Clone, Debug)]
+pub struct Scanner<'a> {
+    line: usize,
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Scanner<'a> {
+    /// `line` is the line's number in the whole input, for error messages.
+    pub fn new(line: usize, text: &'a str) -> Scanner<'a> {
+        Scanner { line, text, pos: 0 }
+    }
+
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn column(&self) -> usize {
+        self.text[..self.pos].chars().count() + 1
+    }
+
+    /// Everything not consumed yet.
+    pub fn remaining(&self) -> &'a str {
+        &self.text[self.pos..]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.remaining().is_empty()
+    }
+
+    /// An error at the current position, describing what's there.
+    pub fn error(&self, expected: impl Into<String>) -> ParseError {
+        let rest = self.remaining();
+        let found = match rest.chars().next() {
+            None => String::from("end of line"),
+            Some(ch) if ch.is_whitespace() => String::from("whitespace"),
+            Some(_) => format!("`{}`", rest.split_whitespace().next().unwrap()),
+        };
+        ParseError::new(self.line, self.column(), expected, found)
+    }
+
+    /// Consumes `literal`, which must come next.
+    pub fn expect(&mut self, literal: &str) -> Result<(), ParseError> {
+        if self.eat(literal) {
+            return Ok(());
+        }
+        // Show what's there instead, lined up against what should have been
+        let found: String = self.remaining().chars().take(literal.chars().count()).collect();
+        let found = if found.is_empty() {
+            String::from("end of line")
+        } else {
+            format!("`{}`", found)
+        };
+        let expected = format!("`{}`", literal);
+        Err(ParseError::new(self.line, self.column(), expected, found))
+    }
+
+    /// Consumes `literal` if it comes next.
+    pub fn eat(&mut self, literal: &str) -> bool {
+        let matches = self.remaining().starts_with(literal);
+        if matches {
+            self.pos += literal.len();
+        }
+        matches
+    }
+
+    /// Consumes a run of characters matching `pred`, which may be empty.
+    pub fn take_while(&mut self, pred: impl Fn(char) -> bool) -> &'a str {
+        let rest = self.remaining();
+        let len = rest.find(|ch| !pred(ch)).unwrap_or(rest.len());
+        self.pos += len;
+        &rest[..len]
+    }
+
+    /// Consumes an integer, with an optional leading `-`.
+    pub fn number<T: FromStr>(&mut self) -> Result<T, ParseError> {
+        let start = self.pos;
+        self.eat("-");
+        let digits = self.take_while(|ch| ch.is_ascii_digit());
+        let number = &self.text[start..self.pos];
+        match number.parse() {
+            Ok(number) if !digits.is_empty() => Ok(number),
+            _ => {
+                self.pos = start;
+                Err(self.error("a number"))
+            }
+        }
+    }
+
+    /// Consumes a run of letters and digits.
+    pub fn word(&mut self) -> Result<&'a str, ParseError> {
+        let word = self.take_while(|ch| ch.is_alphanumeric());
+        if word.is_empty() {
+            Err(self.error("a word"))
+        } else {
+            Ok(word)
+        }
+    }
+
+    /// Consumes a single character, which has to be one of `chars`.
+    pub fn one_of(&mut self, chars: &str) -> Result<char, ParseError> {
+        match self.remaining().chars().next() {
+            Some(ch) if chars.contains(ch) => {
+                self.pos += ch.len_utf8();
+                Ok(ch)
+            }
+            _ => Err(self.error(format!("one of `{}`", chars))),
+        }
+    }
+
+    /// Parses one or more items separated by `separator`.
+    pub fn list<T>(
+        &mut self,
+        separator: &str,
+        mut item: impl FnMut(&mut Scanner<'a>) -> Result<T, ParseError>,
+    ) -> Result<Vec<T>, ParseError> {
+        let mut items = vec![item(self)?];
+        while self.eat(separator) {
+            items.push(item(self)?);
+        }
+        Ok(items)
+    }
+
+    /// Succeeds only if the whole line has been consumed.
+    pub fn end(&self) -> Result<(), ParseError> {
+        if self.is_empty() {
+            Ok(())
+        } else {
+            Err(self.error("end of line"))
+        }
+    }
+}
+
+/// Hands out a `Scanner` for each line of an input in turn.
+pub struct Lines<'a> {
+    lines: std::iter::Enumerate<std::str::Split<'a, char>>,
+    last_line: usize,
+}
+
+impl<'a> Lines<'a> {
+    pub fn new(input: &'a str) -> Lines<'a> {
+        Lines {
+            lines: input.split('\n').enumerate(),
+            last_line: 0,
+        }
+    }
+
+    /// The next line, which has to be there.
+    pub fn next_line(&mut self) -> Result<Scanner<'a>, ParseError> {
+        self.next()
+            .ok_or_else(|| ParseError::new(self.last_line + 1, 1, "another line", "end of input"))
+    }
+}
+
+impl<'a> Iterator for Lines<'a> {
+    type Item = Scanner<'a>;
+
+    fn next(&mut self) -> Option<Scanner<'a>> {
+        let (i, line) = self.lines.next()?;
+        self.last_line = i + 1;
+        Some(Scanner::new(i + 1, line.strip_suffix('\r').unwrap_or(line)))
+    }
+}
+
+/// Parses every line of `input` with `parse_line`.
+pub fn lines<'a, T>(
+    input: &'a str,
+    mut parse_line: impl FnMut(&mut Scanner<'a>) -> Result<T, ParseError>,
+) -> Result<Vec<T>, ParseError> {
+    Lines::new(input)
+        .map(|mut line| {
+            let item = parse_line(&mut line)?;
+            line.end()?;
+            Ok(item)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scanner() {
+        let mut line = Scanner::new(3, "Sensor at x=-2, y=18: valves AA, BB");
+        line.expect("Sensor at x=").unwrap();
+        assert_eq!(line.number::<i32>(), Ok(-2));
+        line.expect(", y=").unwrap();
+        assert_eq!(line.number::<u32>(), Ok(18));
+        assert!(!line.eat(";"));
+        assert!(line.eat(": valves "));
+        assert_eq!(line.list(", ", |l| l.word()), Ok(vec!["AA", "BB"]));
+        assert_eq!(line.end(), Ok(()));
+
+        let mut line = Scanner::new(1, "R 4");
+        assert_eq!(line.one_of("UDLR"), Ok('R'));
+        assert_eq!(
+            line.one_of("UDLR"),
+            Err(ParseError::new(1, 2, "one of `UDLR`", "whitespace"))
+        );
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut line = Scanner::new(2, "x=12, y=abc");
+        assert_eq!(
+            line.expect("y="),
+            Err(ParseError::new(2, 1, "`y=`", "`x=`"))
+        );
+        line.expect("x=").unwrap();
+        line.number::<i32>().unwrap();
+        line.expect(", y=").unwrap();
+        let error = line.number::<i32>().unwrap_err();
+        assert_eq!(error, ParseError::new(2, 9, "a number", "`abc`"));
+        assert_eq!(
+            error.to_string(),
+            "line 2, column 9: expected a number, found `abc`"
+        );
+        // Failing to parse doesn't consume anything
+        assert_eq!(line.word(), Ok("abc"));
+        assert_eq!(
+            line.word(),
+            Err(ParseError::new(2, 12, "a word", "end of line"))
+        );
+        assert_eq!(Scanner::new(1, "-").number::<i32>().unwrap_err().column, 1);
+        assert_eq!(
+            Scanner::new(1, "300").number::<u8>().unwrap_err().found,
+            "`300`"
+        );
+    }
+
+    #[test]
+    fn test_lines() {
+        assert_eq!(lines("1\n2\n3", |l| l.number::<u8>()), Ok(vec![1, 2, 3]));
+        assert_eq!(
+            lines("1\n2x\n3", |l| l.number::<u8>()),
+            Err(ParseError::new(2, 2, "end of line", "`x`"))
+        );
+
+        let mut input = Lines::new("a\r\nb");
+        assert_eq!(input.next_line().unwrap().remaining(), "a");
+        assert_eq!(input.next_line().unwrap().remaining(), "b");
+        assert_eq!(
+            input.next_line().unwrap_err(),
+            ParseError::new(3, 1, "another line", "end of input")
+        );
+    }
+}
@@ -0,0 +1,205 @@
+//! Shortest paths through a graph. Graphs aren't built up front: each search
+//! is given a closure that lists a node's neighbors (and, for the weighted
+//! searches, what it costs to step to each), so a grid, a map of named
+//! valves or anything else can be searched as it stands.
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// A path found by a search, from the start to the goal inclusive.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Path<N, C> {
+    pub nodes: Vec<N>,
+    pub cost: C,
+}
+
+/// Breadth-first search, for when every step costs the same. The path's cost
+/// is its number of steps.
+pub fn bfs<N, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, usize>>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut came_from = HashMap::new();
+    let mut to_visit = VecDeque::from([start.clone()]);
+    while let Some(node) = to_visit.pop_front() {
+        if is_goal(&node) {
+            let nodes = reconstruct(&came_from, node);
+            return Some(Path {
+                cost: nodes.len() - 1,
+                nodes,
+            });
+        }
+        for next in neighbors(&node) {
+            if next != start && !came_from.contains_key(&next) {
+                came_from.insert(next.clone(), node.clone());
+                to_visit.push_back(next);
+            }
+        }
+    }
+    None
+}
+
+/// Dijkstra's algorithm, for steps with differing (but never negative)
+/// costs.
+pub fn dijkstra<N, C, I>(
+    start: N,
+    neighbors: impl FnMut(&N) -> I,
+    is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    astar(start, neighbors, |_| C::default(), is_goal)
+}
+
+/// A*, which is Dijkstra's algorithm steered towards the goal by
+/// `heuristic`. So that the path found is still the cheapest, the heuristic
+/// must never guess more than it would really cost to get to the goal.
+pub fn astar<N, C, I>(
+    start: N,
+    mut neighbors: impl FnMut(&N) -> I,
+    mut heuristic: impl FnMut(&N) -> C,
+    mut is_goal: impl FnMut(&N) -> bool,
+) -> Option<Path<N, C>>
+where
+    N: Clone + Eq + Hash,
+    C: Copy + Ord + Add<Output = C> + Default,
+    I: IntoIterator<Item = (N, C)>,
+{
+    let mut best = HashMap::from([(start.clone(), C::default())]);
+    let mut came_from = HashMap::new();
+    let mut to_visit = BinaryHeap::from([Visit {
+        estimate: heuristic(&start),
+        cost: C::default(),
+        node: start,
+    }]);
+    while let Some(Visit { cost, node, .. }) = to_visit.pop() {
+        // Nodes are queued again whenever a cheaper way to them turns up,
+        // rather than updated in place, so skip the stale ones
+        if best.get(&node).is_some_and(|&best| cost > best) {
+            continue;
+        }
+        if is_goal(&node) {
+            return Some(Path {
+                nodes: reconstruct(&came_from, node),
+                cost,
+            });
+        }
+        for (next, step) in neighbors(&node) {
+            let next_cost = cost + step;
+            if best.get(&next).is_none_or(|&best| next_cost < best) {
+                best.insert(next.clone(), next_cost);
+                came_from.insert(next.clone(), node.clone());
+                to_visit.push(Visit {
+                    estimate: next_cost + heuristic(&next),
+                    cost: next_cost,
+                    node: next,
+                });
+            }
+        }
+    }
+    None
+}
+
+// Follows `came_from` back from `goal` to the start, which is the one node
+// that didn't come from anywhere.
+fn reconstruct<N: Clone + Eq + Hash>(came_from: &HashMap<N, N>, goal: N) -> Vec<N> {
+    let mut nodes = vec![goal];
+    while let Some(previous) = came_from.get(nodes.last().unwrap()) {
+        nodes.push(previous.clone());
+    }
+    nodes.reverse();
+    nodes
+}
+
+// A node waiting to be visited. `BinaryHeap` pops the greatest first, so
+// these are ordered backwards to pop the lowest estimate first.
+struct Visit<N, C> {
+    estimate: C,
+    cost: C,
+    node: N,
+}
+
+impl<N, C: Ord> Ord for Visit<N, C> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.estimate.cmp(&self.estimate)
+    }
+}
+
+impl<N, C: Ord> PartialOrd for Visit<N, C> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<N, C: Ord> PartialEq for Visit<N, C> {
+    fn eq(&self, other: &Self) -> bool {
+        self.estimate == other.estimate
+    }
+}
+
+impl<N, C: Ord> Eq for Visit<N, C> {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A line of nodes 0 to 9, where stepping up costs the node stepped to
+    // and there's also a costly shortcut from 0 straight to 9
+    fn line(&node: &u32) -> Vec<(u32, u32)> {
+        let mut next = Vec::new();
+        if node < 9 {
+            next.push((node + 1, node + 1));
+        }
+        if node == 0 {
+            next.push((9, 100));
+        }
+        next
+    }
+
+    #[test]
+    fn test_bfs() {
+        let path = bfs(
+            0,
+            |node| line(node).into_iter().map(|(n, _)| n),
+            |&n| n == 9,
+        );
+        assert_eq!(
+            path,
+            Some(Path {
+                nodes: vec![0, 9],
+                cost: 1
+            })
+        );
+        let path = bfs(0, |&n| [n + 1], |&n| n == 3).unwrap();
+        assert_eq!(path.nodes, vec![0, 1, 2, 3]);
+        assert_eq!(bfs(0, |_| [], |&n| n == 1), None);
+        // The start can be the goal
+        assert_eq!(bfs(5, |&n| [n + 1], |&n| n == 5).unwrap().cost, 0);
+    }
+
+    #[test]
+    fn test_dijkstra() {
+        let path = dijkstra(0, line, |&n| n == 9).unwrap();
+        assert_eq!(path.nodes, (0..=9).collect::<Vec<_>>());
+        assert_eq!(path.cost, 45);
+        assert_eq!(dijkstra(0, line, |&n| n == 10), None);
+    }
+
+    #[test]
+    fn test_astar() {
+        // How far from 9 a node is, which never overestimates since every
+        // step along the line costs at least 1
+        let path = astar(0, line, |&n| 9 - n, |&n| n == 9).unwrap();
+        assert_eq!(path.cost, 45);
+        assert_eq!(path.nodes.len(), 10);
+    }
+}
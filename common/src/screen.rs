@@ -0,0 +1,83 @@
+//! Reading the letters that some puzzles draw on a screen of lit (`#`) and
+//! dark (`.`) cells. Every letter is four cells wide and six tall, with a dark
+//! column between one letter and the next.
+
+const WIDTH: usize = 4;
+const HEIGHT: usize = 6;
+
+// Each letter's cells, row by row
+const LETTERS: &[(char, &str)] = &[
+    ('A', ".##.#..##..######..##..#"),
+    ('B', "###.#..####.#..##..####."),
+    ('C', ".##.#..##...#...#..#.##."),
+    ('E', "#####...###.#...#...####"),
+    ('F', "#####...###.#...#...#..."),
+    ('G', ".##.#..##...#.###..#.###"),
+    ('H', "#..##..######..##..##..#"),
+    ('J', "..##...#...#...##..#.##."),
+    ('K', "#..##.#.##..#.#.#.#.#..#"),
+    ('L', "#...#...#...#...#...####"),
+    ('O', ".##.#..##..##..##..#.##."),
+    ('P', "###.#..##..####.#...#..."),
+    ('R', "###.#..##..####.#.#.#..#"),
+    ('S', ".####...#....##....####."),
+    ('U', "#..##..##..##..##..#.##."),
+    ('Z', "####...#..#..#..#...####"),
+];
+
+/// The letters drawn on `screen`, or `None` if it isn't six rows of letters
+/// that are all known. Dark rows and columns around the letters are ignored.
+pub fn read(screen: &str) -> Option<String> {
+    let rows: Vec<&[u8]> = screen
+        .lines()
+        .map(str::as_bytes)
+        .filter(|row| row.contains(&b'#'))
+        .collect();
+    if rows.len() != HEIGHT {
+        return None;
+    }
+    let lit = |x: usize| rows.iter().any(|row| row.get(x) == Some(&b'#'));
+    let width = rows.iter().map(|row| row.len()).max()?;
+
+    let mut letters = String::new();
+    let mut x = (0..width).find(|&x| lit(x))?;
+    while (x..width).any(lit) {
+        let cells: String = rows
+            .iter()
+            .flat_map(|row| {
+                (x..x + WIDTH).map(|x| if row.get(x) == Some(&b'#') { '#' } else { '.' })
+            })
+            .collect();
+        let &(letter, _) = LETTERS.iter().find(|(_, drawn)| *drawn == cells)?;
+        letters.push(letter);
+        x += WIDTH + 1;
+    }
+    Some(letters)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_read() {
+        let screen = "\
+            ......................\n\
+            .#..#.####.###...##...\n\
+            .#..#....#.#..#.#..#..\n\
+            .####...#..#..#.#.....\n\
+            .#..#..#...###..#.##..\n\
+            .#..#.#....#.#..#..#..\n\
+            .#..#.####.#..#..###..\n";
+        assert_eq!(read(screen), Some(String::from("HZRG")));
+    }
+
+    #[test]
+    fn test_unreadable() {
+        // The example from 2021 day 13 is a square, not a letter
+        let square = "#####\n#...#\n#...#\n#...#\n#####\n.....\n.....";
+        assert_eq!(read(square), None);
+        assert_eq!(read("#..#\n#..#\n####"), None);
+        assert_eq!(read(""), None);
+    }
+}
@@ -0,0 +1,216 @@
+//! A grid with no edges, for simulations that spread out as far as they like
+//! (or start out far from the origin). Only the points that have something
+//! at them are stored. Unlike `grid::Grid`, points are `(x, y)`, with `y`
+//! growing downwards when drawn.
+use std::collections::HashMap;
+
+pub type Point = (i64, i64);
+
+/// The smallest and largest `x` and `y` of any point, as the top left and
+/// bottom right corners.
+pub type Bounds = (Point, Point);
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct SparseGrid<T = ()> {
+    cells: HashMap<Point, T>,
+    /// Kept up to date as points come and go, rather than worked out again
+    /// every time it's asked for.
+    bounds: Option<Bounds>,
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> SparseGrid<T> {
+        SparseGrid {
+            cells: HashMap::new(),
+            bounds: None,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    pub fn contains(&self, point: Point) -> bool {
+        self.cells.contains_key(&point)
+    }
+
+    pub fn get(&self, point: Point) -> Option<&T> {
+        self.cells.get(&point)
+    }
+
+    /// Puts `value` at `point`, returning whatever was there before.
+    pub fn insert(&mut self, point: Point, value: T) -> Option<T> {
+        self.bounds = Some(match self.bounds {
+            None => (point, point),
+            Some(((min_x, min_y), (max_x, max_y))) => (
+                (min_x.min(point.0), min_y.min(point.1)),
+                (max_x.max(point.0), max_y.max(point.1)),
+            ),
+        });
+        self.cells.insert(point, value)
+    }
+
+    /// The value at `point`, starting from the default if there isn't one
+    /// yet, for counting things up.
+    pub fn entry(&mut self, point: Point) -> &mut T
+    where
+        T: Default,
+    {
+        if !self.contains(point) {
+            self.insert(point, T::default());
+        }
+        self.cells.get_mut(&point).unwrap()
+    }
+
+    pub fn remove(&mut self, point: Point) -> Option<T> {
+        let removed = self.cells.remove(&point)?;
+        // Only a point on the edge can make the bounds any smaller
+        if let Some(((min_x, min_y), (max_x, max_y))) = self.bounds {
+            if point.0 == min_x || point.0 == max_x || point.1 == min_y || point.1 == max_y {
+                self.bounds = bounds_of(self.cells.keys().copied());
+            }
+        }
+        Some(removed)
+    }
+
+    pub fn points(&self) -> impl Iterator<Item = Point> + '_ {
+        self.cells.keys().copied()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Point, &T)> {
+        self.cells.iter().map(|(&point, value)| (point, value))
+    }
+
+    /// `None` when there aren't any points.
+    pub fn bounds(&self) -> Option<Bounds> {
+        self.bounds
+    }
+
+    /// How many points the bounds cover, whether there's anything at them
+    /// or not.
+    pub fn bounds_area(&self) -> u64 {
+        self.bounds.map_or(0, |((min_x, min_y), (max_x, max_y))| {
+            (max_x - min_x + 1) as u64 * (max_y - min_y + 1) as u64
+        })
+    }
+
+    /// How many of the eight points around `point` have something at them.
+    pub fn count_neighbors(&self, (x, y): Point) -> usize {
+        let mut count = 0;
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if (dx, dy) != (0, 0) && self.contains((x + dx, y + dy)) {
+                    count += 1;
+                }
+            }
+        }
+        count
+    }
+
+    /// Draws everything within the bounds a row to a line, with `cell`
+    /// choosing the character for each point (and `None` for the empty
+    /// ones).
+    pub fn render(&self, mut cell: impl FnMut(Option<&T>) -> char) -> String {
+        let Some(((min_x, min_y), (max_x, max_y))) = self.bounds else {
+            return String::new();
+        };
+        let mut drawn = String::new();
+        for y in min_y..=max_y {
+            if y > min_y {
+                drawn.push('\n');
+            }
+            for x in min_x..=max_x {
+                drawn.push(cell(self.get((x, y))));
+            }
+        }
+        drawn
+    }
+}
+
+impl SparseGrid {
+    /// Adds `point` to the set, returning whether it's new.
+    pub fn add(&mut self, point: Point) -> bool {
+        self.insert(point, ()).is_none()
+    }
+}
+
+fn bounds_of(mut points: impl Iterator<Item = Point>) -> Option<Bounds> {
+    let first = points.next()?;
+    Some(points.fold(
+        (first, first),
+        |((min_x, min_y), (max_x, max_y)), (x, y)| {
+            ((min_x.min(x), min_y.min(y)), (max_x.max(x), max_y.max(y)))
+        },
+    ))
+}
+
+impl FromIterator<Point> for SparseGrid {
+    fn from_iter<I: IntoIterator<Item = Point>>(points: I) -> SparseGrid {
+        let mut set = SparseGrid::new();
+        for point in points {
+            set.add(point);
+        }
+        set
+    }
+}
+
+impl<T> FromIterator<(Point, T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = (Point, T)>>(cells: I) -> SparseGrid<T> {
+        let mut grid = SparseGrid::new();
+        for (point, value) in cells {
+            grid.insert(point, value);
+        }
+        grid
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_bounds() {
+        let mut set = SparseGrid::new();
+        assert_eq!(set.bounds(), None);
+        assert_eq!(set.bounds_area(), 0);
+        assert!(set.add((0, 0)));
+        assert!(!set.add((0, 0)));
+        set.add((-5, 2));
+        set.add((3_000_000_000, -1));
+        assert_eq!(set.bounds(), Some(((-5, -1), (3_000_000_000, 2))));
+        assert_eq!(set.bounds_area(), 3_000_000_006 * 4);
+
+        set.remove((3_000_000_000, -1));
+        assert_eq!(set.bounds(), Some(((-5, 0), (0, 2))));
+        // Nowhere near the edge, so the bounds stay put
+        set.add((-2, 1));
+        set.remove((-2, 1));
+        assert_eq!(set.bounds(), Some(((-5, 0), (0, 2))));
+        set.remove((0, 0));
+        set.remove((-5, 2));
+        assert_eq!(set.bounds(), None);
+    }
+
+    #[test]
+    fn test_neighbors_and_render() {
+        let set: SparseGrid = [(0, 0), (1, 0), (1, 1), (3, 1)].into_iter().collect();
+        assert_eq!(set.count_neighbors((0, 0)), 2);
+        assert_eq!(set.count_neighbors((2, 0)), 3);
+        assert_eq!(set.count_neighbors((3, 1)), 0);
+        let drawn = set.render(|cell| if cell.is_some() { '#' } else { '.' });
+        assert_eq!(drawn, "##..\n.#.#");
+
+        let mut counts = SparseGrid::new();
+        *counts.entry((-1, 0)) += 1;
+        *counts.entry((-1, 0)) += 1;
+        *counts.entry((1, 0)) += 1;
+        assert_eq!(
+            counts.render(|count| count.map_or('.', |n| (b'0' + n) as char)),
+            "2.1"
+        );
+    }
+}